@@ -0,0 +1,88 @@
+use std::{
+    env, fs,
+    path::Path,
+    process::{Command, ExitStatus},
+};
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(generic_member_access)");
+
+    // https://github.com/rust-lang/rust/issues/99301 [nightly]
+    match compile_probe(GENERIC_MEMBER_ACCESS_PROBE) {
+        Some(status) if status.success() => println!("cargo:rustc-cfg=generic_member_access"),
+        _ => {}
+    }
+}
+
+// This code exercises the surface area of the generic member access feature
+// used to find backtraces captured by errors further down the chain.
+const GENERIC_MEMBER_ACCESS_PROBE: &str = r#"
+    #![feature(error_generic_member_access)]
+    #![allow(dead_code)]
+
+    use std::backtrace::Backtrace;
+    use std::error::{Error, Request};
+    use std::fmt::{self, Display};
+
+    #[derive(Debug)]
+    struct E {
+        backtrace: Backtrace,
+    }
+
+    impl Display for E {
+        fn fmt(&self, _formatter: &mut fmt::Formatter) -> fmt::Result {
+            unimplemented!()
+        }
+    }
+
+    impl Error for E {
+        fn provide<'a>(&'a self, request: &mut Request<'a>) {
+            request.provide_ref::<Backtrace>(&self.backtrace);
+        }
+    }
+
+    fn request<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a Backtrace> {
+        std::error::request_ref::<Backtrace>(error)
+    }
+"#;
+
+fn compile_probe(probe: &str) -> Option<ExitStatus> {
+    let rustc = env::var_os("RUSTC")?;
+    let out_dir = env::var_os("OUT_DIR")?;
+    let probefile = Path::new(&out_dir).join("probe.rs");
+    fs::write(&probefile, probe).ok()?;
+
+    let rustc_wrapper = env::var_os("RUSTC_WRAPPER").filter(|wrapper| !wrapper.is_empty());
+    let rustc_workspace_wrapper =
+        env::var_os("RUSTC_WORKSPACE_WRAPPER").filter(|wrapper| !wrapper.is_empty());
+    let mut rustc = rustc_wrapper
+        .into_iter()
+        .chain(rustc_workspace_wrapper)
+        .chain(std::iter::once(rustc));
+
+    let mut cmd = Command::new(rustc.next().unwrap());
+    cmd.args(rustc);
+
+    if let Some(target) = env::var_os("TARGET") {
+        cmd.arg("--target").arg(target);
+    }
+
+    // If Cargo wants to set RUSTFLAGS, use that.
+    if let Ok(rustflags) = env::var("CARGO_ENCODED_RUSTFLAGS") {
+        if !rustflags.is_empty() {
+            for arg in rustflags.split('\x1f') {
+                cmd.arg(arg);
+            }
+        }
+    }
+
+    cmd.arg("--edition=2018")
+        .arg("--crate-name=color_eyre_build")
+        .arg("--crate-type=lib")
+        .arg("--emit=metadata")
+        .arg("--out-dir")
+        .arg(out_dir)
+        .arg(probefile)
+        .status()
+        .ok()
+}
@@ -0,0 +1,197 @@
+//! Generate a markdown bug report bundle for a `Report`
+//!
+//! # Details
+//!
+//! A bug report bundles the rendered error report together with information
+//! about the environment the program ran in and, optionally, recent log lines
+//! supplied by the application. The bundle is written to a single markdown
+//! file so that users can attach it to a support request.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use color_eyre::{bug_report::BugReport, eyre::eyre};
+//!
+//! let report = eyre!("something went wrong");
+//!
+//! let path = BugReport::new(&report)
+//!     .recent_logs(|| vec!["INFO starting up".to_string()])
+//!     .write()
+//!     .unwrap();
+//!
+//! println!("Please attach {} to your bug report", path.display());
+//! # std::fs::remove_file(path).unwrap();
+//! ```
+use crate::eyre::Report;
+use std::fmt::{self, Write as _};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs, io};
+
+/// The environment variables that influence how reports are rendered
+pub(crate) const ENV_VARS: &[&str] = &[
+    "RUST_BACKTRACE",
+    "RUST_LIB_BACKTRACE",
+    "RUST_SPANTRACE",
+    "COLORBT_SHOW_HIDDEN",
+];
+
+/// Builder for a markdown bug report bundle
+pub struct BugReport<'a> {
+    report: &'a Report,
+    directory: Option<PathBuf>,
+    recent_logs: Option<Box<dyn FnOnce() -> Vec<String> + 'a>>,
+}
+
+impl fmt::Debug for BugReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BugReport")
+            .field("report", &format_args!("{}", self.report))
+            .field("directory", &self.directory)
+            .finish()
+    }
+}
+
+impl<'a> BugReport<'a> {
+    /// Start building a bug report for the given `Report`
+    pub fn new(report: &'a Report) -> Self {
+        Self {
+            report,
+            directory: None,
+            recent_logs: None,
+        }
+    }
+
+    /// Set the directory the bug report is written to
+    ///
+    /// Defaults to [`std::env::temp_dir`].
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Provide a callback which returns recent log lines to include in the bug report
+    pub fn recent_logs<F>(mut self, logs: F) -> Self
+    where
+        F: FnOnce() -> Vec<String> + 'a,
+    {
+        self.recent_logs = Some(Box::new(logs));
+        self
+    }
+
+    /// Render the bug report as markdown
+    pub fn render(self) -> String {
+        let mut out = String::new();
+        self.render_into(&mut out)
+            .expect("writing to a string doesn't panic");
+        out
+    }
+
+    /// Write the bug report to a new file, returning its path
+    pub fn write(self) -> io::Result<PathBuf> {
+        let directory = self.directory.clone().unwrap_or_else(env::temp_dir);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        let path = directory.join(format!(
+            "bug-report-{}-{}.md",
+            timestamp,
+            std::process::id()
+        ));
+
+        fs::write(&path, self.render())?;
+
+        Ok(path)
+    }
+
+    fn render_into(self, f: &mut String) -> fmt::Result {
+        writeln!(f, "# Bug report")?;
+
+        writeln!(f, "\n## Error\n")?;
+        writeln!(f, "```")?;
+        writeln!(f, "{}", strip_ansi(&format!("{:?}", self.report)).trim())?;
+        writeln!(f, "```")?;
+
+        writeln!(f, "\n## System\n")?;
+        writeln!(f, "|key|value|")?;
+        writeln!(f, "|--|--|")?;
+        writeln!(f, "|**os**|{}|", env::consts::OS)?;
+        writeln!(f, "|**family**|{}|", env::consts::FAMILY)?;
+        writeln!(f, "|**arch**|{}|", env::consts::ARCH)?;
+
+        writeln!(f, "\n## Build\n")?;
+        writeln!(f, "|key|value|")?;
+        writeln!(f, "|--|--|")?;
+        let profile = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+        writeln!(f, "|**profile**|{}|", profile)?;
+        if let Ok(exe) = env::current_exe() {
+            writeln!(f, "|**executable**|{}|", exe.display())?;
+        }
+
+        writeln!(f, "\n## Environment\n")?;
+        writeln!(f, "|key|value|")?;
+        writeln!(f, "|--|--|")?;
+        for var in ENV_VARS {
+            let value = env::var(var).unwrap_or_else(|_| "<unset>".into());
+            writeln!(f, "|**{}**|{}|", var, value)?;
+        }
+
+        if let Some(recent_logs) = self.recent_logs {
+            writeln!(f, "\n## Recent logs\n")?;
+            writeln!(f, "```")?;
+            for line in recent_logs() {
+                writeln!(f, "{}", line)?;
+            }
+            writeln!(f, "```")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Write a markdown bug report for `report` to the temporary directory, returning its path
+///
+/// This is a shorthand for `BugReport::new(report).write()`.
+pub fn bug_report(report: &Report) -> io::Result<PathBuf> {
+    BugReport::new(report).write()
+}
+
+/// Remove ANSI escape sequences from `s`
+pub(crate) fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI sequences end with a byte in the range `@` to `~`.
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC sequences end with BEL or ST (`ESC \`).
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\u{7}' || (c == '\u{1b}' && chars.next() == Some('\\')) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    out
+}
@@ -1,14 +1,16 @@
 //! Configuration options for customizing the behavior of the provided panic
 //! and error reporting hooks
 use crate::{
-    section::PanicMessage,
-    writers::{EnvSection, WriterExt},
+    formatter::{Pretty, ReportFormatter},
+    section::{PanicInfo, PanicMessage},
+    writers::{EnvSection, Hyperlink, WriterExt},
 };
 use fmt::Display;
 use indenter::{indented, Format};
 use owo_colors::{style, OwoColorize, Style};
 use std::env;
 use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::{fmt, path::PathBuf, sync::Arc};
 
 /// A struct that represents a theme that is used by `color_eyre`
@@ -52,6 +54,25 @@ impl Theme {
         Self::default()
     }
 
+    /// Returns a theme without any colors or styling
+    ///
+    /// Every part of a report, including error messages, section headers,
+    /// backtrace frames, span traces and panic messages, is printed as plain
+    /// text. This is the same as [`Theme::new`], but states the intent more
+    /// clearly when disabling colors, e.g. for output written to log files.
+    ///
+    /// ```rust
+    /// use color_eyre::config::{HookBuilder, Theme};
+    ///
+    /// HookBuilder::default()
+    ///     .theme(Theme::plain())
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn plain() -> Self {
+        Self::new()
+    }
+
     /// Returns a theme for dark backgrounds. This is the default
     pub fn dark() -> Self {
         Self {
@@ -157,12 +178,87 @@ pub struct Frame {
     pub filename: Option<PathBuf>,
 }
 
+/// A backtrace that stores only the instruction pointer of each frame.
+///
+/// Capturing an `UnresolvedBacktrace` walks the stack without performing any
+/// symbolication, which makes it cheap enough to record on every error. The
+/// addresses can be turned into [`Frame`]s later with [`resolve`] once the
+/// report is actually printed or logged.
+///
+/// Addresses are only meaningful within the process that captured them.
+///
+/// [`resolve`]: UnresolvedBacktrace::resolve
+#[derive(Debug, Clone)]
+pub struct UnresolvedBacktrace {
+    addresses: Vec<usize>,
+}
+
+impl UnresolvedBacktrace {
+    /// Capture the instruction pointers of the current call stack.
+    #[inline(never)]
+    pub fn capture() -> Self {
+        // Skip the frames of `backtrace::trace` and of this function itself,
+        // unless this function's frame can't be found, e.g. due to inlining.
+        let this = Self::capture as fn() -> Self as usize;
+        let mut addresses = Vec::new();
+        let mut found_this = false;
+        backtrace::trace(|frame| {
+            if !found_this && frame.symbol_address() as usize == this {
+                found_this = true;
+                addresses.clear();
+            } else {
+                addresses.push(frame.ip() as usize);
+            }
+            true
+        });
+
+        Self { addresses }
+    }
+
+    /// The captured instruction pointers, innermost frame first.
+    pub fn addresses(&self) -> impl Iterator<Item = usize> + '_ {
+        self.addresses.iter().copied()
+    }
+
+    /// Resolve the captured addresses into symbolicated frames.
+    ///
+    /// Frames that inline other functions resolve to more than one `Frame`.
+    pub fn resolve(&self) -> Vec<Frame> {
+        let mut frames = Vec::new();
+        for &address in &self.addresses {
+            backtrace::resolve(address as *mut std::ffi::c_void, |sym| {
+                frames.push(Frame {
+                    n: frames.len() + 1,
+                    name: sym.name().map(|x| x.to_string()),
+                    lineno: sym.lineno(),
+                    filename: sym.filename().map(|x| x.into()),
+                })
+            });
+        }
+        frames
+    }
+
+    /// The instruction pointers of an unresolved `backtrace::Backtrace`.
+    pub(crate) fn from_backtrace(trace: &backtrace::Backtrace) -> Self {
+        Self {
+            addresses: trace
+                .frames()
+                .iter()
+                .map(|frame| frame.ip() as usize)
+                .collect(),
+        }
+    }
+}
+
+/// A frame of a backtrace, with its source snippet showing the given number
+/// of lines of context if set, and its location linking to the given url
+/// template if set
 #[derive(Debug)]
-struct StyledFrame<'a>(&'a Frame, Theme);
+struct StyledFrame<'a>(&'a Frame, Theme, Option<usize>, Option<&'a str>, usize);
 
 impl<'a> fmt::Display for StyledFrame<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self(frame, theme) = self;
+        let Self(frame, theme, source_snippets, hyperlinks, max_name_length) = self;
 
         let is_dependency_code = frame.is_dependency_code();
 
@@ -190,6 +286,7 @@ impl<'a> fmt::Display for StyledFrame<'a> {
         } else {
             name
         };
+        let name = ellipsize(name, *max_name_length);
 
         if is_dependency_code {
             write!(f, "{}", (name).style(theme.dependency_code))?;
@@ -211,33 +308,38 @@ impl<'a> fmt::Display for StyledFrame<'a> {
         let lineno = frame
             .lineno
             .map_or("<unknown line>".to_owned(), |x| x.to_string());
+        let url = hyperlinks.and_then(|template| frame.hyperlink_url(template));
         write!(
             &mut separated.ready(),
-            "    at {}:{}",
-            file.style(theme.file),
-            lineno.style(theme.line_number),
+            "    at {}",
+            Hyperlink {
+                url: url.as_deref(),
+                text: format_args!(
+                    "{}:{}",
+                    file.style(theme.file),
+                    lineno.style(theme.line_number)
+                ),
+            }
         )?;
 
-        let v = if std::thread::panicking() {
-            panic_verbosity()
-        } else {
-            lib_verbosity()
-        };
-
         // Maybe print source.
-        if v >= Verbosity::Full {
-            write!(&mut separated.ready(), "{}", SourceSection(frame, *theme))?;
+        if let Some(context_lines) = *source_snippets {
+            write!(
+                &mut separated.ready(),
+                "{}",
+                SourceSection(frame, *theme, context_lines)
+            )?;
         }
 
         Ok(())
     }
 }
 
-struct SourceSection<'a>(&'a Frame, Theme);
+struct SourceSection<'a>(&'a Frame, Theme, usize);
 
 impl fmt::Display for SourceSection<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self(frame, theme) = self;
+        let Self(frame, theme, context_lines) = self;
 
         let (lineno, filename) = match (frame.lineno, frame.filename.as_ref()) {
             (Some(a), Some(b)) => (a, b),
@@ -245,23 +347,32 @@ impl fmt::Display for SourceSection<'_> {
             _ => return Ok(()),
         };
 
+        // Source paths recorded at build time often don't exist or aren't
+        // readable where the program runs, e.g. in containers
         let file = match std::fs::File::open(filename) {
             Ok(file) => file,
-            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
-            e @ Err(_) => e.unwrap(),
+            Err(_) => return Ok(()),
         };
 
+        use std::convert::TryFrom;
         use std::fmt::Write;
         use std::io::BufRead;
 
         // Extract relevant lines.
         let reader = std::io::BufReader::new(file);
-        let start_line = lineno - 2.min(lineno - 1);
-        let surrounding_src = reader.lines().skip(start_line as usize - 1).take(5);
+        let context_lines = u32::try_from(*context_lines).unwrap_or(u32::MAX);
+        let start_line = lineno - context_lines.min(lineno - 1);
+        let surrounding_src = reader
+            .lines()
+            .skip(start_line as usize - 1)
+            .take(2 * context_lines as usize + 1);
         let mut separated = f.header("\n");
         let mut f = separated.in_progress();
         for (line, cur_line_no) in surrounding_src.zip(start_line..) {
-            let line = line.unwrap();
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
             if cur_line_no == lineno {
                 write!(
                     &mut f,
@@ -281,6 +392,25 @@ impl fmt::Display for SourceSection<'_> {
 }
 
 impl Frame {
+    /// The url of the frame's location, following a template with `{path}`
+    /// and `{line}` placeholders
+    fn hyperlink_url(&self, template: &str) -> Option<String> {
+        let path = self.filename.as_ref()?;
+        let path = match std::env::current_dir() {
+            Ok(cwd) if path.is_relative() => cwd.join(path),
+            _ => path.clone(),
+        };
+
+        let mut path = percent_encode_path(&path.to_string_lossy());
+        // Windows paths start with a drive letter rather than a separator
+        if !path.starts_with('/') {
+            path.insert(0, '/');
+        }
+
+        let line = self.lineno.unwrap_or(1).to_string();
+        Some(template.replace("{path}", &path).replace("{line}", &line))
+    }
+
     fn is_dependency_code(&self) -> bool {
         const SYM_PREFIXES: &[&str] = &[
             "std::",
@@ -382,22 +512,69 @@ impl Frame {
     }
 }
 
+type PanicWriter = Arc<std::sync::Mutex<dyn std::io::Write + Send + 'static>>;
+
 /// Builder for customizing the behavior of the global panic and error report hooks
 pub struct HookBuilder {
     filters: Vec<Box<FilterCallback>>,
     capture_span_trace_by_default: bool,
+    #[cfg(feature = "capture-spantrace")]
+    capture_span_fields: bool,
+    capture_backtrace_by_default: bool,
+    resolve_backtraces_eagerly: bool,
+    panic_verbosity: Option<Verbosity>,
+    error_verbosity: Option<Verbosity>,
+    display_source_snippets: Option<bool>,
+    source_context_lines: usize,
+    reversed_stacktrace: bool,
+    collapse_recursion: bool,
+    max_frames: usize,
+    max_frame_name_length: usize,
+    only_crate_frames: bool,
+    hyperlinks: HyperlinkMode,
+    hyperlink_url: String,
+    normalized_output: bool,
+    #[cfg(feature = "capture-spantrace")]
+    reversed_spantrace: bool,
+    #[cfg(feature = "capture-spantrace")]
+    spantrace_filter: Option<Arc<SpanTraceFilterCallback>>,
+    #[cfg(feature = "capture-spantrace")]
+    redact_span_field: Option<Arc<RedactSpanFieldCallback>>,
+    section_order: Arc<[SectionKind]>,
+    throttle_window: Option<std::time::Duration>,
+    formatter: Arc<dyn ReportFormatter>,
+    #[cfg(feature = "opentelemetry")]
+    record_exceptions: bool,
     display_env_section: bool,
+    env_section_vars: Vec<String>,
+    redact_env_var: Arc<RedactEnvVarCallback>,
+    hanging_indent: bool,
+    continuation_prefix: Option<Arc<str>>,
     #[cfg(feature = "track-caller")]
     display_location_section: bool,
+    #[cfg(feature = "track-caller")]
+    display_chain_locations: bool,
+    capture_timestamps: bool,
     panic_section: Option<Box<dyn Display + Send + Sync + 'static>>,
+    panic_writer: Option<PanicWriter>,
+    crash_report_dir: Option<PathBuf>,
+    chain_panic_hook: bool,
+    #[cfg(feature = "indicatif")]
+    progress: Option<indicatif::MultiProgress>,
+    previous_panic_hook_first: bool,
     panic_message: Option<Box<dyn PanicMessage>>,
     theme: Theme,
+    metadata: Vec<(String, Box<dyn Display + Send + Sync + 'static>)>,
     #[cfg(feature = "issue-url")]
     issue_url: Option<String>,
     #[cfg(feature = "issue-url")]
     issue_metadata: Vec<(String, Box<dyn Display + Send + Sync + 'static>)>,
     #[cfg(feature = "issue-url")]
     issue_filter: Arc<IssueFilterCallback>,
+    #[cfg(feature = "issue-url")]
+    issue_report_filter: Arc<IssueReportFilterCallback>,
+    #[cfg(feature = "issue-url")]
+    issue_url_template: Arc<IssueUrlTemplate>,
 }
 
 impl HookBuilder {
@@ -429,18 +606,63 @@ impl HookBuilder {
         HookBuilder {
             filters: vec![],
             capture_span_trace_by_default: false,
+            #[cfg(feature = "capture-spantrace")]
+            capture_span_fields: false,
+            capture_backtrace_by_default: false,
+            resolve_backtraces_eagerly: false,
+            panic_verbosity: None,
+            error_verbosity: None,
+            display_source_snippets: None,
+            source_context_lines: 2,
+            reversed_stacktrace: false,
+            collapse_recursion: true,
+            max_frames: 100,
+            max_frame_name_length: 200,
+            only_crate_frames: false,
+            hyperlinks: HyperlinkMode::Never,
+            hyperlink_url: String::from("file://{path}"),
+            normalized_output: false,
+            #[cfg(feature = "capture-spantrace")]
+            reversed_spantrace: false,
+            #[cfg(feature = "capture-spantrace")]
+            spantrace_filter: None,
+            #[cfg(feature = "capture-spantrace")]
+            redact_span_field: None,
+            section_order: SectionKind::DEFAULT_ORDER.into(),
+            throttle_window: None,
+            formatter: Arc::new(Pretty),
+            #[cfg(feature = "opentelemetry")]
+            record_exceptions: true,
             display_env_section: true,
+            env_section_vars: vec![],
+            redact_env_var: Arc::new(|name, _| is_sensitive_env_var(name)),
+            hanging_indent: false,
+            continuation_prefix: None,
             #[cfg(feature = "track-caller")]
             display_location_section: true,
+            #[cfg(feature = "track-caller")]
+            display_chain_locations: false,
+            capture_timestamps: false,
             panic_section: None,
+            panic_writer: None,
+            crash_report_dir: None,
+            chain_panic_hook: false,
+            #[cfg(feature = "indicatif")]
+            progress: None,
+            previous_panic_hook_first: false,
             panic_message: None,
             theme: Theme::dark(),
+            metadata: vec![],
             #[cfg(feature = "issue-url")]
             issue_url: None,
             #[cfg(feature = "issue-url")]
             issue_metadata: vec![],
             #[cfg(feature = "issue-url")]
             issue_filter: Arc::new(|_| true),
+            #[cfg(feature = "issue-url")]
+            issue_report_filter: Arc::new(|_| true),
+            #[cfg(feature = "issue-url")]
+            issue_url_template: Arc::new(crate::issue_template::github),
         }
     }
 
@@ -610,136 +832,1147 @@ impl HookBuilder {
         self
     }
 
+    /// Configures a filter for disabling issue url generation with access to
+    /// the full error report
+    ///
+    /// Unlike [`issue_filter`](HookBuilder::issue_filter), the closure receives
+    /// an [`IssueContext`](crate::IssueContext) which also exposes the
+    /// report's `Handler`, including its attached sections and
+    /// [`ErrorTag`](crate::ErrorTag)s. If the closure returns `true`, then the
+    /// issue url will be generated. This is checked in addition to the
+    /// [`issue_filter`](HookBuilder::issue_filter).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .issue_url(concat!(env!("CARGO_PKG_REPOSITORY"), "/issues/new"))
+    ///     // the user already knows how to fix the error, don't ask for a bug report
+    ///     .issue_report_filter(|report| !report.has_suggestion())
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// Restricting issue urls to panics and reports tagged as bugs:
+    ///
+    /// ```rust
+    /// use color_eyre::{ErrorKind, ErrorTag};
+    ///
+    /// color_eyre::config::HookBuilder::default()
+    ///     .issue_url(concat!(env!("CARGO_PKG_REPOSITORY"), "/issues/new"))
+    ///     .issue_report_filter(|report| {
+    ///         matches!(report.kind(), ErrorKind::NonRecoverable(_))
+    ///             || report.tags().contains(&ErrorTag::Bug)
+    ///     })
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "issue-url")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "issue-url")))]
+    pub fn issue_report_filter<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&crate::IssueContext<'_>) -> bool + Send + Sync + 'static,
+    {
+        self.issue_report_filter = Arc::new(predicate);
+        self
+    }
+
+    /// Configures how the issue url is generated from the configured
+    /// [`issue_url`](HookBuilder::issue_url) and the details of the error
+    /// report or panic
+    ///
+    /// The default template encodes the issue title and body as query
+    /// parameters of GitHub's new issue page. The
+    /// [`issue_template`](crate::issue_template) module provides templates
+    /// for GitLab, Gitea and Jira, or a closure can be used for any other
+    /// issue tracker.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use color_eyre::issue_template;
+    ///
+    /// color_eyre::config::HookBuilder::default()
+    ///     .issue_url("https://jira.example.com/secure/CreateIssueDetails!init.jspa?pid=10000&issuetype=1")
+    ///     .issue_url_template(issue_template::jira)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "issue-url")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "issue-url")))]
+    pub fn issue_url_template<F>(mut self, template: F) -> Self
+    where
+        F: Fn(&crate::issue_template::IssueData<'_>) -> String + Send + Sync + 'static,
+    {
+        self.issue_url_template = Arc::new(template);
+        self
+    }
+
     /// Configures the default capture mode for `SpanTraces` in error reports and panics
     pub fn capture_span_trace_by_default(mut self, cond: bool) -> Self {
         self.capture_span_trace_by_default = cond;
         self
     }
 
-    /// Configures the enviroment varible info section and whether or not it is displayed
-    pub fn display_env_section(mut self, cond: bool) -> Self {
-        self.display_env_section = cond;
+    /// Configures whether error reports capture the fields of the active spans
+    ///
+    /// When enabled, the key/value fields recorded on every span that is
+    /// active when a `Report` is created are displayed in a "Span context"
+    /// section. This requires a `tracing_error::ErrorLayer` to be installed,
+    /// just like capturing `SpanTrace`s does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .capture_span_fields(true)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "capture-spantrace")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "capture-spantrace")))]
+    pub fn capture_span_fields(mut self, cond: bool) -> Self {
+        self.capture_span_fields = cond;
         self
     }
 
-    /// Configures the location info section and whether or not it is displayed.
+    /// Configures the default capture mode for `Backtraces` in error reports
     ///
-    /// # Notes
+    /// This only applies when neither `RUST_LIB_BACKTRACE` nor `RUST_BACKTRACE`
+    /// are set, so users can still disable backtraces with
+    /// `RUST_LIB_BACKTRACE=0`.
     ///
-    /// This will not disable the location section in a panic message.
-    #[cfg(feature = "track-caller")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "track-caller")))]
-    pub fn display_location_section(mut self, cond: bool) -> Self {
-        self.display_location_section = cond;
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .capture_backtrace_by_default(cfg!(debug_assertions))
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn capture_backtrace_by_default(mut self, cond: bool) -> Self {
+        self.capture_backtrace_by_default = cond;
         self
     }
 
-    /// Add a custom filter to the set of frame filters
+    /// Configures whether error reports resolve the symbols of their backtrace
+    /// as soon as they are created
+    ///
+    /// By default `Report`s only walk the stack when they are created and
+    /// resolve the symbols of its frames the first time the backtrace is
+    /// accessed, e.g. when the report is printed. Symbolication is by far the
+    /// most expensive part of capturing a backtrace, so this keeps creating
+    /// reports which are handled without being printed cheap. Enabling this
+    /// resolves the symbols immediately instead. The addresses of the frames
+    /// are available via
+    /// [`Handler::unresolved_backtrace`](crate::Handler::unresolved_backtrace)
+    /// either way.
     ///
     /// # Examples
     ///
     /// ```rust
     /// color_eyre::config::HookBuilder::default()
-    ///     .add_frame_filter(Box::new(|frames| {
-    ///         let filters = &[
-    ///             "uninteresting_function",
-    ///         ];
+    ///     .resolve_backtraces_eagerly(true)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn resolve_backtraces_eagerly(mut self, cond: bool) -> Self {
+        self.resolve_backtraces_eagerly = cond;
+        self
+    }
+
+    /// Configures the verbosity of panic reports
     ///
-    ///         frames.retain(|frame| {
-    ///             !filters.iter().any(|f| {
-    ///                 let name = if let Some(name) = frame.name.as_ref() {
-    ///                     name.as_str()
-    ///                 } else {
-    ///                     return true;
-    ///                 };
+    /// By default the verbosity of panic reports is controlled by the
+    /// `RUST_BACKTRACE` environment variable. Setting it here overrides that
+    /// variable, and is itself overridden by the `COLOR_EYRE_PANIC_VERBOSITY`
+    /// environment variable, which accepts `minimal`, `medium` and `full` as
+    /// well as the values understood by `RUST_BACKTRACE`.
     ///
-    ///                 name.starts_with(f)
-    ///             })
-    ///         });
-    ///     }))
+    /// # Examples
+    ///
+    /// ```rust
+    /// use color_eyre::config::{HookBuilder, Verbosity};
+    ///
+    /// HookBuilder::default()
+    ///     .panic_verbosity(Verbosity::Full)
+    ///     .error_verbosity(Verbosity::Minimal)
     ///     .install()
     ///     .unwrap();
     /// ```
-    pub fn add_frame_filter(mut self, filter: Box<FilterCallback>) -> Self {
-        self.filters.push(filter);
+    pub fn panic_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.panic_verbosity = Some(verbosity);
         self
     }
 
-    /// Install the given Hook as the global error report hook
-    pub fn install(self) -> Result<(), crate::eyre::Report> {
-        let (panic_hook, eyre_hook) = self.try_into_hooks()?;
-        eyre_hook.install()?;
-        panic_hook.install();
-        Ok(())
+    /// Configures the verbosity of error reports
+    ///
+    /// By default the verbosity of error reports is controlled by the
+    /// `RUST_LIB_BACKTRACE` and `RUST_BACKTRACE` environment variables.
+    /// Setting it here overrides those variables, and is itself overridden by
+    /// the `COLOR_EYRE_ERROR_VERBOSITY` environment variable, which accepts
+    /// `minimal`, `medium` and `full` as well as the values understood by
+    /// `RUST_BACKTRACE`.
+    pub fn error_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.error_verbosity = Some(verbosity);
+        self
     }
 
-    /// Add the default set of filters to this `HookBuilder`'s configuration
-    pub fn add_default_filters(self) -> Self {
-        self.add_frame_filter(Box::new(default_frame_filter))
-            .add_frame_filter(Box::new(eyre_frame_filters))
+    /// Configures the verbosity of both panic and error reports from the
+    /// number of times a `--verbose` flag was passed
+    ///
+    /// See [`Verbosity::from_occurrences`] for how the count is mapped. The
+    /// verbosity can also be changed after installing the hooks via
+    /// [`set_verbosity`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let verbose = 2;
+    /// // e.g. `#[arg(short, long, action = clap::ArgAction::Count)] verbose: u8`
+    /// color_eyre::config::HookBuilder::default()
+    ///     .verbosity_from_occurrences(verbose)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn verbosity_from_occurrences(self, occurrences: u8) -> Self {
+        let verbosity = Verbosity::from_occurrences(occurrences);
+        self.panic_verbosity(verbosity).error_verbosity(verbosity)
     }
 
-    /// Create a `PanicHook` and `EyreHook` from this `HookBuilder`.
-    /// This can be used if you want to combine these handlers with other handlers.
-    pub fn into_hooks(self) -> (PanicHook, EyreHook) {
-        self.try_into_hooks().expect("into_hooks should only be called when no `color_spantrace` themes have previously been set")
+    /// Configures whether source snippets are displayed for backtrace frames
+    ///
+    /// By default source snippets are only displayed at [`Verbosity::Full`],
+    /// e.g. when running with `RUST_BACKTRACE=full`. Displaying them requires
+    /// reading the source files from disk when the report is printed, which
+    /// can be slow and shows misleading lines if the sources have changed
+    /// since the program was built. Disabling them means source files are
+    /// never read, which is useful where the paths recorded at build time
+    /// don't exist, e.g. in containers.
+    ///
+    /// The `COLOR_EYRE_SOURCE` environment variable takes precedence over this
+    /// setting, with `COLOR_EYRE_SOURCE=0` disabling snippets and any other
+    /// value enabling them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .display_source_snippets(false)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn display_source_snippets(mut self, cond: bool) -> Self {
+        self.display_source_snippets = Some(cond);
+        self
     }
 
-    /// Create a `PanicHook` and `EyreHook` from this `HookBuilder`.
-    /// This can be used if you want to combine these handlers with other handlers.
-    pub fn try_into_hooks(self) -> Result<(PanicHook, EyreHook), crate::eyre::Report> {
-        let theme = self.theme;
-        #[cfg(feature = "issue-url")]
-        let metadata = Arc::new(self.issue_metadata);
-        let panic_hook = PanicHook {
-            filters: self.filters.into(),
-            section: self.panic_section,
-            #[cfg(feature = "capture-spantrace")]
-            capture_span_trace_by_default: self.capture_span_trace_by_default,
-            display_env_section: self.display_env_section,
-            panic_message: self
-                .panic_message
-                .unwrap_or_else(|| Box::new(DefaultPanicMessage(theme))),
-            theme,
-            #[cfg(feature = "issue-url")]
-            issue_url: self.issue_url.clone(),
-            #[cfg(feature = "issue-url")]
-            issue_metadata: metadata.clone(),
-            #[cfg(feature = "issue-url")]
-            issue_filter: self.issue_filter.clone(),
-        };
-
-        let eyre_hook = EyreHook {
-            filters: panic_hook.filters.clone(),
-            #[cfg(feature = "capture-spantrace")]
-            capture_span_trace_by_default: self.capture_span_trace_by_default,
-            display_env_section: self.display_env_section,
-            #[cfg(feature = "track-caller")]
-            display_location_section: self.display_location_section,
-            theme,
-            #[cfg(feature = "issue-url")]
-            issue_url: self.issue_url,
-            #[cfg(feature = "issue-url")]
-            issue_metadata: metadata,
-            #[cfg(feature = "issue-url")]
-            issue_filter: self.issue_filter,
-        };
-
-        #[cfg(feature = "capture-spantrace")]
-        eyre::WrapErr::wrap_err(color_spantrace::set_theme(self.theme.into()), "could not set the provided `Theme` via `color_spantrace::set_theme` globally as another was already set")?;
-
-        Ok((panic_hook, eyre_hook))
+    /// Configures how many lines of source are displayed before and after the
+    /// active line of each source snippet
+    ///
+    /// Defaults to 2, displaying 5 lines in total.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .display_source_snippets(true)
+    ///     .source_context_lines(5)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn source_context_lines(mut self, lines: usize) -> Self {
+        self.source_context_lines = lines;
+        self
     }
-}
 
-#[cfg(feature = "capture-spantrace")]
-impl From<Theme> for color_spantrace::Theme {
-    fn from(src: Theme) -> color_spantrace::Theme {
-        color_spantrace::Theme::new()
-            .file(src.file)
-            .line_number(src.line_number)
-            .target(src.spantrace_target)
-            .fields(src.spantrace_fields)
+    /// Configures whether backtraces are printed with the outermost frame
+    /// first, so the frame closest to where the error or panic originated is
+    /// printed last
+    ///
+    /// This keeps the most relevant frames visible at the bottom of long
+    /// backtraces in terminals that only show the end of the output. Frame
+    /// numbers are unaffected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .reversed_stacktrace(true)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn reversed_stacktrace(mut self, cond: bool) -> Self {
+        self.reversed_stacktrace = cond;
+        self
+    }
+
+    /// Configures whether runs of repeated frames in backtraces, such as
+    /// those produced by deep recursion, are collapsed
+    ///
+    /// When enabled, which is the default, a frame or a cycle of up to
+    /// eight frames that repeats is only printed once, followed by a line
+    /// saying how many more times it was repeated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .collapse_recursion(false)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn collapse_recursion(mut self, cond: bool) -> Self {
+        self.collapse_recursion = cond;
+        self
+    }
+
+    /// Configures the maximum number of frames printed in a backtrace
+    ///
+    /// Frames beyond the limit are replaced by a single line saying how many
+    /// more frames were left out. Frames hidden by filters don't count
+    /// towards the limit. Defaults to 100, and `0` disables the limit. The
+    /// `COLOR_EYRE_MAX_FRAMES` environment variable takes precedence over
+    /// this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .max_frames(20)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn max_frames(mut self, frames: usize) -> Self {
+        self.max_frames = frames;
+        self
+    }
+
+    /// Configures the maximum number of characters of a frame's function
+    /// name that are printed in a backtrace
+    ///
+    /// Longer names, such as those of deeply nested generic functions, are
+    /// shortened by replacing their middle with `…`. Defaults to 200, and `0`
+    /// disables the limit. The `COLOR_EYRE_MAX_FRAME_NAME_LENGTH` environment
+    /// variable takes precedence over this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .max_frame_name_length(80)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn max_frame_name_length(mut self, length: usize) -> Self {
+        self.max_frame_name_length = length;
+        self
+    }
+
+    /// Configures whether the file locations of backtrace frames and the
+    /// issue url are rendered as clickable terminal hyperlinks
+    ///
+    /// Hyperlinks use the OSC 8 escape sequence, which is ignored by most
+    /// terminals that don't support it. They are disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use color_eyre::config::{HookBuilder, HyperlinkMode};
+    ///
+    /// HookBuilder::default()
+    ///     .hyperlinks(HyperlinkMode::Auto)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn hyperlinks(mut self, mode: HyperlinkMode) -> Self {
+        self.hyperlinks = mode;
+        self
+    }
+
+    /// Configures the url the file locations of backtrace frames link to when
+    /// [`hyperlinks`](Self::hyperlinks) are enabled
+    ///
+    /// `{path}` is replaced with the absolute path of the file and `{line}`
+    /// with the line number, or `1` if it is unknown. The default is
+    /// `file://{path}`, use e.g. `vscode://file{path}:{line}` to open
+    /// locations in an editor.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use color_eyre::config::{HookBuilder, HyperlinkMode};
+    ///
+    /// HookBuilder::default()
+    ///     .hyperlinks(HyperlinkMode::Always)
+    ///     .hyperlink_url("vscode://file{path}:{line}")
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn hyperlink_url<S: ToString>(mut self, template: S) -> Self {
+        self.hyperlink_url = template.to_string();
+        self
+    }
+
+    /// Configures whether error and panic reports are normalized so that they
+    /// can be compared against snapshots, e.g. with `insta`
+    ///
+    /// Normalized reports contain no ANSI escape codes or hyperlinks, paths
+    /// inside the current working directory are made relative to it, line
+    /// and column numbers of `.rs` files are replaced with `LL` and `CC`, and
+    /// the hash suffixes of function names are removed. When running
+    /// `cargo test`, the working directory is the root of the package being
+    /// tested.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .normalized_output(cfg!(test))
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn normalized_output(mut self, cond: bool) -> Self {
+        self.normalized_output = cond;
+        self
+    }
+
+    /// Configures whether backtraces only show the frames of the crate that
+    /// was compiled into the running executable
+    ///
+    /// The name of the crate is derived from the file name of the executable
+    /// when the hook is installed, so this works for binaries, examples and
+    /// tests built by cargo as long as they aren't renamed. All other
+    /// frames, such as those of the standard library and of dependencies,
+    /// are hidden and only counted. If no frame of a backtrace belongs to the
+    /// crate, e.g. because the name could not be derived, the backtrace is
+    /// printed unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .only_crate_frames(true)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn only_crate_frames(mut self, cond: bool) -> Self {
+        self.only_crate_frames = cond;
+        self
+    }
+
+    /// Configures whether `SpanTrace`s are printed with the outermost span
+    /// first, so the span in which the error or panic originated is printed
+    /// last
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .reversed_spantrace(true)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "capture-spantrace")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "capture-spantrace")))]
+    pub fn reversed_spantrace(mut self, cond: bool) -> Self {
+        self.reversed_spantrace = cond;
+        self
+    }
+
+    /// Configures which spans of `SpanTrace`s are printed in error reports
+    /// and panics
+    ///
+    /// The callback is passed the metadata of each span and returns whether
+    /// the span should be printed, so spans from noisy crates can be skipped.
+    /// Skipped spans are also left out of the fields captured via
+    /// [`capture_span_fields`](Self::capture_span_fields).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .spantrace_filter(|metadata| !metadata.target().starts_with("hyper"))
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "capture-spantrace")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "capture-spantrace")))]
+    pub fn spantrace_filter<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&tracing_core::Metadata<'static>) -> bool + Send + Sync + 'static,
+    {
+        self.spantrace_filter = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Configures which fields of the spans of `SpanTrace`s have their values
+    /// redacted in error reports and panics
+    ///
+    /// The callback is passed the name and the formatted value of each span
+    /// field and returns whether the value should be replaced with
+    /// `<redacted>`, both in printed `SpanTrace`s and in the fields captured
+    /// via [`capture_span_fields`](Self::capture_span_fields). By default no
+    /// values are redacted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .redact_span_fields(|name, _value| name == "password" || name == "email")
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "capture-spantrace")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "capture-spantrace")))]
+    pub fn redact_span_fields<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&str, &str) -> bool + Send + Sync + 'static,
+    {
+        self.redact_span_field = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Configures the order in which the sections of error reports are
+    /// printed after the list of errors
+    ///
+    /// Sections missing from `order` are printed after the listed ones, in
+    /// their default order, see [`SectionKind::DEFAULT_ORDER`].
+    ///
+    /// # Examples
+    ///
+    /// Print suggestions right after the errors and the environment hints
+    /// before the backtrace:
+    ///
+    /// ```rust
+    /// use color_eyre::config::{HookBuilder, SectionKind};
+    ///
+    /// HookBuilder::default()
+    ///     .section_order(&[SectionKind::Notes, SectionKind::Env])
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn section_order(mut self, order: &[SectionKind]) -> Self {
+        let mut sections = Vec::with_capacity(SectionKind::DEFAULT_ORDER.len());
+        for kind in order.iter().chain(SectionKind::DEFAULT_ORDER) {
+            if !sections.contains(kind) {
+                sections.push(*kind);
+            }
+        }
+
+        self.section_order = sections.into();
+        self
+    }
+
+    /// Throttle identical error reports printed within `window` of each other
+    ///
+    /// When enabled, printing a report via
+    /// [`ThrottleExt::throttled`](crate::throttle::ThrottleExt::throttled)
+    /// whose chain of error messages is identical to one printed less than
+    /// `window` ago produces a single line noting how often the error
+    /// repeated, instead of the full report. This keeps the logs of
+    /// long-running services readable when the same failure occurs over and
+    /// over, e.g. during an outage.
+    ///
+    /// Throttling is opt-in per print site: only reports printed through
+    /// `throttled()` are counted and collapsed. The hook doesn't throttle
+    /// anything by itself, so reports returned from `main`, logged with
+    /// `{:?}` or printed by the panic hook are always printed in full, and
+    /// formatting the same report more than once gives the same output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// color_eyre::config::HookBuilder::default()
+    ///     .throttle_repeated_reports(Duration::from_secs(60))
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn throttle_repeated_reports(mut self, window: std::time::Duration) -> Self {
+        self.throttle_window = Some(window);
+        self
+    }
+
+    /// Configures whether panics and printed error reports are recorded as
+    /// OpenTelemetry exception events
+    ///
+    /// When enabled, which is the default with the `opentelemetry` feature,
+    /// an `exception` event is added to the active OpenTelemetry span whenever
+    /// a panic occurs or an error report is formatted with `Debug`. Each
+    /// report is recorded once, in the span that is active when it is first
+    /// printed, so reports that are handled without being printed aren't
+    /// recorded. The event carries the `exception.type`, `exception.message`
+    /// and `exception.stacktrace` attributes defined by the semantic
+    /// conventions, holding the outermost message and the full report
+    /// without ANSI escape codes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .record_opentelemetry_exceptions(false)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "opentelemetry")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+    pub fn record_opentelemetry_exceptions(mut self, cond: bool) -> Self {
+        self.record_exceptions = cond;
+        self
+    }
+
+    /// Configures the [`ReportFormatter`] used to render error reports
+    ///
+    /// Defaults to [`Pretty`]. Panics are not affected by this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use color_eyre::formatter::Plain;
+    ///
+    /// color_eyre::config::HookBuilder::default()
+    ///     .formatter(Plain)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: ReportFormatter,
+    {
+        self.formatter = Arc::new(formatter);
+        self
+    }
+
+    /// Configures whether continuation lines of multi-line notes, warnings
+    /// and suggestions are aligned with the text following their label
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .hanging_indent(true)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn hanging_indent(mut self, cond: bool) -> Self {
+        self.hanging_indent = cond;
+        self
+    }
+
+    /// Configures a prefix written at the start of every continuation line of
+    /// multi-line error messages, notes, warnings and suggestions
+    ///
+    /// The prefix follows the indentation of the item the line belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .hanging_indent(true)
+    ///     .continuation_prefix("│ ")
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn continuation_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.continuation_prefix = Some(prefix.into().into());
+        self
+    }
+
+    /// Configures where panic reports are written, instead of stderr
+    ///
+    /// This lets GUI applications and daemons, which often have no visible
+    /// stderr, direct panic reports to a file, a logger or a channel. Each
+    /// report is written in full followed by a newline and the writer is
+    /// flushed afterwards. Errors while writing are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use color_eyre::config::{HookBuilder, Theme};
+    ///
+    /// HookBuilder::default()
+    ///     .theme(Theme::plain())
+    ///     .panic_writer(std::io::sink())
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn panic_writer<W>(mut self, writer: W) -> Self
+    where
+        W: std::io::Write + Send + 'static,
+    {
+        self.panic_writer = Some(Arc::new(std::sync::Mutex::new(writer)));
+        self
+    }
+
+    /// Configures panic reports to be appended to the file at `path`, creating
+    /// it if it doesn't exist
+    ///
+    /// This is a convenience for [`panic_writer`](Self::panic_writer). Consider
+    /// combining it with [`Theme::plain`] to keep ANSI escape codes out of the
+    /// log file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use color_eyre::config::{HookBuilder, Theme};
+    ///
+    /// fn main() -> color_eyre::Result<()> {
+    ///     HookBuilder::default()
+    ///         .theme(Theme::plain())
+    ///         .panic_to_log_file("crash.log")?
+    ///         .install()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn panic_to_log_file(self, path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(self.panic_writer(file))
+    }
+
+    /// Configures panics to write a machine-readable crash report to a new
+    /// file in `dir`
+    ///
+    /// The crash report is a JSON object holding the panic message, its
+    /// location, the name of the panicking thread, a backtrace, the
+    /// `SpanTrace`, the metadata added via [`add_metadata`](Self::add_metadata)
+    /// and [`add_issue_metadata`](Self::add_issue_metadata) and the environment
+    /// variables that control the report. The backtrace is always included,
+    /// regardless of the panic verbosity. The path of the file is printed at
+    /// the end of the panic report.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .crash_report_dir(std::env::temp_dir())
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn crash_report_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.crash_report_dir = Some(dir.into());
+        self
+    }
+
+    /// Configures whether installing the panic hook keeps the previously
+    /// installed panic hook and calls it for every panic as well
+    ///
+    /// By default the panic hook replaces the previous one, which breaks
+    /// integrations that rely on their own panic hook, such as logging
+    /// panics with `tracing-panic`. When enabled, the previous hook is
+    /// taken with `std::panic::take_hook` on install and called after the
+    /// panic report was printed, or before if
+    /// [`previous_panic_hook_first`](Self::previous_panic_hook_first) is set.
+    ///
+    /// Note that if no other hook was installed, the previous hook is the
+    /// default hook of the standard library, which prints the panic message
+    /// as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .chain_panic_hook(true)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn chain_panic_hook(mut self, cond: bool) -> Self {
+        self.chain_panic_hook = cond;
+        self
+    }
+
+    /// Configures whether a panic hook that is kept via
+    /// [`chain_panic_hook`](Self::chain_panic_hook) is called before the
+    /// panic report is printed, rather than after
+    pub fn previous_panic_hook_first(mut self, cond: bool) -> Self {
+        self.previous_panic_hook_first = cond;
+        self
+    }
+
+    /// Add a new entry to the "Build Info" section printed at the end of every
+    /// error and panic report
+    ///
+    /// This is meant for information which identifies the build of the
+    /// program, such as its version, git commit, target triple or profile.
+    /// Unlike issue metadata, which only ends up in generated issue urls,
+    /// this metadata is always displayed. It is also included in crash
+    /// reports written to the [`crash_report_dir`](Self::crash_report_dir).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .add_metadata("version", env!("CARGO_PKG_VERSION"))
+    ///     .add_metadata("profile", if cfg!(debug_assertions) { "debug" } else { "release" })
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn add_metadata<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Display,
+        V: Display + Send + Sync + 'static,
+    {
+        let pair = (key.to_string(), Box::new(value) as _);
+        self.metadata.push(pair);
+        self
+    }
+
+    /// Configures the enviroment varible info section and whether or not it is displayed
+    pub fn display_env_section(mut self, cond: bool) -> Self {
+        self.display_env_section = cond;
+        self
+    }
+
+    /// Add a table with the values of the given environment variables to the
+    /// environment section of panic and error reports
+    ///
+    /// The values are read when the report is printed. Variables which are
+    /// not set are shown as `<unset>`, and the values of variables matched by
+    /// the callback passed to [`redact_env_vars`](Self::redact_env_vars) as
+    /// `<redacted>`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .env_section_vars(&["RUST_LOG", "APP_CONFIG"])
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn env_section_vars(mut self, vars: &[&str]) -> Self {
+        self.env_section_vars = vars.iter().map(|&var| var.to_owned()).collect();
+        self
+    }
+
+    /// Configures which of the variables added via
+    /// [`env_section_vars`](Self::env_section_vars) have their values redacted
+    ///
+    /// The callback is passed the name and value of each variable and returns
+    /// whether the value should be hidden. By default the values of variables
+    /// whose names contain `SECRET`, `TOKEN`, `PASSWORD`, `KEY` or
+    /// `CREDENTIAL`, in any case, are redacted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .env_section_vars(&["RUST_LOG", "DATABASE_URL"])
+    ///     .redact_env_vars(|name, value| name == "DATABASE_URL" && value.contains('@'))
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn redact_env_vars<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&str, &str) -> bool + Send + Sync + 'static,
+    {
+        self.redact_env_var = Arc::new(predicate);
+        self
+    }
+
+    /// Configures the location info section and whether or not it is displayed.
+    ///
+    /// # Notes
+    ///
+    /// This will not disable the location section in a panic message.
+    #[cfg(feature = "track-caller")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "track-caller")))]
+    pub fn display_location_section(mut self, cond: bool) -> Self {
+        self.display_location_section = cond;
+        self
+    }
+
+    /// Configures whether the location each entry of the error chain was
+    /// created at is printed below it
+    ///
+    /// Every `wrap_err` call records where it was made, so this shows a cheap
+    /// "logical stack" of the report even when backtraces are disabled. The
+    /// locations are also available via
+    /// [`Report::locations`](eyre::Report::locations).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .display_chain_locations(true)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "track-caller")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "track-caller")))]
+    pub fn display_chain_locations(mut self, cond: bool) -> Self {
+        self.display_chain_locations = cond;
+        self
+    }
+
+    /// Configures whether the time at which a report was created and each
+    /// time it was wrapped with `wrap_err` is recorded
+    ///
+    /// Every entry of the error chain that was added by `wrap_err` is then
+    /// printed with the time that passed since the entry below it was
+    /// created, e.g. `(+120ms)`. For errors which are propagated through
+    /// retries or queues this shows how long each layer took.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .capture_timestamps(true)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn capture_timestamps(mut self, cond: bool) -> Self {
+        self.capture_timestamps = cond;
+        self
+    }
+
+    /// Suspend the progress bars of `progress` while printing panic reports
+    /// and reports printed via [`ReportExt::eprint`]
+    ///
+    /// Output written to the terminal while indicatif progress bars are
+    /// drawn gets interleaved with them and partially overwritten. The bars
+    /// are hidden before the report is written and redrawn afterwards, see
+    /// [`progress`](crate::progress) for the limitations during panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use indicatif::{MultiProgress, ProgressBar};
+    ///
+    /// let progress = MultiProgress::new();
+    /// let bar = progress.add(ProgressBar::new(100));
+    ///
+    /// color_eyre::config::HookBuilder::default()
+    ///     .with_progress_suspender(progress)
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// [`ReportExt::eprint`]: crate::progress::ReportExt::eprint
+    #[cfg(feature = "indicatif")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "indicatif")))]
+    pub fn with_progress_suspender(mut self, progress: indicatif::MultiProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Add a custom filter to the set of frame filters
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// color_eyre::config::HookBuilder::default()
+    ///     .add_frame_filter(Box::new(|frames| {
+    ///         let filters = &[
+    ///             "uninteresting_function",
+    ///         ];
+    ///
+    ///         frames.retain(|frame| {
+    ///             !filters.iter().any(|f| {
+    ///                 let name = if let Some(name) = frame.name.as_ref() {
+    ///                     name.as_str()
+    ///                 } else {
+    ///                     return true;
+    ///                 };
+    ///
+    ///                 name.starts_with(f)
+    ///             })
+    ///         });
+    ///     }))
+    ///     .install()
+    ///     .unwrap();
+    /// ```
+    pub fn add_frame_filter(mut self, filter: Box<FilterCallback>) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Install the given Hook as the global error report hook
+    pub fn install(self) -> Result<(), crate::eyre::Report> {
+        let (panic_hook, eyre_hook) = self.try_into_hooks()?;
+        eyre_hook.install()?;
+        panic_hook.install();
+        Ok(())
+    }
+
+    /// Install only the panic hook produced by this `HookBuilder`, leaving the
+    /// global eyre hook untouched.
+    ///
+    /// Useful for applications that already install their own `eyre` handler
+    /// but still want color-eyre's panic reports.
+    pub fn install_panic_hook(self) -> Result<(), crate::eyre::Report> {
+        let (panic_hook, _) = self.try_into_hooks()?;
+        panic_hook.install();
+        Ok(())
+    }
+
+    /// Install only the eyre hook produced by this `HookBuilder`, leaving the
+    /// current panic hook untouched.
+    ///
+    /// Useful for applications that manage their own panic hook, for example
+    /// to restore the terminal before printing, but still want color-eyre's
+    /// error reports.
+    pub fn install_eyre_hook(self) -> Result<(), crate::eyre::Report> {
+        let (_, eyre_hook) = self.try_into_hooks()?;
+        eyre_hook.install()?;
+        Ok(())
+    }
+
+    /// Add the default set of filters to this `HookBuilder`'s configuration
+    pub fn add_default_filters(self) -> Self {
+        self.add_frame_filter(Box::new(default_frame_filter))
+            .add_frame_filter(Box::new(eyre_frame_filters))
+    }
+
+    /// Create a `PanicHook` and `EyreHook` from this `HookBuilder`.
+    /// This can be used if you want to combine these handlers with other handlers.
+    pub fn into_hooks(self) -> (PanicHook, EyreHook) {
+        self.try_into_hooks().expect("into_hooks should only be called when no `color_spantrace` themes have previously been set")
+    }
+
+    /// Create a `PanicHook` and `EyreHook` from this `HookBuilder`.
+    /// This can be used if you want to combine these handlers with other handlers.
+    pub fn try_into_hooks(mut self) -> Result<(PanicHook, EyreHook), crate::eyre::Report> {
+        let theme = self.theme;
+        if self.only_crate_frames {
+            if let Some(crate_name) = executable_crate_name() {
+                self.filters.push(crate_frame_filter(crate_name));
+            }
+        }
+        let hyperlinks: Option<Arc<str>> = if self.hyperlinks.enabled() && !self.normalized_output {
+            Some(self.hyperlink_url.into())
+        } else {
+            None
+        };
+        let env_vars = Arc::new(EnvVars {
+            names: self.env_section_vars,
+            redact: self.redact_env_var,
+        });
+        #[cfg(feature = "capture-spantrace")]
+        let spantrace_filters = Arc::new(SpanTraceFilters {
+            filter: self.spantrace_filter,
+            redact: self.redact_span_field,
+        });
+        let build_info = Arc::new(self.metadata);
+        #[cfg(feature = "issue-url")]
+        let metadata = Arc::new(self.issue_metadata);
+        let panic_hook = PanicHook {
+            filters: self.filters.into(),
+            section: self.panic_section,
+            writer: self.panic_writer,
+            crash_report_dir: self.crash_report_dir,
+            chain_panic_hook: self.chain_panic_hook,
+            #[cfg(feature = "indicatif")]
+            progress: self.progress.clone(),
+            previous_panic_hook_first: self.previous_panic_hook_first,
+            #[cfg(feature = "capture-spantrace")]
+            capture_span_trace_by_default: self.capture_span_trace_by_default,
+            verbosity: self.panic_verbosity,
+            source_snippets: self.display_source_snippets,
+            source_context_lines: self.source_context_lines,
+            reversed_stacktrace: self.reversed_stacktrace,
+            collapse_recursion: self.collapse_recursion,
+            max_frames: self.max_frames,
+            max_frame_name_length: self.max_frame_name_length,
+            hyperlinks: hyperlinks.clone(),
+            normalized_output: self.normalized_output,
+            #[cfg(feature = "capture-spantrace")]
+            reversed_spantrace: self.reversed_spantrace,
+            #[cfg(feature = "capture-spantrace")]
+            spantrace_filters: spantrace_filters.clone(),
+            display_env_section: self.display_env_section,
+            env_vars: env_vars.clone(),
+            #[cfg(feature = "opentelemetry")]
+            record_exceptions: self.record_exceptions,
+            panic_message: self
+                .panic_message
+                .unwrap_or_else(|| Box::new(DefaultPanicMessage(theme))),
+            theme,
+            build_info: build_info.clone(),
+            #[cfg(feature = "issue-url")]
+            issue_url: self.issue_url.clone(),
+            #[cfg(feature = "issue-url")]
+            issue_metadata: metadata.clone(),
+            #[cfg(feature = "issue-url")]
+            issue_filter: self.issue_filter.clone(),
+            #[cfg(feature = "issue-url")]
+            issue_report_filter: self.issue_report_filter.clone(),
+            #[cfg(feature = "issue-url")]
+            issue_url_template: self.issue_url_template.clone(),
+        };
+
+        let eyre_hook = EyreHook {
+            filters: panic_hook.filters.clone(),
+            #[cfg(feature = "capture-spantrace")]
+            capture_span_trace_by_default: self.capture_span_trace_by_default,
+            #[cfg(feature = "capture-spantrace")]
+            capture_span_fields: self.capture_span_fields,
+            capture_backtrace_by_default: self.capture_backtrace_by_default,
+            resolve_backtraces_eagerly: self.resolve_backtraces_eagerly,
+            verbosity: self.error_verbosity,
+            source_snippets: self.display_source_snippets,
+            source_context_lines: self.source_context_lines,
+            reversed_stacktrace: self.reversed_stacktrace,
+            collapse_recursion: self.collapse_recursion,
+            max_frames: self.max_frames,
+            max_frame_name_length: self.max_frame_name_length,
+            hyperlinks,
+            normalized_output: self.normalized_output,
+            #[cfg(feature = "capture-spantrace")]
+            reversed_spantrace: self.reversed_spantrace,
+            #[cfg(feature = "capture-spantrace")]
+            spantrace_filters,
+            section_order: self.section_order,
+            throttle: self
+                .throttle_window
+                .map(|window| Arc::new(crate::throttle::Throttle::new(window))),
+            formatter: self.formatter,
+            #[cfg(feature = "opentelemetry")]
+            record_exceptions: self.record_exceptions,
+            display_env_section: self.display_env_section,
+            env_vars,
+            hanging_indent: self.hanging_indent,
+            continuation_prefix: self.continuation_prefix,
+            #[cfg(feature = "track-caller")]
+            display_location_section: self.display_location_section,
+            #[cfg(feature = "track-caller")]
+            display_chain_locations: self.display_chain_locations,
+            capture_timestamps: self.capture_timestamps,
+            #[cfg(feature = "indicatif")]
+            progress: self.progress,
+            theme,
+            build_info,
+            #[cfg(feature = "issue-url")]
+            issue_url: self.issue_url,
+            #[cfg(feature = "issue-url")]
+            issue_metadata: metadata,
+            #[cfg(feature = "issue-url")]
+            issue_filter: self.issue_filter,
+            #[cfg(feature = "issue-url")]
+            issue_report_filter: self.issue_report_filter,
+            #[cfg(feature = "issue-url")]
+            issue_url_template: self.issue_url_template,
+        };
+
+        #[cfg(feature = "capture-spantrace")]
+        eyre::WrapErr::wrap_err(color_spantrace::set_theme(self.theme.into()), "could not set the provided `Theme` via `color_spantrace::set_theme` globally as another was already set")?;
+
+        Ok((panic_hook, eyre_hook))
+    }
+}
+
+#[cfg(feature = "capture-spantrace")]
+impl From<Theme> for color_spantrace::Theme {
+    fn from(src: Theme) -> color_spantrace::Theme {
+        color_spantrace::Theme::new()
+            .file(src.file)
+            .line_number(src.line_number)
+            .target(src.spantrace_target)
+            .fields(src.spantrace_fields)
             .active_line(src.active_line)
     }
 }
@@ -787,10 +2020,43 @@ fn eyre_frame_filters(frames: &mut Vec<&Frame>) {
     });
 }
 
+/// The name of the crate the running executable was built from, assuming it
+/// was named by cargo, i.e. after the crate and possibly with a `-<hash>`
+/// suffix for tests.
+fn executable_crate_name() -> Option<String> {
+    let exe = std::env::current_exe().ok()?;
+    let name = exe.file_stem()?.to_str()?;
+    let name = match name.rsplit_once('-') {
+        Some((name, hash)) if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) => {
+            name
+        }
+        _ => name,
+    };
+
+    Some(name.replace('-', "_"))
+}
+
+fn crate_frame_filter(crate_name: String) -> Box<FilterCallback> {
+    let prefix = format!("{}::", crate_name);
+
+    Box::new(move |frames| {
+        let is_crate_frame = |frame: &&Frame| {
+            frame.name.as_ref().map_or(false, |name| {
+                // Also match trait impls such as `<app::Config as Default>::default`
+                name.trim_start_matches('<').starts_with(&prefix)
+            })
+        };
+
+        if frames.iter().any(is_crate_frame) {
+            frames.retain(is_crate_frame);
+        }
+    })
+}
+
 struct DefaultPanicMessage(Theme);
 
 impl PanicMessage for DefaultPanicMessage {
-    fn display(&self, pi: &std::panic::PanicInfo<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn display(&self, pi: &PanicInfo<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // XXX is my assumption correct that this function is guaranteed to only run after `color_eyre` was setup successfully (including setting `THEME`), and that therefore the following line will never panic? Otherwise, we could return `fmt::Error`, but if the above is true, I like `unwrap` + a comment why this never fails better
         let theme = &self.0;
 
@@ -822,17 +2088,111 @@ impl PanicMessage for DefaultPanicMessage {
 /// A type representing an error report for a panic.
 pub struct PanicReport<'a> {
     hook: &'a PanicHook,
-    panic_info: &'a std::panic::PanicInfo<'a>,
+    panic_info: &'a PanicInfo<'a>,
     backtrace: Option<backtrace::Backtrace>,
     #[cfg(feature = "capture-spantrace")]
     span_trace: Option<tracing_error::SpanTrace>,
+    thread_name: Option<String>,
+    verbosity: Verbosity,
+    source_snippets: Option<bool>,
+    crash_report: Option<std::io::Result<PathBuf>>,
+}
+
+impl PanicReport<'_> {
+    /// Write a crash report for this panic to a new file in `dir`, returning
+    /// its path
+    fn write_crash_report(&self, dir: &std::path::Path) -> std::io::Result<PathBuf> {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+
+        let payload = self.panic_info.payload();
+        let message = payload
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| payload.downcast_ref::<&str>().cloned())
+            .unwrap_or("<non string panic payload>");
+
+        // Crash reports always include a backtrace, even if the terminal
+        // report doesn't
+        let captured;
+        let backtrace = match self.backtrace.as_ref() {
+            Some(backtrace) => backtrace,
+            None => {
+                captured = backtrace::Backtrace::new();
+                &captured
+            }
+        };
+        let backtrace = self
+            .hook
+            .format_backtrace(backtrace, Verbosity::Medium, Some(false))
+            .to_string();
+
+        #[cfg(feature = "capture-spantrace")]
+        let span_trace = self.span_trace.as_ref().map(ToString::to_string);
+        #[cfg(not(feature = "capture-spantrace"))]
+        let span_trace = None;
+
+        #[cfg(feature = "issue-url")]
+        let issue_metadata = &self.hook.issue_metadata[..];
+        #[cfg(not(feature = "issue-url"))]
+        let issue_metadata = &[];
+
+        let metadata = self
+            .hook
+            .build_info
+            .iter()
+            .chain(issue_metadata)
+            .map(|(key, value)| (key.as_str(), value.to_string()))
+            .collect();
+
+        let crash_report = crate::json::CrashReport {
+            message,
+            location: self.panic_info.location(),
+            thread: self.thread_name.as_deref(),
+            timestamp,
+            backtrace: crate::bug_report::strip_ansi(&backtrace),
+            span_trace,
+            metadata,
+        };
+
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!(
+            "crash-report-{}-{}.json",
+            timestamp,
+            std::process::id()
+        ));
+        std::fs::write(&path, crash_report.to_string())?;
+
+        Ok(path)
+    }
 }
 
 fn print_panic_info(report: &PanicReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    report.hook.panic_message.display(report.panic_info, f)?;
+    #[cfg(feature = "capture-spantrace")]
+    let span_trace_captured = report.span_trace.is_some();
+    #[cfg(not(feature = "capture-spantrace"))]
+    let span_trace_captured = false;
+
+    #[cfg(feature = "issue-url")]
+    let metadata = &report.hook.issue_metadata[..];
+    #[cfg(not(feature = "issue-url"))]
+    let metadata = &[];
+
+    let context = crate::section::PanicContext {
+        thread_name: report.thread_name.as_deref(),
+        backtrace_captured: report.backtrace.is_some(),
+        span_trace_captured,
+        metadata,
+    };
 
-    let v = panic_verbosity();
-    let capture_bt = v != Verbosity::Minimal;
+    report
+        .hook
+        .panic_message
+        .display_with_context(report.panic_info, &context, f)?;
+
+    let capture_bt = report.verbosity != Verbosity::Minimal;
 
     let mut separated = f.header("\n\n");
 
@@ -846,13 +2206,20 @@ fn print_panic_info(report: &PanicReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt
             write!(
                 &mut separated.ready(),
                 "{}",
-                crate::writers::FormattedSpanTrace(span_trace)
+                crate::writers::FormattedSpanTrace(
+                    span_trace,
+                    report.hook.reversed_spantrace,
+                    &report.hook.spantrace_filters,
+                    "SPANTRACE",
+                )
             )?;
         }
     }
 
     if let Some(bt) = report.backtrace.as_ref() {
-        let fmted_bt = report.hook.format_backtrace(bt);
+        let fmted_bt = report
+            .hook
+            .format_backtrace(bt, report.verbosity, report.source_snippets);
         write!(
             indented(&mut separated.ready()).with_format(Format::Uniform { indentation: "  " }),
             "{}",
@@ -863,6 +2230,10 @@ fn print_panic_info(report: &PanicReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt
     if report.hook.display_env_section {
         let env_section = EnvSection {
             bt_captured: &capture_bt,
+            env_vars: &report.hook.env_vars,
+            theme: report.hook.theme,
+            verbosity: report.verbosity,
+            source_snippets: report.source_snippets,
             #[cfg(feature = "capture-spantrace")]
             span_trace: report.span_trace.as_ref(),
         };
@@ -874,20 +2245,33 @@ fn print_panic_info(report: &PanicReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt
     {
         let payload = report.panic_info.payload();
 
-        if report.hook.issue_url.is_some()
-            && (*report.hook.issue_filter)(crate::ErrorKind::NonRecoverable(payload))
+        if let Some(url) = report
+            .hook
+            .issue_url
+            .as_ref()
+            .filter(|_| (*report.hook.issue_filter)(crate::ErrorKind::NonRecoverable(payload)))
+            .filter(|_| {
+                (*report.hook.issue_report_filter)(&crate::IssueContext {
+                    kind: crate::ErrorKind::NonRecoverable(payload),
+                    handler: None,
+                })
+            })
         {
-            let url = report.hook.issue_url.as_ref().unwrap();
             let payload = payload
                 .downcast_ref::<String>()
                 .map(String::as_str)
                 .or_else(|| payload.downcast_ref::<&str>().cloned())
                 .unwrap_or("<non string panic payload>");
 
-            let issue_section = crate::section::github::IssueSection::new(url, payload)
-                .with_backtrace(report.backtrace.as_ref())
-                .with_location(report.panic_info.location())
-                .with_metadata(&report.hook.issue_metadata);
+            let issue_section = crate::section::github::IssueSection::new(
+                url,
+                payload,
+                &*report.hook.issue_url_template,
+            )
+            .with_backtrace(report.backtrace.as_ref())
+            .with_location(report.panic_info.location())
+            .with_metadata(&report.hook.issue_metadata)
+            .with_hyperlink(report.hook.hyperlinks.is_some());
 
             #[cfg(feature = "capture-spantrace")]
             let issue_section = issue_section.with_span_trace(report.span_trace.as_ref());
@@ -896,12 +2280,41 @@ fn print_panic_info(report: &PanicReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt
         }
     }
 
+    if !report.hook.build_info.is_empty() {
+        write!(
+            &mut separated.ready(),
+            "{}",
+            crate::fmt::BuildInfoSection(&report.hook.build_info, report.hook.theme)
+        )?;
+    }
+
+    match &report.crash_report {
+        Some(Ok(path)) => write!(
+            &mut separated.ready(),
+            "A crash report was written to {}",
+            path.display()
+        )?,
+        Some(Err(error)) => write!(
+            &mut separated.ready(),
+            "Failed to write a crash report: {}",
+            error
+        )?,
+        None => {}
+    }
+
     Ok(())
 }
 
 impl fmt::Display for PanicReport<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        print_panic_info(self, f)
+        if !self.hook.normalized_output {
+            return print_panic_info(self, f);
+        }
+
+        let report =
+            crate::formatter::DisplayFn(|f: &mut fmt::Formatter<'_>| print_panic_info(self, f))
+                .to_string();
+        f.write_str(&crate::normalize::normalize(&report))
     }
 }
 
@@ -909,28 +2322,65 @@ impl fmt::Display for PanicReport<'_> {
 pub struct PanicHook {
     filters: Arc<[Box<FilterCallback>]>,
     section: Option<Box<dyn Display + Send + Sync + 'static>>,
+    writer: Option<PanicWriter>,
+    crash_report_dir: Option<PathBuf>,
+    chain_panic_hook: bool,
+    #[cfg(feature = "indicatif")]
+    progress: Option<indicatif::MultiProgress>,
+    previous_panic_hook_first: bool,
     panic_message: Box<dyn PanicMessage>,
     theme: Theme,
     #[cfg(feature = "capture-spantrace")]
     capture_span_trace_by_default: bool,
+    verbosity: Option<Verbosity>,
+    source_snippets: Option<bool>,
+    source_context_lines: usize,
+    reversed_stacktrace: bool,
+    collapse_recursion: bool,
+    max_frames: usize,
+    max_frame_name_length: usize,
+    hyperlinks: Option<Arc<str>>,
+    normalized_output: bool,
+    #[cfg(feature = "capture-spantrace")]
+    reversed_spantrace: bool,
+    #[cfg(feature = "capture-spantrace")]
+    spantrace_filters: Arc<SpanTraceFilters>,
     display_env_section: bool,
+    env_vars: Arc<EnvVars>,
+    #[cfg(feature = "opentelemetry")]
+    record_exceptions: bool,
+    build_info: Arc<Vec<(String, Box<dyn Display + Send + Sync + 'static>)>>,
     #[cfg(feature = "issue-url")]
     issue_url: Option<String>,
     #[cfg(feature = "issue-url")]
     issue_metadata: Arc<Vec<(String, Box<dyn Display + Send + Sync + 'static>)>>,
     #[cfg(feature = "issue-url")]
     issue_filter: Arc<IssueFilterCallback>,
+    #[cfg(feature = "issue-url")]
+    issue_report_filter: Arc<IssueReportFilterCallback>,
+    #[cfg(feature = "issue-url")]
+    issue_url_template: Arc<IssueUrlTemplate>,
 }
 
 impl PanicHook {
     pub(crate) fn format_backtrace<'a>(
         &'a self,
         trace: &'a backtrace::Backtrace,
+        verbosity: Verbosity,
+        source_snippets: Option<bool>,
     ) -> BacktraceFormatter<'a> {
         BacktraceFormatter {
             filters: &self.filters,
-            inner: trace,
+            frames: backtrace_frames(trace),
             theme: self.theme,
+            verbosity,
+            source_snippets,
+            source_context_lines: self.source_context_lines,
+            reversed: self.reversed_stacktrace,
+            collapse_recursion: self.collapse_recursion,
+            max_frames: max_frames(self.max_frames),
+            max_frame_name_length: max_frame_name_length(self.max_frame_name_length),
+            hyperlinks: self.hyperlinks.as_deref(),
         }
     }
 
@@ -942,27 +2392,79 @@ impl PanicHook {
     }
 
     /// Install self as a global panic hook via `std::panic::set_hook`.
+    ///
+    /// If [`HookBuilder::chain_panic_hook`] was enabled, the previously
+    /// installed panic hook is kept and called as well.
     pub fn install(self) {
-        std::panic::set_hook(self.into_panic_hook());
+        if !self.chain_panic_hook {
+            std::panic::set_hook(self.into_panic_hook());
+            return;
+        }
+
+        let previous = std::panic::take_hook();
+        let previous_first = self.previous_panic_hook_first;
+        let hook = self.into_panic_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            if previous_first {
+                previous(panic_info);
+                hook(panic_info);
+            } else {
+                hook(panic_info);
+                previous(panic_info);
+            }
+        }));
     }
 
     /// Convert self into the type expected by `std::panic::set_hook`.
-    pub fn into_panic_hook(
-        self,
-    ) -> Box<dyn Fn(&std::panic::PanicInfo<'_>) + Send + Sync + 'static> {
+    pub fn into_panic_hook(self) -> Box<dyn Fn(&PanicInfo<'_>) + Send + Sync + 'static> {
         Box::new(move |panic_info| {
-            eprintln!("{}", self.panic_report(panic_info));
+            let mut report = self.panic_report(panic_info);
+            if let Some(dir) = self.crash_report_dir.as_ref() {
+                report.crash_report = Some(report.write_crash_report(dir));
+            }
+
+            #[cfg(feature = "opentelemetry")]
+            if self.record_exceptions {
+                let payload = panic_info.payload();
+                let message = payload
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_else(|| "<non string panic payload>".to_owned());
+
+                crate::otel::record_exception("panic", message, || report.to_string());
+            }
+
+            self.write_report(report);
         })
     }
 
+    /// Write a panic report to the configured writer, or stderr by default
+    fn write_report(&self, report: impl Display) {
+        #[cfg(feature = "indicatif")]
+        if let Some(progress) = &self.progress {
+            return progress.suspend(|| self.write_report_to_writer(report));
+        }
+
+        self.write_report_to_writer(report)
+    }
+
+    fn write_report_to_writer(&self, report: impl Display) {
+        match &self.writer {
+            Some(writer) => {
+                let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+                let _ = writeln!(writer, "{}", report);
+                let _ = writer.flush();
+            }
+            None => eprintln!("{}", report),
+        }
+    }
+
     /// Construct a panic reporter which prints it's panic report via the
     /// `Display` trait.
-    pub fn panic_report<'a>(
-        &'a self,
-        panic_info: &'a std::panic::PanicInfo<'_>,
-    ) -> PanicReport<'a> {
-        let v = panic_verbosity();
-        let capture_bt = v != Verbosity::Minimal;
+    pub fn panic_report<'a>(&'a self, panic_info: &'a PanicInfo<'_>) -> PanicReport<'a> {
+        let verbosity = panic_verbosity(self.verbosity);
+        let capture_bt = verbosity != Verbosity::Minimal;
 
         #[cfg(feature = "capture-spantrace")]
         let span_trace = if self.spantrace_capture_enabled() {
@@ -982,6 +2484,10 @@ impl PanicHook {
             #[cfg(feature = "capture-spantrace")]
             span_trace,
             backtrace,
+            thread_name: std::thread::current().name().map(String::from),
+            verbosity,
+            source_snippets: source_snippets(self.source_snippets),
+            crash_report: None,
             hook: self,
         }
     }
@@ -992,16 +2498,51 @@ pub struct EyreHook {
     filters: Arc<[Box<FilterCallback>]>,
     #[cfg(feature = "capture-spantrace")]
     capture_span_trace_by_default: bool,
+    #[cfg(feature = "capture-spantrace")]
+    capture_span_fields: bool,
+    capture_backtrace_by_default: bool,
+    resolve_backtraces_eagerly: bool,
+    verbosity: Option<Verbosity>,
+    source_snippets: Option<bool>,
+    source_context_lines: usize,
+    reversed_stacktrace: bool,
+    collapse_recursion: bool,
+    max_frames: usize,
+    max_frame_name_length: usize,
+    hyperlinks: Option<Arc<str>>,
+    normalized_output: bool,
+    #[cfg(feature = "capture-spantrace")]
+    reversed_spantrace: bool,
+    #[cfg(feature = "capture-spantrace")]
+    spantrace_filters: Arc<SpanTraceFilters>,
+    section_order: Arc<[SectionKind]>,
+    throttle: Option<Arc<crate::throttle::Throttle>>,
+    formatter: Arc<dyn ReportFormatter>,
+    #[cfg(feature = "opentelemetry")]
+    record_exceptions: bool,
     display_env_section: bool,
+    env_vars: Arc<EnvVars>,
+    hanging_indent: bool,
+    continuation_prefix: Option<Arc<str>>,
     #[cfg(feature = "track-caller")]
     display_location_section: bool,
+    #[cfg(feature = "track-caller")]
+    display_chain_locations: bool,
+    capture_timestamps: bool,
+    #[cfg(feature = "indicatif")]
+    progress: Option<indicatif::MultiProgress>,
     theme: Theme,
+    build_info: Arc<Vec<(String, Box<dyn Display + Send + Sync + 'static>)>>,
     #[cfg(feature = "issue-url")]
     issue_url: Option<String>,
     #[cfg(feature = "issue-url")]
     issue_metadata: Arc<Vec<(String, Box<dyn Display + Send + Sync + 'static>)>>,
     #[cfg(feature = "issue-url")]
     issue_filter: Arc<IssueFilterCallback>,
+    #[cfg(feature = "issue-url")]
+    issue_report_filter: Arc<IssueReportFilterCallback>,
+    #[cfg(feature = "issue-url")]
+    issue_url_template: Arc<IssueUrlTemplate>,
 }
 
 type HookFunc = Box<
@@ -1014,8 +2555,14 @@ type HookFunc = Box<
 impl EyreHook {
     #[allow(unused_variables)]
     pub(crate) fn default(&self, error: &(dyn std::error::Error + 'static)) -> crate::Handler {
-        let backtrace = if lib_verbosity() != Verbosity::Minimal {
-            Some(backtrace::Backtrace::new())
+        let verbosity = lib_verbosity(self.verbosity, self.capture_backtrace_by_default);
+        // Don't capture a backtrace of our own if an error in the chain
+        // already captured one, see `get_deepest_backtrace`
+        let capture_bt = verbosity != Verbosity::Minimal
+            && crate::handler::get_deepest_backtrace(error).is_none();
+
+        let backtrace = if capture_bt {
+            Some(DeferredBacktrace::capture(self.resolve_backtraces_eagerly))
         } else {
             None
         };
@@ -1029,25 +2576,85 @@ impl EyreHook {
             None
         };
 
+        #[cfg(feature = "capture-spantrace")]
+        let span_fields = if self.capture_span_fields {
+            let mut fields = Vec::new();
+            tracing_error::SpanTrace::capture().with_spans(|metadata, values| {
+                if !values.is_empty() && self.spantrace_filters.keep(metadata) {
+                    fields.push((metadata.name(), self.spantrace_filters.redact(values)));
+                }
+                true
+            });
+            fields
+        } else {
+            Vec::new()
+        };
+
         crate::Handler {
             filters: self.filters.clone(),
             backtrace,
             suppress_backtrace: false,
+            verbosity,
+            source_snippets: source_snippets(self.source_snippets),
+            source_context_lines: self.source_context_lines,
+            reversed_stacktrace: self.reversed_stacktrace,
+            collapse_recursion: self.collapse_recursion,
+            max_frames: max_frames(self.max_frames),
+            max_frame_name_length: max_frame_name_length(self.max_frame_name_length),
+            hyperlinks: self.hyperlinks.clone(),
+            normalized_output: self.normalized_output,
+            #[cfg(feature = "capture-spantrace")]
+            reversed_spantrace: self.reversed_spantrace,
+            #[cfg(feature = "capture-spantrace")]
+            spantrace_filters: self.spantrace_filters.clone(),
+            section_order: self.section_order.clone(),
+            throttle: self.throttle.clone(),
+            tags: Vec::new(),
+            fields: Vec::new(),
+            severity: eyre::Severity::Error,
+            code: None,
+            formatter: self.formatter.clone(),
+            #[cfg(feature = "opentelemetry")]
+            record_exception: std::sync::atomic::AtomicBool::new(self.record_exceptions),
             #[cfg(feature = "capture-spantrace")]
             span_trace,
+            #[cfg(feature = "capture-spantrace")]
+            span_fields,
+            #[cfg(feature = "tokio")]
+            spawned_from: crate::task::spawned_from(),
             sections: Vec::new(),
             display_env_section: self.display_env_section,
+            env_vars: self.env_vars.clone(),
+            hanging_indent: self.hanging_indent,
+            continuation_prefix: self.continuation_prefix.clone(),
             #[cfg(feature = "track-caller")]
             display_location_section: self.display_location_section,
+            #[cfg(feature = "track-caller")]
+            display_chain_locations: self.display_chain_locations,
+            build_info: self.build_info.clone(),
             #[cfg(feature = "issue-url")]
             issue_url: self.issue_url.clone(),
             #[cfg(feature = "issue-url")]
             issue_metadata: self.issue_metadata.clone(),
             #[cfg(feature = "issue-url")]
             issue_filter: self.issue_filter.clone(),
+            #[cfg(feature = "issue-url")]
+            issue_report_filter: self.issue_report_filter.clone(),
+            #[cfg(feature = "issue-url")]
+            issue_url_template: self.issue_url_template.clone(),
             theme: self.theme,
             #[cfg(feature = "track-caller")]
             location: None,
+            #[cfg(feature = "track-caller")]
+            wrap_locations: Vec::new(),
+            created_at: if self.capture_timestamps {
+                Some(std::time::Instant::now())
+            } else {
+                None
+            },
+            wrap_timestamps: Vec::new(),
+            #[cfg(feature = "indicatif")]
+            progress: self.progress.clone(),
         }
     }
 
@@ -1071,29 +2678,102 @@ impl EyreHook {
 
 pub(crate) struct BacktraceFormatter<'a> {
     pub(crate) filters: &'a [Box<FilterCallback>],
-    pub(crate) inner: &'a backtrace::Backtrace,
+    pub(crate) frames: Vec<Frame>,
     pub(crate) theme: Theme,
+    pub(crate) verbosity: Verbosity,
+    pub(crate) source_snippets: Option<bool>,
+    pub(crate) source_context_lines: usize,
+    pub(crate) reversed: bool,
+    pub(crate) collapse_recursion: bool,
+    pub(crate) max_frames: usize,
+    pub(crate) max_frame_name_length: usize,
+    pub(crate) hyperlinks: Option<&'a str>,
+}
+
+impl BacktraceFormatter<'_> {
+    /// The number of lines of context to display around each frame's source,
+    /// or `None` if source snippets are disabled
+    fn source_context_lines(&self) -> Option<usize> {
+        let show = self
+            .source_snippets
+            .unwrap_or(self.verbosity >= Verbosity::Full);
+        show.then_some(self.source_context_lines)
+    }
+}
+
+/// A backtrace whose symbols are resolved the first time it is accessed,
+/// unless it was resolved when it was captured
+pub(crate) struct DeferredBacktrace {
+    unresolved: UnresolvedBacktrace,
+    trace: backtrace::Backtrace,
+    resolved: once_cell::sync::OnceCell<backtrace::Backtrace>,
+}
+
+impl DeferredBacktrace {
+    #[inline(never)]
+    pub(crate) fn capture(resolve_eagerly: bool) -> Self {
+        let trace = backtrace::Backtrace::new_unresolved();
+        let backtrace = Self {
+            unresolved: UnresolvedBacktrace::from_backtrace(&trace),
+            trace,
+            resolved: once_cell::sync::OnceCell::new(),
+        };
+
+        if resolve_eagerly {
+            backtrace.resolved();
+        }
+
+        backtrace
+    }
+
+    pub(crate) fn unresolved(&self) -> &UnresolvedBacktrace {
+        &self.unresolved
+    }
+
+    pub(crate) fn resolved(&self) -> &backtrace::Backtrace {
+        self.resolved.get_or_init(|| {
+            let mut trace = self.trace.clone();
+            trace.resolve();
+            trace
+        })
+    }
+}
+
+/// Collect the symbols of a resolved backtrace into a list of frames.
+pub(crate) fn backtrace_frames(trace: &backtrace::Backtrace) -> Vec<Frame> {
+    trace
+        .frames()
+        .iter()
+        .flat_map(|frame| frame.symbols())
+        .zip(1usize..)
+        .map(|(sym, n)| Frame {
+            name: sym.name().map(|x| x.to_string()),
+            lineno: sym.lineno(),
+            filename: sym.filename().map(|x| x.into()),
+            n,
+        })
+        .collect()
+}
+
+/// Convert a `std::backtrace::Backtrace` into frames, see
+/// [`eyre::BacktraceFrame::parse`].
+pub(crate) fn std_backtrace_frames(trace: &std::backtrace::Backtrace) -> Vec<Frame> {
+    eyre::BacktraceFrame::parse(&trace.to_string())
+        .into_iter()
+        .map(|frame| Frame {
+            n: frame.n,
+            name: frame.name,
+            lineno: frame.lineno,
+            filename: frame.filename.map(Into::into),
+        })
+        .collect()
 }
 
 impl fmt::Display for BacktraceFormatter<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:━^80}", " BACKTRACE ")?;
 
-        // Collect frame info.
-        let frames: Vec<_> = self
-            .inner
-            .frames()
-            .iter()
-            .flat_map(|frame| frame.symbols())
-            .zip(1usize..)
-            .map(|(sym, n)| Frame {
-                name: sym.name().map(|x| x.to_string()),
-                lineno: sym.lineno(),
-                filename: sym.filename().map(|x| x.into()),
-                n,
-            })
-            .collect();
-
+        let frames = &self.frames;
         let mut filtered_frames = frames.iter().collect();
         match env::var("COLORBT_SHOW_HIDDEN").ok().as_deref() {
             Some("1") | Some("on") | Some("y") => (),
@@ -1116,69 +2796,531 @@ impl fmt::Display for BacktraceFormatter<'_> {
 
         let mut buf = String::new();
 
+        macro_rules! print_decorated {
+            ($($arg:tt)*) => {
+                buf.clear();
+                write!(&mut buf, $($arg)*).expect("writing to strings doesn't panic");
+                write!(
+                    &mut separated.ready(),
+                    "{:^80}",
+                    buf.style(self.theme.hidden_frames)
+                )?;
+            };
+        }
+
         macro_rules! print_hidden {
             ($n:expr) => {
                 let n = $n;
-                buf.clear();
-                write!(
-                    &mut buf,
+                print_decorated!(
                     "{decorator} {n} frame{plural} hidden {decorator}",
                     n = n,
                     plural = if n == 1 { "" } else { "s" },
                     decorator = "⋮",
-                )
-                .expect("writing to strings doesn't panic");
-                write!(
-                    &mut separated.ready(),
-                    "{:^80}",
-                    buf.style(self.theme.hidden_frames)
-                )?;
+                );
             };
         }
 
+        let mut lines = Vec::new();
         let mut last_n = 0;
         for frame in &filtered_frames {
             let frame_delta = frame.n - last_n - 1;
             if frame_delta != 0 {
-                print_hidden!(frame_delta);
+                lines.push(BacktraceLine::Hidden(frame_delta));
             }
-            write!(&mut separated.ready(), "{}", StyledFrame(frame, self.theme))?;
+            lines.push(BacktraceLine::Frame(frame));
             last_n = frame.n;
         }
 
         let last_filtered_n = filtered_frames.last().unwrap().n;
         let last_unfiltered_n = frames.last().unwrap().n;
         if last_filtered_n < last_unfiltered_n {
-            print_hidden!(last_unfiltered_n - last_filtered_n);
+            lines.push(BacktraceLine::Hidden(last_unfiltered_n - last_filtered_n));
+        }
+
+        if self.reversed {
+            lines.reverse();
+        }
+
+        if self.collapse_recursion {
+            lines = collapse_repeated_frames(lines);
+        }
+
+        if self.max_frames != 0 {
+            truncate_frames(&mut lines, self.max_frames);
+        }
+
+        for line in lines {
+            match line {
+                BacktraceLine::Frame(frame) => write!(
+                    &mut separated.ready(),
+                    "{}",
+                    StyledFrame(
+                        frame,
+                        self.theme,
+                        self.source_context_lines(),
+                        self.hyperlinks,
+                        self.max_frame_name_length
+                    )
+                )?,
+                BacktraceLine::Hidden(n) => {
+                    print_hidden!(n);
+                }
+                BacktraceLine::Truncated(n) => {
+                    print_decorated!("⋮ {} more frame{} ⋮", n, if n == 1 { "" } else { "s" },);
+                }
+                BacktraceLine::Repeated { frames: 1, times } => {
+                    print_decorated!(
+                        "⋮ frame repeated {} time{} ⋮",
+                        times,
+                        if times == 1 { "" } else { "s" },
+                    );
+                }
+                BacktraceLine::Repeated { frames, times } => {
+                    print_decorated!(
+                        "⋮ {} frames repeated {} time{} ⋮",
+                        frames,
+                        times,
+                        if times == 1 { "" } else { "s" },
+                    );
+                }
+            }
         }
 
         Ok(())
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-pub(crate) enum Verbosity {
+/// A frame, or a run of hidden frames, in the order it is printed
+#[derive(Clone, Copy)]
+enum BacktraceLine<'a> {
+    Frame(&'a Frame),
+    Hidden(usize),
+    /// The preceding `frames` frames were repeated `times` more times
+    Repeated {
+        frames: usize,
+        times: usize,
+    },
+    /// The remaining frames were left out due to the frame limit
+    Truncated(usize),
+}
+
+impl BacktraceLine<'_> {
+    /// Whether both lines are frames of the same function at the same line
+    fn same_frame(&self, other: &Self) -> bool {
+        match (self, other) {
+            (BacktraceLine::Frame(a), BacktraceLine::Frame(b)) => {
+                a.name == b.name && a.filename == b.filename && a.lineno == b.lineno
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Replace every line after the `max_frames`th printed frame with a single
+/// line counting the frames that were left out
+fn truncate_frames(lines: &mut Vec<BacktraceLine<'_>>, max_frames: usize) {
+    let cutoff = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| matches!(line, BacktraceLine::Frame(_)))
+        .nth(max_frames)
+        .map(|(i, _)| i);
+
+    if let Some(cutoff) = cutoff {
+        let remaining = lines
+            .drain(cutoff..)
+            .map(|line| match line {
+                BacktraceLine::Frame(_) => 1,
+                BacktraceLine::Hidden(n) | BacktraceLine::Truncated(n) => n,
+                BacktraceLine::Repeated { frames, times } => frames * times,
+            })
+            .sum();
+        lines.push(BacktraceLine::Truncated(remaining));
+    }
+}
+
+/// Shorten `name` to at most `max_length` characters by replacing its middle
+/// with an ellipsis, unless `max_length` is 0
+fn ellipsize(name: &str, max_length: usize) -> std::borrow::Cow<'_, str> {
+    let length = name.chars().count();
+    if max_length == 0 || length <= max_length {
+        return name.into();
+    }
+
+    let tail = (max_length - 1) / 2;
+    let head = max_length - 1 - tail;
+    let mut shortened: String = name.chars().take(head).collect();
+    shortened.push('…');
+    shortened.extend(name.chars().skip(length - tail));
+    shortened.into()
+}
+
+/// Replace runs of a repeated frame, or of a repeated cycle of frames, with
+/// their first occurrence followed by a `Repeated` line.
+fn collapse_repeated_frames(lines: Vec<BacktraceLine<'_>>) -> Vec<BacktraceLine<'_>> {
+    // Mutual recursion longer than this is rare, and would make the search
+    // needlessly expensive
+    const MAX_CYCLE: usize = 8;
+
+    // How many times the `len` lines at `start` are repeated right after them
+    let repeats = |start: usize, len: usize| {
+        let cycle = &lines[start..start + len];
+        let mut times = 0;
+        while let Some(next) = lines.get(start + len * (times + 1)..start + len * (times + 2)) {
+            if !cycle.iter().zip(next).all(|(a, b)| a.same_frame(b)) {
+                break;
+            }
+            times += 1;
+        }
+        times
+    };
+
+    let mut collapsed = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        // Pick the cycle that hides the most frames, preferring the shortest
+        // cycle on ties
+        let best = (1..=MAX_CYCLE)
+            .take_while(|len| i + 2 * len <= lines.len())
+            .map(|len| (len, repeats(i, len)))
+            .filter(|&(_, times)| times > 0)
+            .max_by(|(l1, t1), (l2, t2)| (l1 * t1).cmp(&(l2 * t2)).then(l2.cmp(l1)));
+
+        match best {
+            Some((len, times)) => {
+                collapsed.extend_from_slice(&lines[i..i + len]);
+                collapsed.push(BacktraceLine::Repeated { frames: len, times });
+                i += len * (times + 1);
+            }
+            None => {
+                collapsed.push(lines[i]);
+                i += 1;
+            }
+        }
+    }
+
+    collapsed
+}
+
+/// Whether file locations and the issue url are rendered as clickable
+/// terminal hyperlinks, see [`HookBuilder::hyperlinks`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HyperlinkMode {
+    /// Use hyperlinks if stderr is a terminal and `TERM` is not `dumb`
+    Auto,
+    /// Always use hyperlinks
+    Always,
+    /// Never use hyperlinks
+    Never,
+}
+
+impl HyperlinkMode {
+    fn enabled(self) -> bool {
+        use is_terminal::IsTerminal;
+
+        match self {
+            HyperlinkMode::Auto => {
+                std::io::stderr().is_terminal()
+                    && std::env::var("TERM").map_or(true, |term| term != "dumb")
+            }
+            HyperlinkMode::Always => true,
+            HyperlinkMode::Never => false,
+        }
+    }
+}
+
+/// Percent encode the characters of a path which aren't allowed in urls
+fn percent_encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char)
+            }
+            // Windows path separators
+            b'\\' => encoded.push('/'),
+            _ => write!(encoded, "%{:02X}", byte).expect("writing to a string doesn't panic"),
+        }
+    }
+
+    encoded
+}
+
+/// The level of detail included in panic and error reports
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Verbosity {
+    /// Omit the backtrace
     Minimal,
+    /// Include the backtrace
     Medium,
+    /// Include the backtrace along with source snippets for each frame
     Full,
 }
 
-pub(crate) fn panic_verbosity() -> Verbosity {
+impl Verbosity {
+    /// Map the number of occurrences of a `-v` command line flag to a verbosity
+    ///
+    /// No flag maps to [`Minimal`](Verbosity::Minimal), `-v` to
+    /// [`Medium`](Verbosity::Medium) and `-vv` or more to
+    /// [`Full`](Verbosity::Full).
+    pub fn from_occurrences(occurrences: u8) -> Self {
+        match occurrences {
+            0 => Verbosity::Minimal,
+            1 => Verbosity::Medium,
+            _ => Verbosity::Full,
+        }
+    }
+
+    fn from_backtrace_var(value: &str) -> Self {
+        match value {
+            "full" => Verbosity::Full,
+            "0" => Verbosity::Minimal,
+            _ => Verbosity::Medium,
+        }
+    }
+
+    fn from_verbosity_var(value: &str) -> Self {
+        match value {
+            "minimal" => Verbosity::Minimal,
+            "medium" => Verbosity::Medium,
+            value => Verbosity::from_backtrace_var(value),
+        }
+    }
+}
+
+/// The sections of an error report, used to configure the order in which
+/// they are printed via [`HookBuilder::section_order`]
+///
+/// The list of errors the report consists of is always printed first.
+/// Sections whose feature is disabled or which have nothing to display are
+/// skipped.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum SectionKind {
+    /// Where the report was created, requires the `track-caller` feature
+    Location,
+    /// Errors attached via [`Section::error`](crate::Section::error)
+    Errors,
+    /// Custom sections attached via [`Section::section`](crate::Section::section)
+    Custom,
+    /// Fields attached via `Report::wrap_err_field`
+    Fields,
+    /// Fields of the spans that were active when the report was created,
+    /// requires the `capture-spantrace` feature
+    SpanFields,
+    /// The `SpanTrace`, requires the `capture-spantrace` feature
+    SpanTrace,
+    /// The backtrace
+    Backtrace,
+    /// Notes, warnings and suggestions
+    Notes,
+    /// The hints about environment variables that control the report
+    Env,
+    /// The link for creating a GitHub issue, requires the `issue-url` feature
+    IssueUrl,
+    /// The metadata added via [`HookBuilder::add_metadata`]
+    BuildInfo,
+}
+
+impl SectionKind {
+    /// All sections in the order they are printed by default
+    pub const DEFAULT_ORDER: &'static [SectionKind] = &[
+        SectionKind::Location,
+        SectionKind::Errors,
+        SectionKind::Custom,
+        SectionKind::Fields,
+        SectionKind::SpanFields,
+        SectionKind::SpanTrace,
+        SectionKind::Backtrace,
+        SectionKind::Notes,
+        SectionKind::Env,
+        SectionKind::IssueUrl,
+        SectionKind::BuildInfo,
+    ];
+}
+
+/// The verbosity set via [`set_verbosity`], encoded as `Verbosity as u8 + 1`,
+/// or 0 if it was never called.
+static VERBOSITY_OVERRIDE: AtomicU8 = AtomicU8::new(0);
+
+/// Change the verbosity of panic and error reports at runtime
+///
+/// This overrides the verbosity configured on the [`HookBuilder`] for all
+/// reports created afterwards, e.g. once command line arguments have been
+/// parsed after installing the hooks. The `COLOR_EYRE_PANIC_VERBOSITY` and
+/// `COLOR_EYRE_ERROR_VERBOSITY` environment variables still take precedence.
+///
+/// # Examples
+///
+/// ```rust
+/// use color_eyre::config::{set_verbosity, Verbosity};
+///
+/// color_eyre::install().unwrap();
+///
+/// # let verbose = 1;
+/// set_verbosity(Verbosity::from_occurrences(verbose));
+/// ```
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY_OVERRIDE.store(verbosity as u8 + 1, Ordering::Relaxed);
+}
+
+fn verbosity_override() -> Option<Verbosity> {
+    match VERBOSITY_OVERRIDE.load(Ordering::Relaxed) {
+        1 => Some(Verbosity::Minimal),
+        2 => Some(Verbosity::Medium),
+        3 => Some(Verbosity::Full),
+        _ => None,
+    }
+}
+
+/// Determine the verbosity of panic reports.
+///
+/// `COLOR_EYRE_PANIC_VERBOSITY` takes precedence over the verbosity set via
+/// `set_verbosity`, then the one configured via `HookBuilder::panic_verbosity`,
+/// which in turn takes precedence over `RUST_BACKTRACE`.
+pub(crate) fn panic_verbosity(configured: Option<Verbosity>) -> Verbosity {
+    if let Ok(s) = env::var("COLOR_EYRE_PANIC_VERBOSITY") {
+        return Verbosity::from_verbosity_var(&s);
+    }
+
+    if let Some(verbosity) = verbosity_override().or(configured) {
+        return verbosity;
+    }
+
     match env::var("RUST_BACKTRACE") {
-        Ok(s) if s == "full" => Verbosity::Full,
-        Ok(s) if s != "0" => Verbosity::Medium,
+        Ok(s) => Verbosity::from_backtrace_var(&s),
         _ => Verbosity::Minimal,
     }
 }
 
-pub(crate) fn lib_verbosity() -> Verbosity {
+/// Determine whether source snippets were explicitly enabled or disabled.
+///
+/// `COLOR_EYRE_SOURCE` takes precedence over the setting configured via
+/// `HookBuilder::display_source_snippets`. When neither is set, snippets are
+/// displayed depending on the verbosity.
+pub(crate) fn source_snippets(configured: Option<bool>) -> Option<bool> {
+    match env::var("COLOR_EYRE_SOURCE") {
+        Ok(s) => Some(s != "0"),
+        Err(_) => configured,
+    }
+}
+
+/// Determine the maximum number of frames printed in a backtrace.
+///
+/// `COLOR_EYRE_MAX_FRAMES` takes precedence over the limit configured via
+/// `HookBuilder::max_frames`.
+pub(crate) fn max_frames(configured: usize) -> usize {
+    env::var("COLOR_EYRE_MAX_FRAMES")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(configured)
+}
+
+/// Determine the maximum length of function names printed in a backtrace.
+///
+/// `COLOR_EYRE_MAX_FRAME_NAME_LENGTH` takes precedence over the limit
+/// configured via `HookBuilder::max_frame_name_length`.
+pub(crate) fn max_frame_name_length(configured: usize) -> usize {
+    env::var("COLOR_EYRE_MAX_FRAME_NAME_LENGTH")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(configured)
+}
+
+/// Determine the verbosity of error reports.
+///
+/// `COLOR_EYRE_ERROR_VERBOSITY` takes precedence over the verbosity set via
+/// `set_verbosity`, then the one configured via `HookBuilder::error_verbosity`,
+/// which in turn takes precedence over `RUST_LIB_BACKTRACE` and
+/// `RUST_BACKTRACE`. When none of these are set, backtraces are captured if
+/// `HookBuilder::capture_backtrace_by_default` was enabled.
+pub(crate) fn lib_verbosity(
+    configured: Option<Verbosity>,
+    capture_backtrace_by_default: bool,
+) -> Verbosity {
+    if let Ok(s) = env::var("COLOR_EYRE_ERROR_VERBOSITY") {
+        return Verbosity::from_verbosity_var(&s);
+    }
+
+    if let Some(verbosity) = verbosity_override().or(configured) {
+        return verbosity;
+    }
+
     match env::var("RUST_LIB_BACKTRACE").or_else(|_| env::var("RUST_BACKTRACE")) {
-        Ok(s) if s == "full" => Verbosity::Full,
-        Ok(s) if s != "0" => Verbosity::Medium,
+        Ok(s) => Verbosity::from_backtrace_var(&s),
+        _ if capture_backtrace_by_default => Verbosity::Medium,
         _ => Verbosity::Minimal,
     }
 }
 
+/// Callback deciding whether the value of an environment variable is
+/// redacted in the environment section, see
+/// [`HookBuilder::redact_env_vars`]
+pub type RedactEnvVarCallback = dyn Fn(&str, &str) -> bool + Send + Sync + 'static;
+
+/// The environment variables displayed in the environment section
+pub(crate) struct EnvVars {
+    pub(crate) names: Vec<String>,
+    pub(crate) redact: Arc<RedactEnvVarCallback>,
+}
+
+/// The span filter and field redaction configured via
+/// [`HookBuilder::spantrace_filter`] and [`HookBuilder::redact_span_fields`]
+#[cfg(feature = "capture-spantrace")]
+pub(crate) struct SpanTraceFilters {
+    pub(crate) filter: Option<Arc<SpanTraceFilterCallback>>,
+    pub(crate) redact: Option<Arc<RedactSpanFieldCallback>>,
+}
+
+#[cfg(feature = "capture-spantrace")]
+impl SpanTraceFilters {
+    pub(crate) fn keep(&self, metadata: &tracing_core::Metadata<'static>) -> bool {
+        self.filter.as_ref().map_or(true, |filter| filter(metadata))
+    }
+
+    pub(crate) fn redact(&self, fields: &str) -> String {
+        match &self.redact {
+            Some(redact) => color_spantrace::redact_fields(fields, &**redact),
+            None => fields.to_owned(),
+        }
+    }
+
+    pub(crate) fn apply<'a>(
+        &'a self,
+        span_trace: color_spantrace::ColorSpanTrace<'a>,
+    ) -> color_spantrace::ColorSpanTrace<'a> {
+        let span_trace = match &self.filter {
+            Some(filter) => span_trace.span_filter(&**filter),
+            None => span_trace,
+        };
+
+        match &self.redact {
+            Some(redact) => span_trace.field_redactor(&**redact),
+            None => span_trace,
+        }
+    }
+}
+
+fn is_sensitive_env_var(name: &str) -> bool {
+    const SENSITIVE: &[&str] = &["SECRET", "TOKEN", "PASSWORD", "KEY", "CREDENTIAL"];
+
+    let name = name.to_ascii_uppercase();
+    SENSITIVE.iter().any(|word| name.contains(word))
+}
+
+/// Callback for skipping spans of `SpanTrace`s, see
+/// [`HookBuilder::spantrace_filter`]
+#[cfg(feature = "capture-spantrace")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capture-spantrace")))]
+pub type SpanTraceFilterCallback =
+    dyn Fn(&tracing_core::Metadata<'static>) -> bool + Send + Sync + 'static;
+
+/// Callback for redacting the values of span fields, see
+/// [`HookBuilder::redact_span_fields`]
+#[cfg(feature = "capture-spantrace")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capture-spantrace")))]
+pub type RedactSpanFieldCallback = dyn Fn(&str, &str) -> bool + Send + Sync + 'static;
+
 /// Callback for filtering a vector of `Frame`s
 pub type FilterCallback = dyn Fn(&mut Vec<&Frame>) + Send + Sync + 'static;
 
@@ -1186,3 +3328,19 @@ pub type FilterCallback = dyn Fn(&mut Vec<&Frame>) + Send + Sync + 'static;
 #[cfg(feature = "issue-url")]
 #[cfg_attr(docsrs, doc(cfg(feature = "issue-url")))]
 pub type IssueFilterCallback = dyn Fn(crate::ErrorKind<'_>) -> bool + Send + Sync + 'static;
+
+/// Callback for filtering issue url generation with access to the full error report
+#[cfg(feature = "issue-url")]
+#[cfg_attr(docsrs, doc(cfg(feature = "issue-url")))]
+pub type IssueReportFilterCallback =
+    dyn Fn(&crate::IssueContext<'_>) -> bool + Send + Sync + 'static;
+
+/// Callback for turning an issue report into the issue URL printed in error
+/// reports and panics
+///
+/// See the [`issue_template`](crate::issue_template) module for the built-in
+/// templates.
+#[cfg(feature = "issue-url")]
+#[cfg_attr(docsrs, doc(cfg(feature = "issue-url")))]
+pub type IssueUrlTemplate =
+    dyn Fn(&crate::issue_template::IssueData<'_>) -> String + Send + Sync + 'static;
@@ -23,3 +23,125 @@ impl fmt::Display for LocationSection<'_> {
         Ok(())
     }
 }
+
+/// A duration rounded to milliseconds below a minute and to seconds above,
+/// e.g. `120ms` or `95s`
+pub(crate) struct Elapsed(pub(crate) std::time::Duration);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.as_secs() < 60 {
+            write!(f, "{}ms", self.0.as_millis())
+        } else {
+            write!(f, "{}s", self.0.as_secs())
+        }
+    }
+}
+
+/// The structured fields attached to a report via `Report::wrap_err_field`
+pub(crate) struct FieldsSection<'a>(
+    pub(crate) &'a [(&'static str, String)],
+    pub(crate) crate::config::Theme,
+);
+
+impl fmt::Display for FieldsSection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = self.1;
+        let width = self.0.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+        write!(f, "Fields:")?;
+        for (key, value) in self.0 {
+            write!(
+                f,
+                "\n   {}{:pad$} = {}",
+                key.style(theme.spantrace_target),
+                "",
+                value.style(theme.spantrace_fields),
+                pad = width - key.len()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) struct BuildInfoSection<'a>(
+    pub(crate) &'a [(String, Box<dyn fmt::Display + Send + Sync + 'static>)],
+    pub(crate) crate::config::Theme,
+);
+
+impl fmt::Display for BuildInfoSection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = self.1;
+        let width = self.0.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+        write!(f, "Build Info:")?;
+        for (key, value) in self.0 {
+            write!(
+                f,
+                "\n   {}{:pad$} = {}",
+                key.style(theme.spantrace_target),
+                "",
+                value.style(theme.spantrace_fields),
+                pad = width - key.len()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) struct EnvVarsSection<'a>(
+    pub(crate) &'a crate::config::EnvVars,
+    pub(crate) crate::config::Theme,
+);
+
+impl fmt::Display for EnvVarsSection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = self.1;
+        let names = &self.0.names;
+        let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+        write!(f, "Environment:")?;
+        for name in names {
+            let value = match std::env::var(name) {
+                Ok(value) if (self.0.redact)(name, &value) => "<redacted>".into(),
+                Ok(value) => value,
+                Err(std::env::VarError::NotUnicode(_)) => "<not unicode>".into(),
+                Err(std::env::VarError::NotPresent) => "<unset>".into(),
+            };
+            write!(
+                f,
+                "\n   {}{:pad$} = {}",
+                name.style(theme.spantrace_target),
+                "",
+                value.style(theme.spantrace_fields),
+                pad = width - name.len()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The fields of the spans that were active when a report was created
+#[cfg(feature = "capture-spantrace")]
+pub(crate) struct SpanFieldsSection<'a>(
+    pub(crate) &'a [(&'static str, String)],
+    pub(crate) crate::config::Theme,
+);
+
+#[cfg(feature = "capture-spantrace")]
+impl fmt::Display for SpanFieldsSection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = self.1;
+        write!(f, "Span context:")?;
+        for (name, fields) in self.0 {
+            write!(
+                f,
+                "\n   {}: {}",
+                name.style(theme.spantrace_target),
+                fields.style(theme.spantrace_fields)
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+//! Pluggable rendering of error reports
+//!
+//! `color-eyre`'s [`Handler`] captures backtraces, spantraces and sections
+//! when a report is constructed, and delegates rendering them to a
+//! [`ReportFormatter`]. The formatter is selected globally via
+//! [`HookBuilder::formatter`] or for a single report via [`Formatted`].
+//!
+//! [`HookBuilder::formatter`]: crate::config::HookBuilder::formatter
+use crate::{eyre::Report, Handler};
+use std::fmt;
+
+/// A presentation layer for error reports
+///
+/// Implementations only decide how a report is printed; they can access
+/// everything the [`Handler`] captured through its public accessors, such as
+/// [`Handler::backtrace`] or [`Handler::suggestions`].
+///
+/// # Examples
+///
+/// ```rust
+/// use color_eyre::{formatter::ReportFormatter, Handler};
+/// use std::fmt;
+///
+/// struct OneLine;
+///
+/// impl ReportFormatter for OneLine {
+///     fn format(
+///         &self,
+///         _handler: &Handler,
+///         error: &(dyn std::error::Error + 'static),
+///         f: &mut fmt::Formatter<'_>,
+///     ) -> fmt::Result {
+///         let chain: Vec<_> = color_eyre::eyre::Chain::new(error)
+///             .map(ToString::to_string)
+///             .collect();
+///         write!(f, "{}", chain.join(": "))
+///     }
+/// }
+///
+/// color_eyre::config::HookBuilder::default()
+///     .formatter(OneLine)
+///     .install()
+///     .unwrap();
+/// ```
+pub trait ReportFormatter: Send + Sync + 'static {
+    /// Render the report for `error`, using the state captured by `handler`
+    fn format(
+        &self,
+        handler: &Handler,
+        error: &(dyn std::error::Error + 'static),
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result;
+}
+
+/// The default colorful, multi-section report format
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pretty;
+
+impl ReportFormatter for Pretty {
+    fn format(
+        &self,
+        handler: &Handler,
+        error: &(dyn std::error::Error + 'static),
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        handler.format_pretty(error, f)
+    }
+}
+
+/// The [`Pretty`] report format without any ANSI escape codes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Plain;
+
+impl ReportFormatter for Plain {
+    fn format(
+        &self,
+        handler: &Handler,
+        error: &(dyn std::error::Error + 'static),
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let pretty =
+            DisplayFn(|f: &mut fmt::Formatter<'_>| handler.format_pretty(error, f)).to_string();
+        f.write_str(&crate::bug_report::strip_ansi(&pretty))
+    }
+}
+
+/// A single-line JSON object, for consumption by log aggregators
+///
+/// The object contains the error message, its causes, the location the
+/// report was constructed at and any notes, warnings and suggestions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+impl ReportFormatter for Json {
+    fn format(
+        &self,
+        handler: &Handler,
+        error: &(dyn std::error::Error + 'static),
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        #[cfg(feature = "track-caller")]
+        let location = handler.location();
+        #[cfg(not(feature = "track-caller"))]
+        let location = None;
+
+        crate::json::write_report(f, error, location, Some(handler))
+    }
+}
+
+/// Renders a report with a specific [`ReportFormatter`], regardless of the
+/// formatter configured on the installed hook
+///
+/// Reports whose handler isn't a `color-eyre` [`Handler`] are printed with
+/// their own `Debug` implementation.
+///
+/// # Examples
+///
+/// ```rust
+/// use color_eyre::{eyre::eyre, formatter::{Formatted, Json}};
+///
+/// let report = eyre!("disk full");
+/// eprintln!("{}", Formatted::new(&report, &Json));
+/// ```
+pub struct Formatted<'a> {
+    report: &'a Report,
+    formatter: &'a dyn ReportFormatter,
+}
+
+impl<'a> Formatted<'a> {
+    /// Render `report` with `formatter`
+    pub fn new(report: &'a Report, formatter: &'a dyn ReportFormatter) -> Self {
+        Self { report, formatter }
+    }
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.report.handler().downcast_ref::<Handler>() {
+            Some(handler) => self.formatter.format(handler, &**self.report, f),
+            None => fmt::Debug::fmt(self.report, f),
+        }
+    }
+}
+
+impl fmt::Debug for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Adapts a closure into a `Display` implementation
+pub(crate) struct DisplayFn<F>(pub(crate) F);
+
+impl<F> fmt::Display for DisplayFn<F>
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
@@ -1,8 +1,11 @@
 use crate::{
-    config::BacktraceFormatter,
+    config::{
+        backtrace_frames, std_backtrace_frames, BacktraceFormatter, DeferredBacktrace, SectionKind,
+        UnresolvedBacktrace,
+    },
     section::help::HelpInfo,
-    writers::{EnvSection, WriterExt},
-    Handler,
+    writers::{EnvSection, HangingIndent, WriterExt},
+    ErrorTag, Handler,
 };
 use backtrace::Backtrace;
 use indenter::{indented, Format};
@@ -18,8 +21,14 @@ impl std::fmt::Debug for Handler {
 
 impl Handler {
     /// Return a reference to the captured `Backtrace` type
+    ///
+    /// Unless [`HookBuilder::resolve_backtraces_eagerly`] is enabled, the
+    /// symbols of the backtrace are resolved by the first call to this method
+    /// or the first time the report is printed.
+    ///
+    /// [`HookBuilder::resolve_backtraces_eagerly`]: crate::config::HookBuilder::resolve_backtraces_eagerly
     pub fn backtrace(&self) -> Option<&Backtrace> {
-        self.backtrace.as_ref()
+        self.backtrace.as_ref().map(DeferredBacktrace::resolved)
     }
 
     /// Return a reference to the captured `SpanTrace` type
@@ -29,14 +38,186 @@ impl Handler {
         self.span_trace.as_ref()
     }
 
+    /// Return the span traces of the places the tokio task the report was
+    /// created in was spawned from, innermost first, see [`crate::task`]
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub fn spawned_from(&self) -> &[SpanTrace] {
+        &self.spawned_from
+    }
+
+    /// Return the fields of the spans that were active when the report was
+    /// created, innermost span first
+    ///
+    /// This is only populated when the hook was configured with
+    /// [`HookBuilder::capture_span_fields`].
+    ///
+    /// [`HookBuilder::capture_span_fields`]: crate::config::HookBuilder::capture_span_fields
+    #[cfg(feature = "capture-spantrace")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "capture-spantrace")))]
+    pub fn span_fields(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.span_fields
+            .iter()
+            .map(|(name, fields)| (*name, fields.as_str()))
+    }
+
+    /// Iterate over the structured fields attached to the report via
+    /// [`Report::wrap_err_field`](eyre::Report::wrap_err_field)
+    pub fn fields(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.fields
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+    }
+
+    /// The severity of the report, set via
+    /// [`Report::set_severity`](eyre::Report::set_severity)
+    pub fn severity(&self) -> eyre::Severity {
+        self.severity
+    }
+
+    /// The stable error code of the report, set via
+    /// [`Report::with_code`](eyre::Report::with_code)
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Return the captured backtrace without resolving its symbols
+    ///
+    /// This is set whenever [`backtrace`](Handler::backtrace) is, but unlike
+    /// it never triggers symbol resolution.
+    pub fn unresolved_backtrace(&self) -> Option<&UnresolvedBacktrace> {
+        self.backtrace.as_ref().map(DeferredBacktrace::unresolved)
+    }
+
+    /// Return the [`ErrorTag`]s attached to the report
+    pub fn tags(&self) -> &[ErrorTag] {
+        &self.tags
+    }
+
+    /// Returns `true` if the report was tagged with `tag`
+    pub fn has_tag(&self, tag: &ErrorTag) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// Return the location the report was constructed at
+    #[cfg(feature = "track-caller")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "track-caller")))]
+    pub fn location(&self) -> Option<&'static std::panic::Location<'static>> {
+        self.location
+    }
+
+    /// The location the entry at `position` in the error chain was created
+    /// at, if known
+    ///
+    /// Each `wrap_err` layer is one entry of the chain, with the newest
+    /// outermost, followed by the error the report was constructed from.
+    #[cfg(feature = "track-caller")]
+    fn chain_location(&self, position: usize) -> Option<&'static std::panic::Location<'static>> {
+        let wrapped = self.wrap_locations.len();
+        match position.cmp(&wrapped) {
+            std::cmp::Ordering::Less => self.wrap_locations[wrapped - 1 - position],
+            std::cmp::Ordering::Equal => self.location,
+            std::cmp::Ordering::Greater => None,
+        }
+    }
+
+    /// The time that passed between the creation of the entry below the
+    /// entry at `position` in the error chain and the entry itself, if
+    /// timestamps are captured and the entry was added by `wrap_err`
+    fn chain_elapsed(&self, position: usize) -> Option<std::time::Duration> {
+        let created_at = self.created_at?;
+        let wrapped = self.wrap_timestamps.len();
+        if position >= wrapped {
+            return None;
+        }
+
+        let layer = wrapped - 1 - position;
+        let previous = match layer {
+            0 => created_at,
+            _ => self.wrap_timestamps[layer - 1],
+        };
+        Some(self.wrap_timestamps[layer].saturating_duration_since(previous))
+    }
+
+    /// Return the notes attached to the report
+    pub fn notes(&self) -> impl Iterator<Item = &(dyn std::fmt::Display + Send + Sync)> {
+        self.sections.iter().filter_map(|section| match section {
+            HelpInfo::Note(note, _) => Some(note.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Return the warnings attached to the report
+    pub fn warnings(&self) -> impl Iterator<Item = &(dyn std::fmt::Display + Send + Sync)> {
+        self.sections.iter().filter_map(|section| match section {
+            HelpInfo::Warning(warning, _) => Some(warning.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Return the suggestions attached to the report
+    pub fn suggestions(&self) -> impl Iterator<Item = &(dyn std::fmt::Display + Send + Sync)> {
+        self.sections.iter().filter_map(|section| match section {
+            HelpInfo::Suggestion(suggestion, _) => Some(suggestion.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Render the report with the configured formatter, normalizing it if
+    /// [`HookBuilder::normalized_output`] is enabled.
+    ///
+    /// [`HookBuilder::normalized_output`]: crate::config::HookBuilder::normalized_output
+    fn format_report(
+        &self,
+        error: &(dyn std::error::Error + 'static),
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        if !self.normalized_output {
+            return self.formatter.format(self, error, f);
+        }
+
+        let report = crate::formatter::DisplayFn(|f: &mut core::fmt::Formatter<'_>| {
+            self.formatter.format(self, error, f)
+        })
+        .to_string();
+        f.write_str(&crate::normalize::normalize(&report))
+    }
+
     pub(crate) fn format_backtrace<'a>(
         &'a self,
         trace: &'a backtrace::Backtrace,
     ) -> BacktraceFormatter<'a> {
         BacktraceFormatter {
             filters: &self.filters,
-            inner: trace,
+            frames: backtrace_frames(trace),
+            theme: self.theme,
+            verbosity: self.verbosity,
+            source_snippets: self.source_snippets,
+            source_context_lines: self.source_context_lines,
+            reversed: self.reversed_stacktrace,
+            collapse_recursion: self.collapse_recursion,
+            max_frames: self.max_frames,
+            max_frame_name_length: self.max_frame_name_length,
+            hyperlinks: self.hyperlinks.as_deref(),
+        }
+    }
+
+    pub(crate) fn format_std_backtrace(
+        &self,
+        trace: &std::backtrace::Backtrace,
+    ) -> BacktraceFormatter<'_> {
+        BacktraceFormatter {
+            filters: &self.filters,
+            frames: std_backtrace_frames(trace),
             theme: self.theme,
+            verbosity: self.verbosity,
+            source_snippets: self.source_snippets,
+            source_context_lines: self.source_context_lines,
+            reversed: self.reversed_stacktrace,
+            collapse_recursion: self.collapse_recursion,
+            max_frames: self.max_frames,
+            max_frame_name_length: self.max_frame_name_length,
+            hyperlinks: self.hyperlinks.as_deref(),
         }
     }
 }
@@ -51,129 +232,448 @@ impl eyre::EyreHandler for Handler {
             return core::fmt::Debug::fmt(error, f);
         }
 
+        #[cfg(feature = "opentelemetry")]
+        if self
+            .record_exception
+            .swap(false, std::sync::atomic::Ordering::Relaxed)
+        {
+            let report = crate::formatter::DisplayFn(|f: &mut core::fmt::Formatter<'_>| {
+                self.format_report(error, f)
+            })
+            .to_string();
+
+            crate::otel::record_exception("eyre::Report", error.to_string(), || report.clone());
+            return f.write_str(&report);
+        }
+
+        self.format_report(error, f)
+    }
+
+    #[cfg(feature = "track-caller")]
+    fn track_caller(&mut self, location: &'static std::panic::Location<'static>) {
+        self.location = Some(location);
+    }
+
+    #[cfg_attr(not(feature = "track-caller"), allow(unused_variables))]
+    fn on_wrap(
+        &mut self,
+        _msg: &dyn std::fmt::Display,
+        location: Option<&'static std::panic::Location<'static>>,
+    ) {
+        #[cfg(feature = "track-caller")]
+        self.wrap_locations.push(location);
+
+        if self.created_at.is_some() {
+            self.wrap_timestamps.push(std::time::Instant::now());
+        }
+    }
+
+    fn set_severity(&mut self, severity: eyre::Severity) {
+        self.severity = severity;
+    }
+
+    fn set_code(&mut self, code: &str) {
+        self.code = Some(code.into());
+    }
+
+    fn diagnostic(&mut self, diagnostic: &dyn eyre::Diagnostic) {
+        if let Some(help) = diagnostic.help() {
+            self.sections
+                .push(HelpInfo::Note(Box::new(help), self.theme));
+        }
+
+        if let Some(suggestion) = diagnostic.suggestion() {
+            self.sections
+                .push(HelpInfo::Suggestion(Box::new(suggestion), self.theme));
+        }
+    }
+
+    fn exit_report(
+        &self,
+        error: &(dyn std::error::Error + 'static),
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        let report =
+            crate::formatter::DisplayFn(|f: &mut core::fmt::Formatter<'_>| self.debug(error, f))
+                .to_string();
+
+        // Reports without a severity label or error code start with the
+        // error chain on a new line, and need a label of their own
+        if report.starts_with('\n') {
+            f.write_str("Error:")?;
+        }
+
+        f.write_str(&report)
+    }
+
+    fn field(&mut self, key: &'static str, value: &dyn std::fmt::Display) {
+        let value = value.to_string();
+        match self
+            .fields
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.fields.push((key, value)),
+        }
+    }
+
+    fn captured_backtrace(&self, error: &(dyn std::error::Error + 'static)) -> Option<String> {
+        let formatted = if let Some(backtrace) = get_deepest_backtrace(error) {
+            self.format_std_backtrace(backtrace).to_string()
+        } else {
+            self.format_backtrace(self.backtrace()?).to_string()
+        };
+
+        Some(crate::bug_report::strip_ansi(&formatted))
+    }
+
+    fn sections(&self) -> Vec<String> {
+        self.sections
+            .iter()
+            .map(|section| crate::bug_report::strip_ansi(&section.to_string()))
+            .collect()
+    }
+}
+
+impl Handler {
+    /// Render the report in the default format, see [`Pretty`](crate::formatter::Pretty)
+    pub(crate) fn format_pretty(
+        &self,
+        error: &(dyn std::error::Error + 'static),
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        // Errors are numbered after filtering, but keep their position in the
+        // chain to find the location they were created at
         #[cfg(feature = "capture-spantrace")]
         let errors = || {
             eyre::Chain::new(error)
-                .filter(|e| e.span_trace().is_none())
+                .enumerate()
+                .filter(|(_, e)| e.span_trace().is_none())
                 .enumerate()
         };
 
         #[cfg(not(feature = "capture-spantrace"))]
-        let errors = || eyre::Chain::new(error).enumerate();
+        let errors = || eyre::Chain::new(error).enumerate().enumerate();
 
-        for (n, error) in errors() {
-            writeln!(f)?;
-            write!(indented(f).ind(n), "{}", self.theme.error.style(error))?;
-        }
+        let prefix = self.continuation_prefix.as_deref().unwrap_or("");
 
-        let mut separated = f.header("\n\n");
+        // Errors are the default, so they don't need a label unless they
+        // carry an error code
+        let (label, style) = match self.severity {
+            eyre::Severity::Info => (Some("Info"), self.theme.help_info_note),
+            eyre::Severity::Warning => (Some("Warning"), self.theme.help_info_warning),
+            eyre::Severity::Fatal => (Some("Fatal"), self.theme.panic_header),
+            _ => (None, self.theme.error),
+        };
 
-        #[cfg(feature = "track-caller")]
-        if self.display_location_section {
+        match (label, &self.code) {
+            (label, Some(code)) => write!(
+                f,
+                "{}",
+                style.style(format_args!("{}[{}]:", label.unwrap_or("Error"), code))
+            )?,
+            (Some(label), None) => write!(f, "{}", style.style(format_args!("{}:", label)))?,
+            (None, None) => {}
+        }
+
+        #[allow(unused_variables)]
+        for (n, (position, error)) in errors() {
+            write!(f, "\n{: >4}: ", n)?;
             write!(
-                separated.ready(),
+                HangingIndent::new(&mut *f, 6, prefix),
                 "{}",
-                crate::SectionExt::header(
-                    crate::fmt::LocationSection(self.location, self.theme),
-                    "Location:"
-                )
+                style.style(error)
             )?;
-        }
 
-        for section in self
-            .sections
-            .iter()
-            .filter(|s| matches!(s, HelpInfo::Error(_, _)))
-        {
-            write!(separated.ready(), "{}", section)?;
-        }
+            if let Some(elapsed) = self.chain_elapsed(position) {
+                write!(
+                    f,
+                    " {}",
+                    self.theme
+                        .hidden_frames
+                        .style(format_args!("(+{})", crate::fmt::Elapsed(elapsed)))
+                )?;
+            }
 
-        for section in self
-            .sections
-            .iter()
-            .filter(|s| matches!(s, HelpInfo::Custom(_)))
-        {
-            write!(separated.ready(), "{}", section)?;
+            #[cfg(feature = "track-caller")]
+            if self.display_chain_locations {
+                if let Some(location) = self.chain_location(position) {
+                    write!(
+                        f,
+                        "\n      at {}",
+                        crate::fmt::LocationSection(Some(location), self.theme)
+                    )?;
+                }
+            }
         }
 
+        // User errors are not defects, so diagnostics beyond the error chain
+        // and any attached sections would only be noise.
+        let terse = self.has_tag(&ErrorTag::UserError);
+
+        let mut separated = f.header("\n\n");
+
         #[cfg(feature = "capture-spantrace")]
         let span_trace = self
             .span_trace
             .as_ref()
             .or_else(|| get_deepest_spantrace(error));
 
-        #[cfg(feature = "capture-spantrace")]
-        {
-            if let Some(span_trace) = span_trace {
-                write!(
-                    &mut separated.ready(),
-                    "{}",
-                    crate::writers::FormattedSpanTrace(span_trace)
-                )?;
-            }
-        }
+        // Prefer the deepest backtrace captured by an error in the chain over
+        // our own, which would otherwise print a second, nearly identical trace
+        let source_backtrace = get_deepest_backtrace(error);
 
-        if !self.suppress_backtrace {
-            if let Some(backtrace) = self.backtrace.as_ref() {
-                let fmted_bt = self.format_backtrace(backtrace);
+        for kind in self.section_order.iter() {
+            match kind {
+                SectionKind::Location =>
+                {
+                    #[cfg(feature = "track-caller")]
+                    if self.display_location_section && !terse {
+                        write!(
+                            separated.ready(),
+                            "{}",
+                            crate::SectionExt::header(
+                                crate::fmt::LocationSection(self.location, self.theme),
+                                "Location:"
+                            )
+                        )?;
+                    }
+                }
+                SectionKind::Errors => {
+                    let tree = crate::section::help::ErrorTree(&self.sections);
+                    if tree.applies() {
+                        write!(separated.ready(), "{}", tree)?;
+                    } else {
+                        for section in self
+                            .sections
+                            .iter()
+                            .filter(|s| matches!(s, HelpInfo::Error(..) | HelpInfo::Report(..)))
+                        {
+                            write!(separated.ready(), "{}", section)?;
+                        }
+                    }
+                }
+                SectionKind::Custom => {
+                    for section in self
+                        .sections
+                        .iter()
+                        .filter(|s| matches!(s, HelpInfo::Custom(_)))
+                    {
+                        write!(separated.ready(), "{}", section)?;
+                    }
+                }
+                SectionKind::Fields => {
+                    if !self.fields.is_empty() {
+                        write!(
+                            separated.ready(),
+                            "{}",
+                            crate::fmt::FieldsSection(&self.fields, self.theme)
+                        )?;
+                    }
+                }
+                SectionKind::SpanFields =>
+                {
+                    #[cfg(feature = "capture-spantrace")]
+                    if !self.span_fields.is_empty() && !terse {
+                        write!(
+                            separated.ready(),
+                            "{}",
+                            crate::fmt::SpanFieldsSection(&self.span_fields, self.theme)
+                        )?;
+                    }
+                }
+                SectionKind::SpanTrace => {
+                    #[cfg(feature = "capture-spantrace")]
+                    if let Some(span_trace) = span_trace.filter(|_| !terse) {
+                        write!(
+                            &mut separated.ready(),
+                            "{}",
+                            crate::writers::FormattedSpanTrace(
+                                span_trace,
+                                self.reversed_spantrace,
+                                &self.spantrace_filters,
+                                "SPANTRACE",
+                            )
+                        )?;
+                    }
 
-                write!(
-                    indented(&mut separated.ready())
-                        .with_format(Format::Uniform { indentation: "  " }),
-                    "{}",
-                    fmted_bt
-                )?;
+                    #[cfg(feature = "tokio")]
+                    for span_trace in self.spawned_from.iter().filter(|_| !terse) {
+                        write!(
+                            &mut separated.ready(),
+                            "{}",
+                            crate::writers::FormattedSpanTrace(
+                                span_trace,
+                                self.reversed_spantrace,
+                                &self.spantrace_filters,
+                                "SPAWNED FROM",
+                            )
+                        )?;
+                    }
+                }
+                SectionKind::Backtrace => {
+                    if self.suppress_backtrace || terse {
+                        continue;
+                    }
+
+                    let fmted_bt = if let Some(backtrace) = source_backtrace {
+                        Some(self.format_std_backtrace(backtrace))
+                    } else {
+                        self.backtrace()
+                            .map(|backtrace| self.format_backtrace(backtrace))
+                    };
+
+                    if let Some(fmted_bt) = fmted_bt {
+                        write!(
+                            indented(&mut separated.ready())
+                                .with_format(Format::Uniform { indentation: "  " }),
+                            "{}",
+                            fmted_bt
+                        )?;
+                    }
+                }
+                SectionKind::Notes => {
+                    let f = separated.ready();
+                    let mut h = f.header("\n");
+                    let mut f = h.in_progress();
+
+                    for section in self.sections.iter().filter(|s| {
+                        !matches!(
+                            s,
+                            HelpInfo::Custom(_) | HelpInfo::Error(..) | HelpInfo::Report(..)
+                        )
+                    }) {
+                        section.fmt_indented(&mut f, self.hanging_indent, prefix)?;
+                        f = h.ready();
+                    }
+                }
+                SectionKind::Env => {
+                    if self.display_env_section && !terse {
+                        let env_section = EnvSection {
+                            env_vars: &self.env_vars,
+                            theme: self.theme,
+                            verbosity: self.verbosity,
+                            source_snippets: self.source_snippets,
+                            bt_captured: &(self.backtrace.is_some() || source_backtrace.is_some()),
+                            #[cfg(feature = "capture-spantrace")]
+                            span_trace,
+                        };
+
+                        write!(&mut separated.ready(), "{}", env_section)?;
+                    }
+                }
+                SectionKind::IssueUrl => {
+                    #[cfg(feature = "issue-url")]
+                    if let Some(url) = self
+                        .issue_url
+                        .as_ref()
+                        .filter(|_| (*self.issue_filter)(crate::ErrorKind::Recoverable(error)))
+                        .filter(|_| {
+                            (*self.issue_report_filter)(&crate::IssueContext {
+                                kind: crate::ErrorKind::Recoverable(error),
+                                handler: Some(self),
+                            })
+                        })
+                    {
+                        let payload = issue_payload(error)?;
+                        let issue_section = self.issue_section(url, &payload, error);
+
+                        write!(&mut separated.ready(), "{}", issue_section)?;
+                    }
+                }
+                SectionKind::BuildInfo => {
+                    if !self.build_info.is_empty() && !terse {
+                        write!(
+                            separated.ready(),
+                            "{}",
+                            crate::fmt::BuildInfoSection(&self.build_info, self.theme)
+                        )?;
+                    }
+                }
             }
         }
 
-        let f = separated.ready();
-        let mut h = f.header("\n");
-        let mut f = h.in_progress();
+        Ok(())
+    }
+}
 
-        for section in self
-            .sections
-            .iter()
-            .filter(|s| !matches!(s, HelpInfo::Custom(_) | HelpInfo::Error(_, _)))
-        {
-            write!(&mut f, "{}", section)?;
-            f = h.ready();
-        }
+#[cfg(feature = "issue-url")]
+impl Handler {
+    /// Generate the issue URL for the given error, if an issue URL was configured.
+    ///
+    /// This ignores the configured issue filter.
+    pub(crate) fn issue_url(&self, error: &(dyn std::error::Error + 'static)) -> Option<String> {
+        let url = self.issue_url.as_ref()?;
+        let payload = issue_payload(error).ok()?;
+        self.issue_section(url, &payload, error).to_url().ok()
+    }
 
-        if self.display_env_section {
-            let env_section = EnvSection {
-                bt_captured: &self.backtrace.is_some(),
-                #[cfg(feature = "capture-spantrace")]
-                span_trace,
-            };
+    fn issue_section<'a>(
+        &'a self,
+        url: &'a str,
+        payload: &'a str,
+        error: &'a (dyn std::error::Error + 'static),
+    ) -> crate::section::github::IssueSection<'a> {
+        let issue_section =
+            crate::section::github::IssueSection::new(url, payload, &*self.issue_url_template)
+                .with_backtrace(self.backtrace())
+                .with_metadata(&self.issue_metadata)
+                .with_code(self.code.as_deref())
+                .with_hyperlink(self.hyperlinks.is_some());
 
-            write!(&mut separated.ready(), "{}", env_section)?;
-        }
+        #[cfg(feature = "capture-spantrace")]
+        let issue_section = issue_section.with_span_trace(
+            self.span_trace
+                .as_ref()
+                .or_else(|| get_deepest_spantrace(error)),
+        );
 
-        #[cfg(feature = "issue-url")]
-        if self.issue_url.is_some() && (*self.issue_filter)(crate::ErrorKind::Recoverable(error)) {
-            let url = self.issue_url.as_ref().unwrap();
-            let mut payload = String::from("Error: ");
-            for (n, error) in errors() {
-                writeln!(&mut payload)?;
-                write!(indented(&mut payload).ind(n), "{}", error)?;
-            }
+        #[cfg(not(feature = "capture-spantrace"))]
+        let _ = error;
 
-            let issue_section = crate::section::github::IssueSection::new(url, &payload)
-                .with_backtrace(self.backtrace.as_ref())
-                .with_metadata(&self.issue_metadata);
+        issue_section
+    }
+}
 
-            #[cfg(feature = "capture-spantrace")]
-            let issue_section = issue_section.with_span_trace(span_trace);
+#[cfg(feature = "issue-url")]
+fn issue_payload(error: &(dyn std::error::Error + 'static)) -> Result<String, std::fmt::Error> {
+    #[cfg(feature = "capture-spantrace")]
+    let errors = eyre::Chain::new(error)
+        .filter(|e| e.span_trace().is_none())
+        .enumerate();
 
-            write!(&mut separated.ready(), "{}", issue_section)?;
-        }
+    #[cfg(not(feature = "capture-spantrace"))]
+    let errors = eyre::Chain::new(error).enumerate();
 
-        Ok(())
+    let mut payload = String::from("Error: ");
+    for (n, error) in errors {
+        writeln!(&mut payload)?;
+        write!(indented(&mut payload).ind(n), "{}", error)?;
     }
 
-    #[cfg(feature = "track-caller")]
-    fn track_caller(&mut self, location: &'static std::panic::Location<'static>) {
-        self.location = Some(location);
+    Ok(payload)
+}
+
+/// Find the deepest `std::backtrace::Backtrace` captured by an error in the
+/// chain, if any of them provide one via generic member access.
+pub(crate) fn get_deepest_backtrace<'a>(
+    error: &'a (dyn std::error::Error + 'static),
+) -> Option<&'a std::backtrace::Backtrace> {
+    #[cfg(generic_member_access)]
+    {
+        use std::backtrace::BacktraceStatus;
+
+        eyre::Chain::new(error)
+            .rev()
+            .flat_map(std::error::request_ref::<std::backtrace::Backtrace>)
+            .find(|backtrace| backtrace.status() == BacktraceStatus::Captured)
+    }
+
+    #[cfg(not(generic_member_access))]
+    {
+        let _ = error;
+        None
     }
 }
 
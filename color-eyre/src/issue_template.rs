@@ -0,0 +1,77 @@
+//! Templates for turning an issue report into the "report this bug" URL of a
+//! specific issue tracker
+//!
+//! By default the URL configured with [`HookBuilder::issue_url`] is treated as
+//! the "new issue" page of a GitHub repository. Use
+//! [`HookBuilder::issue_url_template`] with one of the templates in this
+//! module, or a closure of your own, to generate links for other trackers.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use color_eyre::{config::HookBuilder, issue_template};
+//!
+//! HookBuilder::default()
+//!     .issue_url("https://gitlab.com/my-group/my-project/-/issues/new")
+//!     .issue_url_template(issue_template::gitlab)
+//!     .install()
+//!     .unwrap();
+//! ```
+//!
+//! [`HookBuilder::issue_url`]: crate::config::HookBuilder::issue_url
+//! [`HookBuilder::issue_url_template`]: crate::config::HookBuilder::issue_url_template
+use url::Url;
+
+/// The parts of an issue report an issue URL is generated from
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct IssueData<'a> {
+    /// The URL configured with [`HookBuilder::issue_url`](crate::config::HookBuilder::issue_url)
+    pub url: &'a str,
+    /// The title of the issue
+    pub title: &'a str,
+    /// The markdown body of the issue, containing the error message, the
+    /// metadata added with `add_issue_metadata`, and the captured backtrace
+    /// and span trace
+    pub body: &'a str,
+}
+
+impl IssueData<'_> {
+    /// Append the given query parameters to the configured URL, keeping any
+    /// query parameters it already has
+    ///
+    /// Falls back to the bare configured URL if it can't be parsed.
+    pub fn url_with_params(&self, params: &[(&str, &str)]) -> String {
+        Url::parse_with_params(self.url, params)
+            .map(String::from)
+            .unwrap_or_else(|_| self.url.to_string())
+    }
+}
+
+/// GitHub's new issue page, e.g. `https://github.com/owner/repo/issues/new`
+///
+/// This is the default template.
+pub fn github(data: &IssueData<'_>) -> String {
+    data.url_with_params(&[("title", data.title), ("body", data.body)])
+}
+
+/// GitLab's new issue page, e.g. `https://gitlab.com/group/project/-/issues/new`
+pub fn gitlab(data: &IssueData<'_>) -> String {
+    data.url_with_params(&[
+        ("issue[title]", data.title),
+        ("issue[description]", data.body),
+    ])
+}
+
+/// Gitea's and Forgejo's new issue page, e.g. `https://gitea.com/owner/repo/issues/new`
+pub fn gitea(data: &IssueData<'_>) -> String {
+    data.url_with_params(&[("title", data.title), ("body", data.body)])
+}
+
+/// Jira's create issue page, e.g.
+/// `https://jira.example.com/secure/CreateIssueDetails!init.jspa?pid=10000&issuetype=1`
+///
+/// The project and issue type have to be part of the configured URL.
+pub fn jira(data: &IssueData<'_>) -> String {
+    data.url_with_params(&[("summary", data.title), ("description", data.body)])
+}
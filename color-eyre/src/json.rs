@@ -0,0 +1,235 @@
+//! Line-oriented JSON output for error reports and panics, used by
+//! [`auto_install`](crate::auto_install) when `LOG_FORMAT=json` is set and by
+//! the [`Json`](crate::formatter::Json) formatter.
+use std::fmt::{self, Write};
+
+/// An `EyreHandler` which renders reports as a single JSON object
+pub(crate) struct JsonHandler {
+    #[cfg(feature = "track-caller")]
+    location: Option<&'static std::panic::Location<'static>>,
+}
+
+impl eyre::EyreHandler for JsonHandler {
+    fn debug(
+        &self,
+        error: &(dyn std::error::Error + 'static),
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        if f.alternate() {
+            return fmt::Debug::fmt(error, f);
+        }
+
+        #[cfg(feature = "track-caller")]
+        let location = self.location;
+        #[cfg(not(feature = "track-caller"))]
+        let location = None;
+
+        write_report(f, error, location, None)
+    }
+
+    #[cfg(feature = "track-caller")]
+    fn track_caller(&mut self, location: &'static std::panic::Location<'static>) {
+        self.location = Some(location);
+    }
+}
+
+/// Write `error` as a single JSON object, including the help sections of
+/// `handler` if given
+pub(crate) fn write_report(
+    f: &mut dyn Write,
+    error: &(dyn std::error::Error + 'static),
+    location: Option<&std::panic::Location<'_>>,
+    handler: Option<&crate::Handler>,
+) -> fmt::Result {
+    let mut chain = eyre::Chain::new(error);
+
+    write!(f, "{{\"error\":")?;
+    write_string(
+        f,
+        chain.next().expect("chain always yields the error itself"),
+    )?;
+
+    write_array(f, "causes", chain)?;
+
+    if let Some(location) = location {
+        write!(f, ",\"location\":")?;
+        write_string(f, location)?;
+    }
+
+    if let Some(handler) = handler {
+        if handler.severity() != eyre::Severity::Error {
+            write!(f, ",\"severity\":")?;
+            write_string(f, handler.severity())?;
+        }
+        if handler.notes().next().is_some() {
+            write_array(f, "notes", handler.notes())?;
+        }
+        if handler.warnings().next().is_some() {
+            write_array(f, "warnings", handler.warnings())?;
+        }
+        if handler.suggestions().next().is_some() {
+            write_array(f, "suggestions", handler.suggestions())?;
+        }
+        if handler.fields().next().is_some() {
+            write!(f, ",\"fields\":{{")?;
+            for (n, (key, value)) in handler.fields().enumerate() {
+                if n != 0 {
+                    f.write_char(',')?;
+                }
+                write_string(f, key)?;
+                f.write_char(':')?;
+                write_string(f, value)?;
+            }
+            f.write_char('}')?;
+        }
+    }
+
+    f.write_char('}')
+}
+
+/// Write `,"key":[...]` with the `Display` output of each item as a string
+fn write_array<T: fmt::Display>(
+    f: &mut dyn Write,
+    key: &str,
+    items: impl Iterator<Item = T>,
+) -> fmt::Result {
+    write!(f, ",\"{}\":[", key)?;
+    for (n, item) in items.enumerate() {
+        if n != 0 {
+            f.write_char(',')?;
+        }
+        write_string(f, item)?;
+    }
+    f.write_char(']')
+}
+
+/// Write `,"key":{...}` with each entry's value as a string
+fn write_object<'a>(
+    f: &mut dyn Write,
+    key: &str,
+    entries: impl Iterator<Item = (&'a str, String)>,
+) -> fmt::Result {
+    write!(f, ",\"{}\":{{", key)?;
+    for (n, (key, value)) in entries.enumerate() {
+        if n != 0 {
+            f.write_char(',')?;
+        }
+        write_string(f, key)?;
+        f.write_char(':')?;
+        write_string(f, value)?;
+    }
+    f.write_char('}')
+}
+
+/// Install the JSON report and panic hooks
+pub(crate) fn install() -> Result<(), crate::eyre::Report> {
+    crate::eyre::set_hook(Box::new(|_| {
+        Box::new(JsonHandler {
+            #[cfg(feature = "track-caller")]
+            location: None,
+        })
+    }))?;
+
+    std::panic::set_hook(Box::new(|panic_info| {
+        let payload = panic_info.payload();
+        let message = payload
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| payload.downcast_ref::<&str>().cloned())
+            .unwrap_or("<non string panic payload>");
+
+        let mut out = String::from("{\"panic\":");
+        write_string(&mut out, message).expect("writing to a string doesn't panic");
+        if let Some(location) = panic_info.location() {
+            out.push_str(",\"location\":");
+            write_string(&mut out, location).expect("writing to a string doesn't panic");
+        }
+        out.push('}');
+
+        eprintln!("{}", out);
+    }));
+
+    Ok(())
+}
+
+/// The contents of a crash report written for a panic, see
+/// [`HookBuilder::crash_report_dir`](crate::config::HookBuilder::crash_report_dir)
+pub(crate) struct CrashReport<'a> {
+    pub(crate) message: &'a str,
+    pub(crate) location: Option<&'a std::panic::Location<'a>>,
+    pub(crate) thread: Option<&'a str>,
+    pub(crate) timestamp: u128,
+    pub(crate) backtrace: String,
+    pub(crate) span_trace: Option<String>,
+    pub(crate) metadata: Vec<(&'a str, String)>,
+}
+
+impl fmt::Display for CrashReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{\"panic\":")?;
+        write_string(f, self.message)?;
+        if let Some(location) = self.location {
+            write!(f, ",\"location\":")?;
+            write_string(f, location)?;
+        }
+        if let Some(thread) = self.thread {
+            write!(f, ",\"thread\":")?;
+            write_string(f, thread)?;
+        }
+        write!(f, ",\"timestamp\":{}", self.timestamp)?;
+        write!(f, ",\"os\":")?;
+        write_string(f, std::env::consts::OS)?;
+        write!(f, ",\"arch\":")?;
+        write_string(f, std::env::consts::ARCH)?;
+        write!(f, ",\"backtrace\":")?;
+        write_string(f, &self.backtrace)?;
+        if let Some(span_trace) = &self.span_trace {
+            write!(f, ",\"spantrace\":")?;
+            write_string(f, span_trace)?;
+        }
+
+        write_object(
+            f,
+            "metadata",
+            self.metadata
+                .iter()
+                .map(|(key, value)| (*key, value.clone())),
+        )?;
+        write_object(
+            f,
+            "env",
+            crate::bug_report::ENV_VARS
+                .iter()
+                .filter_map(|var| Some((*var, std::env::var(var).ok()?))),
+        )?;
+
+        f.write_char('}')
+    }
+}
+
+/// Write the `Display` output of `value` as a JSON string literal
+fn write_string(f: &mut dyn Write, value: impl fmt::Display) -> fmt::Result {
+    f.write_char('"')?;
+    write!(JsonEscaper(f), "{}", value)?;
+    f.write_char('"')
+}
+
+struct JsonEscaper<'a>(&'a mut dyn Write);
+
+impl Write for JsonEscaper<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                c if c.is_control() => write!(self.0, "\\u{:04x}", c as u32)?,
+                c => self.0.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -221,6 +221,15 @@
 //!
 //! <span style="color: #34E2E2">Suggestion</span>: try using a file that exists next time</pre>
 //!
+//! The verbosity of panic and error reports can also be configured
+//! independently of each other with [`HookBuilder::panic_verbosity`] and
+//! [`HookBuilder::error_verbosity`], or at runtime with the
+//! `COLOR_EYRE_PANIC_VERBOSITY` and `COLOR_EYRE_ERROR_VERBOSITY` environment
+//! variables, which take precedence over both the builder and `RUST_BACKTRACE`.
+//!
+//! [`HookBuilder::panic_verbosity`]: config::HookBuilder::panic_verbosity
+//! [`HookBuilder::error_verbosity`]: config::HookBuilder::error_verbosity
+//!
 //! ### Custom `Section`s for error reports via [`Section`] trait
 //!
 //! The `section` module provides helpers for adding extra sections to error
@@ -336,6 +345,7 @@
 //! [`examples/multiple_errors.rs`]: https://github.com/yaahc/color-eyre/blob/master/examples/multiple_errors.rs
 #![doc(html_root_url = "https://docs.rs/color-eyre/0.6.2")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(generic_member_access, feature(error_generic_member_access))]
 #![warn(
     missing_docs,
     rustdoc::missing_doc_code_examples,
@@ -359,7 +369,7 @@
 
 use std::sync::Arc;
 
-use backtrace::Backtrace;
+pub use bug_report::bug_report;
 pub use eyre;
 #[doc(hidden)]
 pub use eyre::Report;
@@ -370,16 +380,35 @@ use section::help::HelpInfo;
 #[doc(hidden)]
 pub use section::Section as Help;
 pub use section::{IndentedSection, Section, SectionExt};
+pub use tag::ErrorTag;
 #[cfg(feature = "capture-spantrace")]
 use tracing_error::SpanTrace;
 #[doc(hidden)]
 pub use Handler as Context;
 
+pub mod bug_report;
 pub mod config;
 mod fmt;
+pub mod formatter;
 mod handler;
+#[cfg(feature = "issue-url")]
+#[cfg_attr(docsrs, doc(cfg(feature = "issue-url")))]
+pub mod issue_template;
+mod json;
+mod normalize;
+#[cfg(feature = "opentelemetry")]
+mod otel;
 pub(crate) mod private;
+#[cfg(feature = "indicatif")]
+#[cfg_attr(docsrs, doc(cfg(feature = "indicatif")))]
+pub mod progress;
 pub mod section;
+pub mod sink;
+mod tag;
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub mod task;
+pub mod throttle;
 mod writers;
 
 /// A custom handler type for [`eyre::Report`] which provides colorful error
@@ -396,14 +425,48 @@ mod writers;
 /// [`color_eyre::Result`]: type.Result.html
 pub struct Handler {
     filters: Arc<[Box<config::FilterCallback>]>,
-    backtrace: Option<Backtrace>,
+    backtrace: Option<config::DeferredBacktrace>,
     suppress_backtrace: bool,
+    verbosity: config::Verbosity,
+    source_snippets: Option<bool>,
+    source_context_lines: usize,
+    reversed_stacktrace: bool,
+    collapse_recursion: bool,
+    max_frames: usize,
+    max_frame_name_length: usize,
+    hyperlinks: Option<Arc<str>>,
+    normalized_output: bool,
+    #[cfg(feature = "capture-spantrace")]
+    reversed_spantrace: bool,
+    #[cfg(feature = "capture-spantrace")]
+    spantrace_filters: Arc<config::SpanTraceFilters>,
+    section_order: Arc<[config::SectionKind]>,
+    throttle: Option<Arc<throttle::Throttle>>,
+    tags: Vec<ErrorTag>,
+    fields: Vec<(&'static str, String)>,
+    severity: eyre::Severity,
+    code: Option<String>,
+    formatter: Arc<dyn formatter::ReportFormatter>,
+    /// Set while the report still has to be recorded as an OpenTelemetry
+    /// exception, which happens the first time it is formatted.
+    #[cfg(feature = "opentelemetry")]
+    record_exception: std::sync::atomic::AtomicBool,
     #[cfg(feature = "capture-spantrace")]
     span_trace: Option<SpanTrace>,
+    #[cfg(feature = "capture-spantrace")]
+    span_fields: Vec<(&'static str, String)>,
+    #[cfg(feature = "tokio")]
+    spawned_from: Arc<[SpanTrace]>,
     sections: Vec<HelpInfo>,
     display_env_section: bool,
+    env_vars: Arc<config::EnvVars>,
+    hanging_indent: bool,
+    continuation_prefix: Option<Arc<str>>,
     #[cfg(feature = "track-caller")]
     display_location_section: bool,
+    #[cfg(feature = "track-caller")]
+    display_chain_locations: bool,
+    build_info: Arc<Vec<(String, Box<dyn std::fmt::Display + Send + Sync + 'static>)>>,
     #[cfg(feature = "issue-url")]
     issue_url: Option<String>,
     #[cfg(feature = "issue-url")]
@@ -411,9 +474,19 @@ pub struct Handler {
         std::sync::Arc<Vec<(String, Box<dyn std::fmt::Display + Send + Sync + 'static>)>>,
     #[cfg(feature = "issue-url")]
     issue_filter: std::sync::Arc<config::IssueFilterCallback>,
+    #[cfg(feature = "issue-url")]
+    issue_report_filter: std::sync::Arc<config::IssueReportFilterCallback>,
+    #[cfg(feature = "issue-url")]
+    issue_url_template: std::sync::Arc<config::IssueUrlTemplate>,
     theme: crate::config::Theme,
     #[cfg(feature = "track-caller")]
     location: Option<&'static std::panic::Location<'static>>,
+    #[cfg(feature = "track-caller")]
+    wrap_locations: Vec<Option<&'static std::panic::Location<'static>>>,
+    created_at: Option<std::time::Instant>,
+    wrap_timestamps: Vec<std::time::Instant>,
+    #[cfg(feature = "indicatif")]
+    progress: Option<indicatif::MultiProgress>,
 }
 
 /// The kind of type erased error being reported
@@ -426,6 +499,42 @@ pub enum ErrorKind<'a> {
     Recoverable(&'a (dyn std::error::Error + 'static)),
 }
 
+/// A view of the error report or panic an issue url is about to be generated for
+///
+/// Passed to the callback registered with [`HookBuilder::issue_report_filter`].
+///
+/// [`HookBuilder::issue_report_filter`]: config::HookBuilder::issue_report_filter
+#[cfg(feature = "issue-url")]
+#[cfg_attr(docsrs, doc(cfg(feature = "issue-url")))]
+pub struct IssueContext<'a> {
+    kind: ErrorKind<'a>,
+    handler: Option<&'a Handler>,
+}
+
+#[cfg(feature = "issue-url")]
+impl<'a> IssueContext<'a> {
+    /// The error or panic payload being reported
+    pub fn kind(&self) -> &ErrorKind<'a> {
+        &self.kind
+    }
+
+    /// The `Handler` of the error report, or `None` for panics
+    pub fn handler(&self) -> Option<&'a Handler> {
+        self.handler
+    }
+
+    /// The [`ErrorTag`]s attached to the error report
+    pub fn tags(&self) -> &'a [ErrorTag] {
+        self.handler.map(Handler::tags).unwrap_or_default()
+    }
+
+    /// Returns `true` if a suggestion was attached to the error report
+    pub fn has_suggestion(&self) -> bool {
+        self.handler
+            .map_or(false, |handler| handler.suggestions().next().is_some())
+    }
+}
+
 /// Install the default panic and error report hooks
 ///
 /// # Details
@@ -439,8 +548,8 @@ pub enum ErrorKind<'a> {
 /// function _must_ be called before any `eyre::Report`s are constructed to
 /// prevent the default handler from being installed.
 ///
-/// Installing a global theme in `color_spantrace` manually (by calling
-/// `color_spantrace::set_theme` or `color_spantrace::colorize` before
+/// Installing a different global theme in `color_spantrace` manually (by
+/// calling `color_spantrace::set_theme` or `color_spantrace::colorize` before
 /// `install` is called) will result in an error if this function is called.
 ///
 /// # Examples
@@ -458,3 +567,142 @@ pub enum ErrorKind<'a> {
 pub fn install() -> Result<(), crate::eyre::Report> {
     config::HookBuilder::default().install()
 }
+
+/// Install only the default panic hook
+///
+/// # Details
+///
+/// This is the panic half of [`install`], for applications that want
+/// color-eyre's panic reports but install their own `eyre` handler. The
+/// global `eyre` hook is left untouched.
+///
+/// Calling both [`install_panic_hook`] and [`install_eyre_hook`] is
+/// equivalent to calling [`install`].
+///
+/// # Examples
+///
+/// ```rust
+/// use color_eyre::eyre::Result;
+///
+/// fn main() -> Result<()> {
+///     color_eyre::install_panic_hook()?;
+///
+///     // ...
+///     # Ok(())
+/// }
+/// ```
+pub fn install_panic_hook() -> Result<(), crate::eyre::Report> {
+    config::HookBuilder::default().install_panic_hook()
+}
+
+/// Install only the default error report hook
+///
+/// # Details
+///
+/// This is the error half of [`install`], for applications that manage their
+/// own panic hook, such as TUIs that need to restore the terminal before
+/// printing or programs with a custom crash pipeline. The current panic hook
+/// is left untouched.
+///
+/// The same restrictions as for [`install`] apply.
+///
+/// # Examples
+///
+/// ```rust
+/// use color_eyre::eyre::Result;
+///
+/// fn main() -> Result<()> {
+///     color_eyre::install_eyre_hook()?;
+///
+///     // ...
+///     # Ok(())
+/// }
+/// ```
+pub fn install_eyre_hook() -> Result<(), crate::eyre::Report> {
+    config::HookBuilder::default().install_eyre_hook()
+}
+
+/// Install panic and error report hooks suited to where the output is going
+///
+/// # Details
+///
+/// This picks an output format based on the environment the program runs in:
+///
+/// - When the `LOG_FORMAT` environment variable is set to `json`, reports and
+///   panics are printed as single-line JSON objects, for consumption by log
+///   aggregators.
+/// - Otherwise, when stderr is a terminal, the default colorful hooks are
+///   installed, exactly as with [`install`].
+/// - Otherwise, when stderr is piped or redirected, the default hooks are
+///   installed with a blank [`Theme`](config::Theme) so that no ANSI escape
+///   codes end up in log files.
+///
+/// The same restrictions as for [`install`] apply.
+///
+/// # Examples
+///
+/// ```rust
+/// use color_eyre::eyre::Result;
+///
+/// fn main() -> Result<()> {
+///     color_eyre::auto_install()?;
+///
+///     // ...
+///     # Ok(())
+/// }
+/// ```
+pub fn auto_install() -> Result<(), crate::eyre::Report> {
+    use is_terminal::IsTerminal;
+
+    let json =
+        std::env::var("LOG_FORMAT").map_or(false, |format| format.eq_ignore_ascii_case("json"));
+
+    if json {
+        json::install()
+    } else if std::io::stderr().is_terminal() {
+        install()
+    } else {
+        config::HookBuilder::default()
+            .theme(config::Theme::new())
+            .install()
+    }
+}
+
+/// Generate the issue URL for a report, as it would appear in the report's
+/// `Debug` output
+///
+/// # Details
+///
+/// This is useful for surfacing the issue URL outside of the printed report,
+/// for example behind a "Report this bug" button in a TUI or GUI. The URL
+/// contains the same error chain, metadata and traces as the URL printed by
+/// the report itself.
+///
+/// Returns `None` if the report was not created by `color-eyre`'s handler or
+/// if no issue URL was configured via [`HookBuilder::issue_url`]. The
+/// configured [`HookBuilder::issue_filter`] is not consulted, since the caller
+/// is explicitly asking for a URL.
+///
+/// [`HookBuilder::issue_url`]: config::HookBuilder::issue_url
+/// [`HookBuilder::issue_filter`]: config::HookBuilder::issue_filter
+///
+/// # Examples
+///
+/// ```rust
+/// use color_eyre::eyre::eyre;
+///
+/// color_eyre::config::HookBuilder::default()
+///     .issue_url("https://github.com/eyre-rs/eyre/issues/new")
+///     .install()
+///     .unwrap();
+///
+/// let report = eyre!("something went wrong");
+/// let url = color_eyre::issue_url(&report).unwrap();
+/// assert!(url.starts_with("https://github.com/eyre-rs/eyre/issues/new?"));
+/// ```
+#[cfg(feature = "issue-url")]
+#[cfg_attr(docsrs, doc(cfg(feature = "issue-url")))]
+pub fn issue_url(report: &Report) -> Option<String> {
+    let handler = report.handler().downcast_ref::<Handler>()?;
+    handler.issue_url(report.as_ref())
+}
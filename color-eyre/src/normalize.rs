@@ -0,0 +1,80 @@
+//! Deterministic output for snapshot tests, see
+//! [`HookBuilder::normalized_output`](crate::config::HookBuilder::normalized_output)
+
+/// Remove everything from a rendered report that differs between machines,
+/// builds or edits of unrelated code.
+pub(crate) fn normalize(report: &str) -> String {
+    let mut report = crate::bug_report::strip_ansi(report);
+
+    if let Ok(cwd) = std::env::current_dir() {
+        let prefix = format!("{}{}", cwd.display(), std::path::MAIN_SEPARATOR);
+        report = report.replace(&prefix, "");
+    }
+
+    elide_hash_suffixes(&replace_line_numbers(&report))
+}
+
+/// Replace the line and column numbers following `.rs:` with `LL` and `CC`
+fn replace_line_numbers(report: &str) -> String {
+    let mut out = String::with_capacity(report.len());
+    let mut rest = report;
+
+    while let Some(index) = rest.find(".rs:") {
+        let (head, tail) = rest.split_at(index + ".rs:".len());
+        out.push_str(head);
+        rest = tail;
+
+        let line = leading_digits(rest);
+        if line == 0 {
+            continue;
+        }
+        out.push_str("LL");
+        rest = &rest[line..];
+
+        if let Some(tail) = rest.strip_prefix(':') {
+            let column = leading_digits(tail);
+            if column > 0 {
+                out.push_str(":CC");
+                rest = &tail[column..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Remove the `::h0123456789abcdef` suffix of symbol names
+fn elide_hash_suffixes(report: &str) -> String {
+    const HASH_LEN: usize = "::h".len() + 16;
+
+    let mut out = String::with_capacity(report.len());
+    let mut rest = report;
+
+    while let Some(index) = rest.find("::h") {
+        out.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        let is_hash = rest.len() >= HASH_LEN
+            && rest.as_bytes()[3..HASH_LEN]
+                .iter()
+                .all(u8::is_ascii_hexdigit)
+            && !rest.as_bytes()[HASH_LEN..]
+                .first()
+                .map_or(false, u8::is_ascii_alphanumeric);
+
+        if is_hash {
+            rest = &rest[HASH_LEN..];
+        } else {
+            out.push_str("::h");
+            rest = &rest[3..];
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn leading_digits(s: &str) -> usize {
+    s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len()
+}
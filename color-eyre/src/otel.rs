@@ -0,0 +1,28 @@
+//! Recording of error reports and panics as OpenTelemetry exception events
+use opentelemetry::{trace::get_active_span, KeyValue};
+
+/// Add an `exception` event following the OpenTelemetry semantic conventions
+/// to the active span, if it is being recorded
+///
+/// The stacktrace is only rendered if the event is actually recorded.
+pub(crate) fn record_exception(
+    ty: &'static str,
+    message: String,
+    stacktrace: impl FnOnce() -> String,
+) {
+    get_active_span(|span| {
+        if span.is_recording() {
+            span.add_event(
+                "exception",
+                vec![
+                    KeyValue::new("exception.type", ty),
+                    KeyValue::new("exception.message", message),
+                    KeyValue::new(
+                        "exception.stacktrace",
+                        crate::bug_report::strip_ansi(&stacktrace()),
+                    ),
+                ],
+            );
+        }
+    });
+}
@@ -0,0 +1,74 @@
+//! Printing reports while indicatif progress bars are drawn
+//!
+//! Reports printed to the terminal while progress bars are active get
+//! interleaved with the bars and partially overwritten by the next redraw.
+//! After registering the `MultiProgress` of the application with
+//! [`HookBuilder::with_progress_suspender`], panic reports and reports printed
+//! via [`ReportExt::eprint`] hide the bars while they are written.
+//!
+//! indicatif doesn't draw anything on a thread that is panicking, so the bars
+//! can't be cleared for panic reports. Bars updated by other threads are
+//! still held back until the panic report is written.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use color_eyre::{eyre::eyre, progress::ReportExt};
+//! use indicatif::{MultiProgress, ProgressBar};
+//!
+//! let progress = MultiProgress::new();
+//! let bar = progress.add(ProgressBar::new(3));
+//!
+//! color_eyre::config::HookBuilder::default()
+//!     .with_progress_suspender(progress)
+//!     .install()
+//!     .unwrap();
+//!
+//! for file in ["a.txt", "b.txt", "c.txt"] {
+//!     eyre!("failed to process {}", file).eprint();
+//!     bar.inc(1);
+//! }
+//! ```
+//!
+//! [`HookBuilder::with_progress_suspender`]: crate::config::HookBuilder::with_progress_suspender
+use crate::eyre::Report;
+use crate::throttle::ThrottleExt;
+
+/// Extension trait for printing reports without garbling progress bars, see
+/// the [module documentation](self).
+pub trait ReportExt: crate::private::Sealed {
+    /// Print the report to stderr like an error returned from `main`,
+    /// suspending the progress bars registered via
+    /// [`HookBuilder::with_progress_suspender`] while it is written.
+    ///
+    /// Reports whose handler isn't a color-eyre [`Handler`](crate::Handler),
+    /// or which were created without a registered `MultiProgress`, are
+    /// printed directly. Repeated reports are collapsed as with
+    /// [`ThrottleExt::throttled`] if throttling is enabled.
+    ///
+    /// [`HookBuilder::with_progress_suspender`]: crate::config::HookBuilder::with_progress_suspender
+    fn eprint(&self);
+}
+
+impl ReportExt for Report {
+    fn eprint(&self) {
+        let handler = self.handler();
+        let throttled = self.throttled();
+        let report = crate::formatter::DisplayFn(|f: &mut std::fmt::Formatter<'_>| {
+            match throttled.repeats() {
+                Some(_) => std::fmt::Display::fmt(&throttled, f),
+                // Labelled like an error returned from `main`
+                None => handler.exit_report(&**self, f),
+            }
+        });
+
+        let progress = handler
+            .downcast_ref::<crate::Handler>()
+            .and_then(|handler| handler.progress.as_ref());
+
+        match progress {
+            Some(progress) => progress.suspend(|| eprintln!("{}", report)),
+            None => eprintln!("{}", report),
+        }
+    }
+}
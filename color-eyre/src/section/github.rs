@@ -3,7 +3,6 @@ use backtrace::Backtrace;
 use std::{fmt, panic::Location};
 #[cfg(feature = "capture-spantrace")]
 use tracing_error::SpanTrace;
-use url::Url;
 
 type Display<'a> = Box<dyn std::fmt::Display + Send + Sync + 'a>;
 
@@ -15,10 +14,17 @@ pub(crate) struct IssueSection<'a> {
     #[cfg(feature = "capture-spantrace")]
     span_trace: Option<&'a SpanTrace>,
     metadata: &'a [(String, Display<'a>)],
+    template: &'a crate::config::IssueUrlTemplate,
+    hyperlink: bool,
+    code: Option<&'a str>,
 }
 
 impl<'a> IssueSection<'a> {
-    pub(crate) fn new(url: &'a str, msg: &'a str) -> Self {
+    pub(crate) fn new(
+        url: &'a str,
+        msg: &'a str,
+        template: &'a crate::config::IssueUrlTemplate,
+    ) -> Self {
         IssueSection {
             url,
             msg,
@@ -27,6 +33,9 @@ impl<'a> IssueSection<'a> {
             #[cfg(feature = "capture-spantrace")]
             span_trace: None,
             metadata: &[],
+            template,
+            hyperlink: false,
+            code: None,
         }
     }
 
@@ -46,14 +55,26 @@ impl<'a> IssueSection<'a> {
         self
     }
 
+    pub(crate) fn with_hyperlink(mut self, hyperlink: bool) -> Self {
+        self.hyperlink = hyperlink;
+        self
+    }
+
+    pub(crate) fn with_code(mut self, code: Option<&'a str>) -> Self {
+        self.code = code;
+        self
+    }
+
     pub(crate) fn with_metadata(mut self, metadata: &'a [(String, Display<'a>)]) -> Self {
         self.metadata = metadata;
         self
     }
 }
 
-impl fmt::Display for IssueSection<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl IssueSection<'_> {
+    /// Build the issue URL, with the report details encoded by the configured
+    /// issue URL template.
+    pub(crate) fn to_url(&self) -> Result<String, fmt::Error> {
         let location = self
             .location
             .map(|loc| ("location".to_string(), Box::new(loc) as _));
@@ -83,14 +104,25 @@ impl fmt::Display for IssueSection<'_> {
             )?;
         }
 
-        let url_result = Url::parse_with_params(
-            self.url,
-            &[("title", "<autogenerated-issue>"), ("body", &body.body)],
-        );
+        let title = match self.code {
+            Some(code) => format!("[{}] <autogenerated-issue>", code),
+            None => "<autogenerated-issue>".to_string(),
+        };
 
-        let url: &dyn fmt::Display = match &url_result {
-            Ok(url_struct) => url_struct,
-            Err(_) => &self.url,
+        Ok((self.template)(&crate::issue_template::IssueData {
+            url: self.url,
+            title: &title,
+            body: &body.body,
+        }))
+    }
+}
+
+impl fmt::Display for IssueSection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let url = self.to_url()?;
+        let url = crate::writers::Hyperlink {
+            url: if self.hyperlink { Some(&url) } else { None },
+            text: &url,
         };
 
         url.with_header("Consider reporting this error using this URL: ")
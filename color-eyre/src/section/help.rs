@@ -2,6 +2,7 @@
 use crate::{
     config::Theme,
     eyre::{Report, Result},
+    writers::HangingIndent,
     Section,
 };
 use indenter::indented;
@@ -143,6 +144,23 @@ impl Section for Report {
         self
     }
 
+    fn report(mut self, report: Report) -> Self::Return {
+        if let Some(handler) = self.handler_mut().downcast_mut::<crate::Handler>() {
+            handler
+                .sections
+                .push(HelpInfo::Report(report, handler.theme));
+        }
+
+        self
+    }
+
+    fn with_report<F>(self, report: F) -> Self::Return
+    where
+        F: FnOnce() -> Report,
+    {
+        self.report(report())
+    }
+
     fn suppress_backtrace(mut self, suppress: bool) -> Self::Return {
         if let Some(handler) = self.handler_mut().downcast_mut::<crate::Handler>() {
             handler.suppress_backtrace = suppress;
@@ -150,6 +168,16 @@ impl Section for Report {
 
         self
     }
+
+    fn tag(mut self, tag: crate::ErrorTag) -> Self::Return {
+        if let Some(handler) = self.handler_mut().downcast_mut::<crate::Handler>() {
+            if !handler.tags.contains(&tag) {
+                handler.tags.push(tag);
+            }
+        }
+
+        self
+    }
 }
 
 impl<T, E> Section for Result<T, E>
@@ -243,14 +271,33 @@ where
             .map_err(|report| report.error(error()))
     }
 
+    fn report(self, report: Report) -> Self::Return {
+        self.map_err(|error| error.into())
+            .map_err(|error| error.report(report))
+    }
+
+    fn with_report<F>(self, report: F) -> Self::Return
+    where
+        F: FnOnce() -> Report,
+    {
+        self.map_err(|error| error.into())
+            .map_err(|error| error.report(report()))
+    }
+
     fn suppress_backtrace(self, suppress: bool) -> Self::Return {
         self.map_err(|error| error.into())
             .map_err(|report| report.suppress_backtrace(suppress))
     }
+
+    fn tag(self, tag: crate::ErrorTag) -> Self::Return {
+        self.map_err(|error| error.into())
+            .map_err(|report| report.tag(tag))
+    }
 }
 
 pub(crate) enum HelpInfo {
     Error(Box<dyn std::error::Error + Send + Sync + 'static>, Theme),
+    Report(Report, Theme),
     Custom(Box<dyn Display + Send + Sync + 'static>),
     Note(Box<dyn Display + Send + Sync + 'static>, Theme),
     Warning(Box<dyn Display + Send + Sync + 'static>, Theme),
@@ -276,13 +323,8 @@ impl Display for HelpInfo {
                 suggestion
             ),
             HelpInfo::Custom(section) => write!(f, "{}", section),
-            HelpInfo::Error(error, theme) => {
-                // a lot here
-                let errors = std::iter::successors(
-                    Some(error.as_ref() as &(dyn std::error::Error + 'static)),
-                    |e| e.source(),
-                );
-
+            HelpInfo::Error(..) | HelpInfo::Report(..) => {
+                let (errors, theme) = self.errors().expect("error or report section");
                 write!(f, "Error:")?;
                 for (n, error) in errors.enumerate() {
                     writeln!(f)?;
@@ -295,6 +337,62 @@ impl Display for HelpInfo {
     }
 }
 
+impl HelpInfo {
+    /// The chain of errors of an error or report section and its theme
+    fn errors(
+        &self,
+    ) -> Option<(
+        impl Iterator<Item = &(dyn std::error::Error + 'static)>,
+        Theme,
+    )> {
+        let (error, theme): (&(dyn std::error::Error + 'static), _) = match self {
+            HelpInfo::Error(error, theme) => (error.as_ref(), *theme),
+            HelpInfo::Report(report, theme) => (&**report, *theme),
+            _ => return None,
+        };
+
+        Some((
+            std::iter::successors(Some(error), |error| error.source()),
+            theme,
+        ))
+    }
+
+    /// The error and report sections attached to the report of a report
+    /// section, displayed as its children in an [`ErrorTree`]
+    fn children(&self) -> &[HelpInfo] {
+        match self {
+            HelpInfo::Report(report, _) => report
+                .handler()
+                .downcast_ref::<crate::Handler>()
+                .map_or(&[], |handler| &handler.sections),
+            _ => &[],
+        }
+    }
+
+    /// Like the `Display` impl, but aligns continuation lines of notes,
+    /// warnings and suggestions with the text following their label when
+    /// `hanging` is set, and starts them with `prefix`
+    pub(crate) fn fmt_indented(
+        &self,
+        f: &mut dyn fmt::Write,
+        hanging: bool,
+        prefix: &str,
+    ) -> fmt::Result {
+        let (label, body, style) = match self {
+            HelpInfo::Note(note, theme) => ("Note", note, theme.help_info_note),
+            HelpInfo::Warning(warning, theme) => ("Warning", warning, theme.help_info_warning),
+            HelpInfo::Suggestion(suggestion, theme) => {
+                ("Suggestion", suggestion, theme.help_info_suggestion)
+            }
+            _ => return write!(f, "{}", self),
+        };
+
+        let indent = if hanging { label.len() + 2 } else { 0 };
+        write!(f, "{}: ", label.style(style))?;
+        write!(HangingIndent::new(f, indent, prefix), "{}", body)
+    }
+}
+
 impl fmt::Debug for HelpInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -315,6 +413,72 @@ impl fmt::Debug for HelpInfo {
                 .field(&format_args!("{}", custom))
                 .finish(),
             HelpInfo::Error(error, ..) => f.debug_tuple("Error").field(error).finish(),
+            HelpInfo::Report(report, ..) => f.debug_tuple("Report").field(report).finish(),
+        }
+    }
+}
+
+/// The error and report sections of a report, displayed as a tree when
+/// multiple errors were aggregated into it or reports with attached errors of
+/// their own were attached
+pub(crate) struct ErrorTree<'a>(pub(crate) &'a [HelpInfo]);
+
+impl ErrorTree<'_> {
+    /// Whether the sections should be displayed as a tree rather than as
+    /// separate `Error:` sections
+    pub(crate) fn applies(&self) -> bool {
+        let mut errors = self.errors().peekable();
+        let first = errors.next();
+        errors.peek().is_some() || first.map_or(false, |first| !first.children().is_empty())
+    }
+
+    fn errors(&self) -> impl Iterator<Item = &HelpInfo> {
+        self.0
+            .iter()
+            .filter(|section| matches!(section, HelpInfo::Error(..) | HelpInfo::Report(..)))
+    }
+
+    fn fmt_branches(&self, f: &mut fmt::Formatter<'_>, prefix: &str) -> fmt::Result {
+        let count = self.errors().count();
+
+        for (index, section) in self.errors().enumerate() {
+            let last = index + 1 == count;
+            let (branch, continuation) = if last {
+                ("└─ ", "   ")
+            } else {
+                ("├─ ", "│  ")
+            };
+            let child_prefix = format!("{}{}", prefix, continuation);
+            let (errors, theme) = section.errors().expect("error or report section");
+
+            for (n, error) in errors.enumerate() {
+                let message = error
+                    .to_string()
+                    .replace('\n', &format!("\n{}   ", child_prefix));
+                let lead = if n == 0 {
+                    format!("{}{}", prefix, branch)
+                } else {
+                    child_prefix.clone()
+                };
+                write!(
+                    f,
+                    "\n{}{}: {}",
+                    lead,
+                    n,
+                    message.style(theme.help_info_error)
+                )?;
+            }
+
+            ErrorTree(section.children()).fmt_branches(f, &child_prefix)?;
         }
+
+        Ok(())
+    }
+}
+
+impl Display for ErrorTree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Errors:")?;
+        self.fmt_branches(f, "   ")
     }
 }
@@ -2,6 +2,9 @@
 use crate::writers::WriterExt;
 use std::fmt::{self, Display};
 
+#[allow(deprecated)]
+pub(crate) type PanicInfo<'a> = std::panic::PanicInfo<'a>;
+
 #[cfg(feature = "issue-url")]
 pub(crate) mod github;
 pub(crate) mod help;
@@ -233,6 +236,36 @@ pub trait Section: crate::private::Sealed {
         F: FnOnce() -> E,
         E: std::error::Error + Send + Sync + 'static;
 
+    /// Add another report to an error report, to be displayed after the primary error message
+    /// section.
+    ///
+    /// Unlike [`error`](Section::error), the errors and reports attached to the added report are
+    /// kept. When multiple errors are attached, or an attached report has errors of its own, they
+    /// are displayed as a tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,should_panic
+    /// use color_eyre::{eyre::eyre, eyre::Report, Section};
+    ///
+    /// let download = eyre!("connection refused").wrap_err("failed to download `serde`");
+    /// let build = eyre!("no space left on device")
+    ///     .wrap_err("failed to build `syn`")
+    ///     .report(eyre!("failed to write `libsyn.rlib`"));
+    ///
+    /// Err(eyre!("failed to install dependencies"))
+    ///     .report(download)
+    ///     .report(build)?;
+    /// # Ok::<_, Report>(())
+    /// ```
+    fn report(self, report: eyre::Report) -> Self::Return;
+
+    /// Add another report to an error report, to be displayed after the primary error message
+    /// section. The closure to create the report is lazily evaluated only in the case of an error.
+    fn with_report<F>(self, report: F) -> Self::Return
+    where
+        F: FnOnce() -> eyre::Report;
+
     /// Add a Note to an error report, to be displayed after the chain of errors.
     ///
     /// # Examples
@@ -324,10 +357,99 @@ pub trait Section: crate::private::Sealed {
     /// Useful for reporting "unexceptional" errors for which a backtrace
     /// isn't really necessary.
     fn suppress_backtrace(self, suppress: bool) -> Self::Return;
+
+    /// Attach an [`ErrorTag`](crate::ErrorTag) classifying the error report.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use color_eyre::{eyre::eyre, eyre::Report, ErrorTag, Section};
+    ///
+    /// let report: Report = eyre!("missing argument `--config`").tag(ErrorTag::UserError);
+    /// ```
+    fn tag(self, tag: crate::ErrorTag) -> Self::Return;
 }
 
 /// Trait for printing a panic error message for the given PanicInfo
 pub trait PanicMessage: Send + Sync + 'static {
     /// Display trait equivalent for implementing the display logic
-    fn display(&self, pi: &std::panic::PanicInfo<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+    fn display(&self, pi: &PanicInfo<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Display the panic message with access to additional context about the panic
+    ///
+    /// The panic hook always calls this method. The default implementation
+    /// ignores the context and defers to [`display`](PanicMessage::display),
+    /// so implementors only need to override it when their message depends on
+    /// the context.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use color_eyre::section::{PanicContext, PanicMessage};
+    /// use std::fmt;
+    ///
+    /// struct ThreadAwareMessage;
+    ///
+    /// impl PanicMessage for ThreadAwareMessage {
+    ///     fn display(
+    ///         &self,
+    ///         _pi: &std::panic::PanicInfo<'_>,
+    ///         f: &mut fmt::Formatter<'_>,
+    ///     ) -> fmt::Result {
+    ///         writeln!(f, "The application panicked.")
+    ///     }
+    ///
+    ///     fn display_with_context(
+    ///         &self,
+    ///         _pi: &std::panic::PanicInfo<'_>,
+    ///         context: &PanicContext<'_>,
+    ///         f: &mut fmt::Formatter<'_>,
+    ///     ) -> fmt::Result {
+    ///         let thread = context.thread_name.unwrap_or("<unnamed>");
+    ///         writeln!(f, "Thread '{}' panicked.", thread)
+    ///     }
+    /// }
+    /// ```
+    fn display_with_context(
+        &self,
+        pi: &PanicInfo<'_>,
+        context: &PanicContext<'_>,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let _ = context;
+        self.display(pi, f)
+    }
+}
+
+/// Additional information about a panic for use by a [`PanicMessage`]
+#[non_exhaustive]
+pub struct PanicContext<'a> {
+    /// The name of the thread that panicked, if it has one
+    pub thread_name: Option<&'a str>,
+    /// Whether a backtrace was captured for this panic
+    pub backtrace_captured: bool,
+    /// Whether a `SpanTrace` was captured for this panic
+    pub span_trace_captured: bool,
+    /// The metadata configured via `HookBuilder::add_issue_metadata`
+    ///
+    /// This is always empty when the `issue-url` feature is disabled.
+    pub metadata: &'a [(String, Box<dyn Display + Send + Sync + 'static>)],
+}
+
+impl fmt::Debug for PanicContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PanicContext")
+            .field("thread_name", &self.thread_name)
+            .field("backtrace_captured", &self.backtrace_captured)
+            .field("span_trace_captured", &self.span_trace_captured)
+            .field(
+                "metadata",
+                &self
+                    .metadata
+                    .iter()
+                    .map(|(key, value)| (key, value.to_string()))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
 }
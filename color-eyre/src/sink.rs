@@ -0,0 +1,174 @@
+//! Collecting reports from many threads or tasks into one
+//!
+//! Supervisor style programs often run many workers and want to report all of
+//! their failures together, either once at the end or periodically. A
+//! [`channel`] provides a cloneable [`ReportSink`] that workers send their
+//! reports to, and a [`ReportCollector`] that gathers them back up.
+//!
+//! ```rust
+//! use color_eyre::eyre::eyre;
+//! use std::thread;
+//!
+//! let (sink, collector) = color_eyre::sink::channel();
+//!
+//! let workers: Vec<_> = (0..3)
+//!     .map(|n| {
+//!         let sink = sink.clone();
+//!         thread::spawn(move || {
+//!             sink.send_from(format!("worker {}", n), eyre!("worker {} failed", n));
+//!         })
+//!     })
+//!     .collect();
+//! drop(sink);
+//!
+//! for worker in workers {
+//!     worker.join().unwrap();
+//! }
+//!
+//! let report = collector.finish("some workers failed").unwrap_err();
+//! ```
+use crate::eyre::Report;
+use crate::Section;
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::mpsc;
+
+/// Create a connected [`ReportSink`] and [`ReportCollector`]
+pub fn channel() -> (ReportSink, ReportCollector) {
+    let (tx, rx) = mpsc::channel();
+    (ReportSink { tx }, ReportCollector { rx })
+}
+
+/// The sending half of a [`channel`]
+///
+/// Sinks can be cloned and moved to other threads or tasks. Sending never
+/// blocks, and reports sent after the [`ReportCollector`] has been dropped are
+/// discarded.
+#[derive(Debug, Clone)]
+pub struct ReportSink {
+    tx: mpsc::Sender<CollectedReport>,
+}
+
+impl ReportSink {
+    /// Send a report to the collector
+    pub fn send(&self, report: Report) {
+        let _ = self.tx.send(CollectedReport {
+            origin: None,
+            report,
+        });
+    }
+
+    /// Send a report to the collector, tagged with the identity of the thread
+    /// or task it came from
+    pub fn send_from(&self, origin: impl Into<String>, report: Report) {
+        let _ = self.tx.send(CollectedReport {
+            origin: Some(origin.into()),
+            report,
+        });
+    }
+
+    /// Send a report to the collector, tagged with the name of the current
+    /// thread if it has one
+    pub fn send_from_current_thread(&self, report: Report) {
+        let origin = std::thread::current().name().map(String::from);
+        let _ = self.tx.send(CollectedReport { origin, report });
+    }
+}
+
+/// The receiving half of a [`channel`]
+#[derive(Debug)]
+pub struct ReportCollector {
+    rx: mpsc::Receiver<CollectedReport>,
+}
+
+impl ReportCollector {
+    /// Take all reports that have been sent so far without waiting for more
+    ///
+    /// This is intended for periodically logging failures while workers are
+    /// still running.
+    pub fn drain(&self) -> Vec<CollectedReport> {
+        self.rx.try_iter().collect()
+    }
+
+    /// Wait until every [`ReportSink`] has been dropped and return all the
+    /// reports that were sent
+    pub fn collect(self) -> Vec<CollectedReport> {
+        self.rx.iter().collect()
+    }
+
+    /// Wait until every [`ReportSink`] has been dropped and combine all the
+    /// reports that were sent into a single report
+    ///
+    /// Returns `Ok(())` if no reports were sent. Otherwise the returned report
+    /// has the message `msg` and one error section per collected report, in
+    /// the order they were received.
+    pub fn finish<D>(self, msg: D) -> Result<(), Report>
+    where
+        D: fmt::Display + fmt::Debug + Send + Sync + 'static,
+    {
+        let reports = self.collect();
+
+        if reports.is_empty() {
+            return Ok(());
+        }
+
+        Err(reports
+            .into_iter()
+            .fold(Report::msg(msg), |report, collected| {
+                report.error(collected)
+            }))
+    }
+}
+
+/// A report received by a [`ReportCollector`]
+///
+/// Displays as the report's message, prefixed with its origin when present,
+/// and exposes the rest of the report's chain of errors as its sources.
+pub struct CollectedReport {
+    origin: Option<String>,
+    report: Report,
+}
+
+impl CollectedReport {
+    /// The identity of the thread or task the report was sent from, if it was
+    /// tagged with one
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// The collected report
+    pub fn report(&self) -> &Report {
+        &self.report
+    }
+
+    /// Take ownership of the collected report
+    pub fn into_report(self) -> Report {
+        self.report
+    }
+}
+
+impl fmt::Display for CollectedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(origin) = &self.origin {
+            write!(f, "[{}] ", origin)?;
+        }
+
+        fmt::Display::fmt(&self.report, f)
+    }
+}
+
+impl fmt::Debug for CollectedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(origin) = &self.origin {
+            write!(f, "[{}] ", origin)?;
+        }
+
+        fmt::Debug::fmt(&self.report, f)
+    }
+}
+
+impl StdError for CollectedReport {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.report.source()
+    }
+}
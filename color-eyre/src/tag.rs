@@ -0,0 +1,52 @@
+//! Tags for classifying error reports
+use std::borrow::Cow;
+use std::fmt;
+
+/// A tag classifying the kind of failure an error report describes
+///
+/// Tags are attached to reports via [`Section::tag`](crate::Section::tag) and
+/// can be read back with [`Handler::tags`](crate::Handler::tags). They
+/// influence how `color-eyre` renders a report:
+///
+/// - Reports tagged [`ErrorTag::UserError`] are rendered tersely, showing only
+///   the chain of errors and any help sections.
+/// - With the `issue-url` feature, [`HookBuilder::issue_report_filter`] can
+///   restrict issue URL generation to reports with certain tags, e.g. only
+///   those tagged [`ErrorTag::Bug`].
+///
+/// [`HookBuilder::issue_report_filter`]: crate::config::HookBuilder::issue_report_filter
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorTag {
+    /// The error was caused by invalid input or usage, not a defect
+    UserError,
+    /// The error is temporary and the operation may succeed if retried
+    Transient,
+    /// The error indicates a defect in the program
+    Bug,
+    /// An application specific tag
+    Custom(Cow<'static, str>),
+}
+
+impl ErrorTag {
+    /// Construct an application specific tag
+    pub fn custom(tag: impl Into<Cow<'static, str>>) -> Self {
+        ErrorTag::Custom(tag.into())
+    }
+
+    /// The name of the tag, e.g. `user-error`
+    pub fn as_str(&self) -> &str {
+        match self {
+            ErrorTag::UserError => "user-error",
+            ErrorTag::Transient => "transient",
+            ErrorTag::Bug => "bug",
+            ErrorTag::Custom(tag) => tag,
+        }
+    }
+}
+
+impl fmt::Display for ErrorTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
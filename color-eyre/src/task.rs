@@ -0,0 +1,70 @@
+//! Span traces across tokio task boundaries
+//!
+//! A `SpanTrace` captured inside a spawned task only contains the spans
+//! entered within that task, so reports created there don't show which
+//! operation the task was spawned for. Tasks spawned with [`spawn`], or
+//! futures wrapped with [`trace_spawn`], remember the span trace of the
+//! place they were spawned from, and reports created while they run print it
+//! as a separate `SPAWNED FROM` section after their own span trace.
+//!
+//! Tasks spawned from within such tasks remember the whole chain of span
+//! traces, innermost first.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use color_eyre::{eyre::eyre, Report};
+//! use tracing::instrument;
+//!
+//! #[instrument]
+//! async fn handle_request(id: u32) -> Result<(), Report> {
+//!     color_eyre::task::spawn(async move { Err(eyre!("request {} failed", id)) })
+//!         .await
+//!         .unwrap()
+//! }
+//! ```
+use std::future::Future;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing_error::SpanTrace;
+
+tokio::task_local! {
+    static SPAWNED_FROM: Arc<[SpanTrace]>;
+}
+
+/// Spawn a new tokio task which remembers the span trace of the caller
+///
+/// This is [`tokio::spawn`] for a future wrapped with [`trace_spawn`], and
+/// has to be called from within a tokio runtime as well.
+#[track_caller]
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(trace_spawn(future))
+}
+
+/// Remember the span trace of the caller while `future` is polled
+///
+/// The span trace is captured when this function is called, so it should be
+/// called where the future is handed to a spawner, e.g. for
+/// `tokio::task::spawn_local` or a `JoinSet`.
+pub fn trace_spawn<F>(future: F) -> impl Future<Output = F::Output>
+where
+    F: Future,
+{
+    let parents = spawned_from();
+    let traces = std::iter::once(SpanTrace::capture())
+        .chain(parents.iter().cloned())
+        .collect();
+
+    SPAWNED_FROM.scope(traces, future)
+}
+
+/// The span traces remembered by the current task, innermost first
+pub(crate) fn spawned_from() -> Arc<[SpanTrace]> {
+    SPAWNED_FROM
+        .try_with(Arc::clone)
+        .unwrap_or_else(|_| Arc::new([]))
+}
@@ -0,0 +1,156 @@
+//! Suppression of identical error reports printed in quick succession
+//!
+//! After enabling throttling with
+//! [`HookBuilder::throttle_repeated_reports`], reports printed via
+//! [`ThrottleExt::throttled`] collapse into a single line noting how often
+//! the error repeated if an identical report was printed less than the
+//! configured window ago. Reports printed any other way, e.g. with `{:?}`,
+//! are neither throttled nor counted.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use color_eyre::{eyre::eyre, throttle::ThrottleExt};
+//! use std::time::Duration;
+//!
+//! color_eyre::config::HookBuilder::default()
+//!     .throttle_repeated_reports(Duration::from_secs(60))
+//!     .install()
+//!     .unwrap();
+//!
+//! for _ in 0..3 {
+//!     let report = eyre!("connection refused");
+//!     eprintln!("{}", report.throttled());
+//! }
+//! ```
+//!
+//! [`HookBuilder::throttle_repeated_reports`]: crate::config::HookBuilder::throttle_repeated_reports
+use crate::eyre::{Report, ReportKey};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Tracks recently printed reports by their [`Report::dedup_key`]
+pub(crate) struct Throttle {
+    window: Duration,
+    seen: Mutex<HashMap<ReportKey, Seen>>,
+}
+
+struct Seen {
+    first: Instant,
+    repeats: usize,
+}
+
+impl Throttle {
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record that `report` is being printed.
+    ///
+    /// Returns the number of times an identical report has already been
+    /// printed within the window, or `None` if this is the first one and the
+    /// full report should be printed.
+    fn record(&self, report: &Report) -> Option<usize> {
+        let key = report.dedup_key();
+        let now = Instant::now();
+        let window = self.window;
+
+        // A poisoned lock only means another report panicked while printing,
+        // the map itself is still usable.
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.retain(|_, seen| now.duration_since(seen.first) < window);
+
+        match seen.get_mut(&key) {
+            Some(seen) => {
+                seen.repeats += 1;
+                Some(seen.repeats)
+            }
+            None => {
+                seen.insert(
+                    key,
+                    Seen {
+                        first: now,
+                        repeats: 0,
+                    },
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Extension trait for printing reports throttled according to
+/// [`HookBuilder::throttle_repeated_reports`], see the
+/// [module documentation](self).
+///
+/// [`HookBuilder::throttle_repeated_reports`]: crate::config::HookBuilder::throttle_repeated_reports
+pub trait ThrottleExt: crate::private::Sealed {
+    /// Record that the report is about to be printed and return its output.
+    ///
+    /// The returned value displays the `Debug` output of the report, or a
+    /// single line if an identical report was printed within the throttling
+    /// window. The decision is made once by this call, so the value can be
+    /// formatted any number of times. Reports whose handler isn't a
+    /// color-eyre [`Handler`](crate::Handler) or which were created without
+    /// throttling enabled are always printed in full.
+    fn throttled(&self) -> Throttled<'_>;
+}
+
+impl ThrottleExt for Report {
+    fn throttled(&self) -> Throttled<'_> {
+        let repeats = self
+            .handler()
+            .downcast_ref::<crate::Handler>()
+            .and_then(|handler| handler.throttle.as_ref())
+            .and_then(|throttle| throttle.record(self));
+
+        Throttled {
+            report: self,
+            repeats,
+        }
+    }
+}
+
+/// The output of a report printed via [`ThrottleExt::throttled`]
+#[derive(Debug)]
+pub struct Throttled<'a> {
+    report: &'a Report,
+    repeats: Option<usize>,
+}
+
+impl Throttled<'_> {
+    /// The number of times an identical report was already printed within
+    /// the throttling window, or `None` if the report is printed in full.
+    pub fn repeats(&self) -> Option<usize> {
+        self.repeats
+    }
+}
+
+impl fmt::Display for Throttled<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let repeats = match self.repeats {
+            Some(repeats) => repeats,
+            None => return fmt::Debug::fmt(self.report, f),
+        };
+
+        let theme = self
+            .report
+            .handler()
+            .downcast_ref::<crate::Handler>()
+            .map(|handler| handler.theme)
+            .unwrap_or_default();
+
+        write!(
+            f,
+            "{} (previous error repeated {} time{})",
+            theme.error.style(&**self.report),
+            repeats,
+            if repeats == 1 { "" } else { "s" },
+        )
+    }
+}
@@ -1,4 +1,4 @@
-use crate::config::{lib_verbosity, panic_verbosity, Verbosity};
+use crate::config::{EnvVars, Theme, Verbosity};
 use fmt::Write;
 use std::fmt::{self, Display};
 #[cfg(feature = "capture-spantrace")]
@@ -159,8 +159,34 @@ where
     }
 }
 
+/// Text rendered as an OSC 8 terminal hyperlink to `url`, or as plain text if
+/// there is no url
+pub(crate) struct Hyperlink<'a, T> {
+    pub(crate) url: Option<&'a str>,
+    pub(crate) text: T,
+}
+
+impl<T> fmt::Display for Hyperlink<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.url {
+            Some(url) => write!(f, "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, self.text),
+            None => write!(f, "{}", self.text),
+        }
+    }
+}
+
 #[cfg(feature = "capture-spantrace")]
-pub(crate) struct FormattedSpanTrace<'a>(pub(crate) &'a SpanTrace);
+/// A `SpanTrace`, whether to print it with the outermost span first, and the
+/// spans and fields to leave out
+pub(crate) struct FormattedSpanTrace<'a>(
+    pub(crate) &'a SpanTrace,
+    pub(crate) bool,
+    pub(crate) &'a crate::config::SpanTraceFilters,
+    pub(crate) &'a str,
+);
 
 #[cfg(feature = "capture-spantrace")]
 impl fmt::Display for FormattedSpanTrace<'_> {
@@ -169,11 +195,11 @@ impl fmt::Display for FormattedSpanTrace<'_> {
         use indenter::Format;
 
         if self.0.status() == SpanTraceStatus::CAPTURED {
-            write!(
-                indented(f).with_format(Format::Uniform { indentation: "  " }),
-                "{}",
-                color_spantrace::colorize(self.0)
-            )?;
+            let mut f = indented(f).with_format(Format::Uniform { indentation: "  " });
+            let span_trace = color_spantrace::ColorSpanTrace::new(self.0)
+                .reversed(self.1)
+                .title(self.3);
+            write!(f, "{}", self.2.apply(span_trace))?;
         }
 
         Ok(())
@@ -182,17 +208,17 @@ impl fmt::Display for FormattedSpanTrace<'_> {
 
 pub(crate) struct EnvSection<'a> {
     pub(crate) bt_captured: &'a bool,
+    pub(crate) env_vars: &'a EnvVars,
+    pub(crate) theme: Theme,
+    pub(crate) verbosity: Verbosity,
+    pub(crate) source_snippets: Option<bool>,
     #[cfg(feature = "capture-spantrace")]
     pub(crate) span_trace: Option<&'a SpanTrace>,
 }
 
 impl fmt::Display for EnvSection<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let v = if std::thread::panicking() {
-            panic_verbosity()
-        } else {
-            lib_verbosity()
-        };
+        let v = self.verbosity;
         write!(f, "{}", BacktraceOmited(!self.bt_captured))?;
 
         let mut separated = HeaderWriter {
@@ -200,13 +226,24 @@ impl fmt::Display for EnvSection<'_> {
             header: &"\n",
             started: false,
         };
-        write!(&mut separated.ready(), "{}", SourceSnippets(v))?;
+        write!(
+            &mut separated.ready(),
+            "{}",
+            SourceSnippets(v, self.source_snippets)
+        )?;
         #[cfg(feature = "capture-spantrace")]
         write!(
             &mut separated.ready(),
             "{}",
             SpanTraceOmited(self.span_trace)
         )?;
+        if !self.env_vars.names.is_empty() {
+            write!(
+                &mut separated.ready(),
+                "{}",
+                crate::fmt::EnvVarsSection(self.env_vars, self.theme)
+            )?;
+        }
         Ok(())
     }
 }
@@ -253,11 +290,12 @@ impl fmt::Display for BacktraceOmited {
     }
 }
 
-struct SourceSnippets(Verbosity);
+struct SourceSnippets(Verbosity, Option<bool>);
 
 impl fmt::Display for SourceSnippets {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.0 <= Verbosity::Medium {
+        // Only suggest increasing the verbosity if snippets weren't explicitly configured.
+        if self.1.is_none() && self.0 <= Verbosity::Medium {
             write!(
                 f,
                 "Run with RUST_BACKTRACE=full to include source snippets."
@@ -267,3 +305,53 @@ impl fmt::Display for SourceSnippets {
         Ok(())
     }
 }
+
+/// A writer which starts every line after the first with `indent` spaces
+/// followed by `prefix`, so that wrapped text stays aligned with the item it
+/// belongs to.
+pub(crate) struct HangingIndent<'a, W> {
+    inner: W,
+    indent: usize,
+    prefix: &'a str,
+    needs_indent: bool,
+}
+
+impl<'a, W> HangingIndent<'a, W> {
+    pub(crate) fn new(inner: W, indent: usize, prefix: &'a str) -> Self {
+        HangingIndent {
+            inner,
+            indent,
+            prefix,
+            needs_indent: false,
+        }
+    }
+}
+
+impl<W> fmt::Write for HangingIndent<'_, W>
+where
+    W: fmt::Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (n, line) in s.split('\n').enumerate() {
+            if n > 0 {
+                self.inner.write_char('\n')?;
+                self.needs_indent = true;
+            }
+
+            if self.needs_indent && !line.is_empty() {
+                write!(
+                    self.inner,
+                    "{:indent$}{}",
+                    "",
+                    self.prefix,
+                    indent = self.indent
+                )?;
+                self.needs_indent = false;
+            }
+
+            self.inner.write_str(line)?;
+        }
+
+        Ok(())
+    }
+}
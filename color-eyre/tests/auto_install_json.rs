@@ -0,0 +1,17 @@
+use color_eyre::eyre;
+use eyre::{eyre, WrapErr};
+
+#[test]
+fn json_output() {
+    std::env::set_var("LOG_FORMAT", "json");
+    color_eyre::auto_install().unwrap();
+
+    let report = Err::<(), _>(eyre!("disk \"full\""))
+        .wrap_err("failed to save")
+        .unwrap_err();
+
+    let report = format!("{:?}", report);
+    assert!(report.starts_with(r#"{"error":"failed to save","causes":["disk \"full\""]"#));
+    assert!(report.ends_with('}'));
+    assert!(!report.contains('\n'));
+}
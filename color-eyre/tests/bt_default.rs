@@ -0,0 +1,24 @@
+use color_eyre::eyre;
+use eyre::eyre;
+
+#[test]
+fn captured_by_default() {
+    std::env::remove_var("RUST_LIB_BACKTRACE");
+    std::env::remove_var("RUST_BACKTRACE");
+    std::env::remove_var("COLOR_EYRE_ERROR_VERBOSITY");
+
+    color_eyre::config::HookBuilder::default()
+        .capture_backtrace_by_default(true)
+        .install()
+        .unwrap();
+
+    let report = eyre!("error occured");
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    assert!(handler.backtrace().is_some());
+
+    let report = format!("{:?}", report);
+    assert!(report.contains(" BACKTRACE ━"));
+}
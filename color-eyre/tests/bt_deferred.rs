@@ -0,0 +1,21 @@
+use color_eyre::{eyre, Handler};
+use eyre::eyre;
+
+#[test]
+fn deferred_backtrace_resolution() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    color_eyre::install().unwrap();
+
+    let report = eyre!("error occured");
+
+    let handler = report.handler().downcast_ref::<Handler>().unwrap();
+    let backtrace = handler.backtrace().unwrap();
+    assert!(backtrace
+        .frames()
+        .iter()
+        .any(|frame| !frame.symbols().is_empty()));
+
+    let report = format!("{:?}", report);
+    assert!(report.contains("BACKTRACE"));
+}
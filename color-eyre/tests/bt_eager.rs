@@ -0,0 +1,31 @@
+use color_eyre::{eyre, Handler};
+use eyre::{eyre, Report};
+
+#[inline(never)]
+fn create_report() -> Report {
+    eyre!("error occured")
+}
+
+#[test]
+fn eager_backtrace_resolution() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    color_eyre::config::HookBuilder::default()
+        .resolve_backtraces_eagerly(true)
+        .install()
+        .unwrap();
+
+    let report = create_report();
+
+    // The backtrace shows where the report was created, not where it was
+    // first accessed
+    let handler = report.handler().downcast_ref::<Handler>().unwrap();
+    let backtrace = handler.backtrace().unwrap();
+    assert!(backtrace
+        .frames()
+        .iter()
+        .flat_map(|frame| frame.symbols())
+        .any(|symbol| symbol.name().map_or(false, |name| name
+            .to_string()
+            .contains("bt_eager::create_report"))));
+}
@@ -0,0 +1,52 @@
+#![cfg(generic_member_access)]
+#![feature(error_generic_member_access)]
+
+use color_eyre::eyre::{Report, WrapErr};
+use std::backtrace::Backtrace;
+use std::error::Request;
+use std::fmt;
+
+#[derive(Debug)]
+struct TracedError {
+    backtrace: Backtrace,
+}
+
+impl fmt::Display for TracedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("traced error")
+    }
+}
+
+impl std::error::Error for TracedError {
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {
+        request.provide_ref::<Backtrace>(&self.backtrace);
+    }
+}
+
+#[test]
+fn prefers_source_backtrace() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+    std::env::remove_var("COLOR_EYRE_ERROR_VERBOSITY");
+
+    color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::new())
+        .install()
+        .unwrap();
+
+    let error = TracedError {
+        backtrace: Backtrace::force_capture(),
+    };
+    let report = Err::<(), _>(Report::new(error))
+        .wrap_err("outer")
+        .unwrap_err();
+
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    assert!(handler.backtrace().is_none());
+
+    let output = format!("{:?}", report);
+    assert_eq!(output.matches(" BACKTRACE ━").count(), 1);
+    assert!(output.contains("bt_source::prefers_source_backtrace"));
+}
@@ -0,0 +1,42 @@
+use color_eyre::{eyre, Handler};
+use eyre::eyre;
+
+#[test]
+fn unresolved_backtrace() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    color_eyre::install().unwrap();
+
+    let report = eyre!("error occured");
+
+    let handler = report.handler().downcast_ref::<Handler>().unwrap();
+    let backtrace = handler.unresolved_backtrace().unwrap();
+    assert!(backtrace.addresses().next().is_some());
+    assert!(backtrace.resolve().iter().any(|frame| frame
+        .name
+        .as_deref()
+        .map_or(false, |name| name
+            .contains("bt_unresolved::unresolved_backtrace"))));
+
+    assert!(handler.backtrace().is_some());
+
+    let report = format!("{:?}", report);
+    assert!(report.contains("BACKTRACE"));
+}
+
+#[test]
+fn capture_unresolved_backtrace() {
+    let backtrace = color_eyre::config::UnresolvedBacktrace::capture();
+    let frames = backtrace.resolve();
+
+    let names: Vec<_> = frames
+        .iter()
+        .filter_map(|frame| frame.name.as_deref())
+        .collect();
+    assert!(names
+        .iter()
+        .any(|name| name.contains("bt_unresolved::capture_unresolved_backtrace")));
+    assert!(!names
+        .iter()
+        .any(|name| name.contains("UnresolvedBacktrace::capture")));
+}
@@ -0,0 +1,31 @@
+use color_eyre::{bug_report::BugReport, eyre};
+use eyre::{eyre, WrapErr};
+
+#[test]
+fn bug_report_bundle() {
+    color_eyre::install().unwrap();
+
+    let report = Err::<(), _>(eyre!("disk full"))
+        .wrap_err("failed to save")
+        .unwrap_err();
+
+    let directory = std::env::temp_dir().join("color-eyre-bug-report-test");
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let path = BugReport::new(&report)
+        .directory(&directory)
+        .recent_logs(|| vec!["INFO saving file".into()])
+        .write()
+        .unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert!(path.starts_with(&directory));
+    assert!(contents.starts_with("# Bug report"));
+    assert!(contents.contains("failed to save"));
+    assert!(contents.contains("disk full"));
+    assert!(contents.contains("## System"));
+    assert!(contents.contains("INFO saving file"));
+    assert!(!contents.contains('\u{1b}'));
+}
@@ -0,0 +1,42 @@
+use color_eyre::config::{HookBuilder, Theme};
+use color_eyre::eyre::eyre;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn build_info() {
+    let buffer = SharedBuffer::default();
+
+    HookBuilder::default()
+        .theme(Theme::plain())
+        .display_env_section(false)
+        .panic_writer(buffer.clone())
+        .add_metadata("version", "1.2.3")
+        .add_metadata("git sha", "0123abc")
+        .install()
+        .unwrap();
+
+    let expected = "Build Info:\n   version = 1.2.3\n   git sha = 0123abc";
+
+    let report = eyre!("the reactor core melted");
+    let output = format!("{:?}", report);
+    assert!(output.ends_with(expected), "{}", output);
+
+    let _ = std::panic::catch_unwind(|| panic!("the reactor core melted"));
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains(expected), "{}", output);
+}
@@ -0,0 +1,39 @@
+use color_eyre::eyre::eyre;
+use std::time::Duration;
+
+fn elapsed_ms(line: &str) -> u128 {
+    let (_, elapsed) = line.rsplit_once("(+").unwrap();
+    elapsed.strip_suffix("ms)").unwrap().parse().unwrap()
+}
+
+#[test]
+fn capture_timestamps() {
+    color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::plain())
+        .capture_timestamps(true)
+        .install()
+        .unwrap();
+
+    let report = eyre!("connection refused");
+    std::thread::sleep(Duration::from_millis(20));
+    let report = report
+        .wrap_err("failed to fetch index")
+        .wrap_err("failed to update");
+
+    let output = format!("{:?}", report);
+    let lines: Vec<_> = output.lines().collect();
+    assert!(
+        lines[1].starts_with("   0: failed to update (+"),
+        "{}",
+        output
+    );
+    assert!(
+        lines[2].starts_with("   1: failed to fetch index (+"),
+        "{}",
+        output
+    );
+    assert_eq!(lines[3], "   2: connection refused", "{}", output);
+
+    assert!(elapsed_ms(lines[1]) < 20, "{}", output);
+    assert!(elapsed_ms(lines[2]) >= 20, "{}", output);
+}
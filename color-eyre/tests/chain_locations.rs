@@ -0,0 +1,29 @@
+#![cfg(feature = "track-caller")]
+
+use color_eyre::config::{HookBuilder, Theme};
+use color_eyre::eyre::{eyre, WrapErr};
+
+#[test]
+fn chain_locations() {
+    HookBuilder::default()
+        .theme(Theme::plain())
+        .display_env_section(false)
+        .display_location_section(false)
+        .display_chain_locations(true)
+        .install()
+        .unwrap();
+
+    let line = line!();
+    let report = Err::<(), _>(eyre!("connection refused"))
+        .wrap_err("failed to connect")
+        .unwrap_err();
+
+    let output = format!("{:?}", report);
+    let expected = format!(
+        "\n   0: failed to connect\n      at {file}:{outer}\n   1: connection refused\n      at {file}:{inner}",
+        file = file!(),
+        outer = line + 2,
+        inner = line + 1,
+    );
+    assert!(output.starts_with(&expected), "{}", output);
+}
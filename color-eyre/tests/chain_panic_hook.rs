@@ -0,0 +1,43 @@
+use color_eyre::config::HookBuilder;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Calls(Arc<Mutex<Vec<&'static str>>>);
+
+impl Calls {
+    fn take(&self) -> Vec<&'static str> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl io::Write for Calls {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().unwrap().push("color-eyre");
+        Ok(())
+    }
+}
+
+fn install_previous_hook(calls: &Calls) {
+    let calls = calls.clone();
+    std::panic::set_hook(Box::new(move |_| calls.0.lock().unwrap().push("previous")));
+}
+
+#[test]
+fn chain_panic_hook() {
+    let calls = Calls::default();
+    install_previous_hook(&calls);
+
+    HookBuilder::default()
+        .panic_writer(calls.clone())
+        .chain_panic_hook(true)
+        .install()
+        .unwrap();
+
+    let _ = std::panic::catch_unwind(|| panic!("oh no!"));
+    assert_eq!(calls.take(), ["color-eyre", "previous"]);
+}
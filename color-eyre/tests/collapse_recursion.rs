@@ -0,0 +1,33 @@
+use color_eyre::eyre::{eyre, Report};
+
+#[inline(never)]
+fn recurse(depth: usize) -> Report {
+    if depth == 0 {
+        return eyre!("recursed too deep");
+    }
+
+    recurse(depth - 1)
+}
+
+#[test]
+fn collapse_recursion() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::plain())
+        .install()
+        .unwrap();
+
+    let report = format!("{:?}", recurse(50));
+    let (_, backtrace) = report.split_once(" BACKTRACE ").unwrap();
+    assert!(
+        backtrace.contains("⋮ frame repeated 49 times ⋮"),
+        "{}",
+        backtrace
+    );
+    assert!(
+        backtrace.matches("collapse_recursion::recurse").count() < 5,
+        "{}",
+        backtrace
+    );
+}
@@ -0,0 +1,35 @@
+#[test]
+fn crash_report() {
+    let dir = std::env::temp_dir().join(format!("color-eyre-crash-{}", std::process::id()));
+    let log = std::env::temp_dir().join(format!("color-eyre-crash-{}.log", std::process::id()));
+
+    color_eyre::config::HookBuilder::blank()
+        .theme(color_eyre::config::Theme::plain())
+        .panic_to_log_file(&log)
+        .unwrap()
+        .crash_report_dir(&dir)
+        .install()
+        .unwrap();
+
+    std::panic::catch_unwind(|| panic!("oh no \"quoted\"")).unwrap_err();
+
+    let reports: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(reports.len(), 1);
+
+    let crash_report = std::fs::read_to_string(&reports[0]).unwrap();
+    assert!(crash_report.starts_with(r#"{"panic":"oh no \"quoted\"","location":""#));
+    assert!(crash_report.contains(r#""backtrace":""#));
+    assert!(crash_report.contains(r#""thread":"crash_report""#));
+
+    let panic_report = std::fs::read_to_string(&log).unwrap();
+    assert!(panic_report.contains(&format!(
+        "A crash report was written to {}",
+        reports[0].display()
+    )));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    std::fs::remove_file(&log).unwrap();
+}
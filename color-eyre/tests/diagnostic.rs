@@ -0,0 +1,54 @@
+use color_eyre::eyre::{Diagnostic, Report};
+use std::fmt;
+
+#[derive(Debug)]
+struct NotFound(&'static str);
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "config file `{}` not found", self.0)
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+impl Diagnostic for NotFound {
+    fn code(&self) -> Option<&'static str> {
+        Some("CFG001")
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("config files are looked up in the working directory".into())
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        Some(format!("run `init` to create `{}`", self.0))
+    }
+}
+
+#[test]
+fn diagnostic_sections() {
+    color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::plain())
+        .display_env_section(false)
+        .install()
+        .unwrap();
+
+    let report = Report::from_diagnostic(NotFound("app.toml"));
+    let output = format!("{:?}", report);
+
+    assert!(output.starts_with("Error[CFG001]:"), "{}", output);
+    assert!(
+        output.contains("Note: config files are looked up in the working directory"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("Suggestion: run `init` to create `app.toml`"),
+        "{}",
+        output
+    );
+
+    let output = format!("{:?}", Report::new(NotFound("app.toml")));
+    assert!(!output.contains("Note:"), "{}", output);
+}
@@ -0,0 +1,35 @@
+use color_eyre::eyre::{self, eyre};
+
+#[test]
+fn error_code() {
+    let builder = color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::plain())
+        .display_env_section(false);
+    #[cfg(feature = "issue-url")]
+    let builder = builder.issue_url("https://github.com/eyre-rs/eyre/issues/new");
+    builder.install().unwrap();
+
+    let mut report = eyre!(code = "E0425", "cannot find value `x` in this scope");
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    assert_eq!(handler.code(), Some("E0425"));
+
+    let output = format!("{:?}", report);
+    assert!(
+        output.starts_with("Error[E0425]:\n   0: cannot find value"),
+        "{}",
+        output
+    );
+
+    report.set_severity(eyre::Severity::Warning);
+    let output = format!("{:?}", report);
+    assert!(output.starts_with("Warning[E0425]:\n"), "{}", output);
+
+    #[cfg(feature = "issue-url")]
+    {
+        let url = color_eyre::issue_url(&report).unwrap();
+        assert!(url.contains("title=%5BE0425%5D"), "{}", url);
+    }
+}
@@ -0,0 +1,34 @@
+use color_eyre::{eyre, ErrorTag, Section};
+use eyre::eyre;
+
+#[test]
+fn user_errors_render_tersely() {
+    color_eyre::config::HookBuilder::default()
+        .add_default_filters()
+        .install()
+        .unwrap();
+
+    let bug = eyre!("index out of bounds").tag(ErrorTag::Bug);
+    let handler = bug.handler().downcast_ref::<color_eyre::Handler>().unwrap();
+    assert_eq!(handler.tags(), &[ErrorTag::Bug]);
+
+    let report = Err::<(), _>(eyre!("missing argument `--config`"))
+        .tag(ErrorTag::UserError)
+        .tag(ErrorTag::UserError)
+        .suggestion("pass `--config <path>`")
+        .unwrap_err();
+
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    assert_eq!(handler.tags(), &[ErrorTag::UserError]);
+
+    let output = format!("{:?}", report);
+    assert!(output.contains("missing argument `--config`"));
+    assert!(output.contains("pass `--config <path>`"));
+    assert!(!output.contains("Location:"));
+    assert!(!output.contains(" BACKTRACE ━"));
+    assert!(!output.contains("RUST_BACKTRACE"));
+    assert_eq!(ErrorTag::custom("io").to_string(), "io");
+}
@@ -0,0 +1,75 @@
+use color_eyre::{eyre::eyre, Section};
+use std::fmt;
+
+#[derive(Debug)]
+struct StrError(&'static str);
+
+impl fmt::Display for StrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for StrError {}
+
+fn errors_section(output: &str) -> &str {
+    let start = output.find("Errors:").expect(output);
+    let end = output[start..]
+        .find("\n\n")
+        .map_or(output.len(), |end| start + end);
+    &output[start..end]
+}
+
+#[test]
+fn error_tree() {
+    color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::plain())
+        .display_env_section(false)
+        .install()
+        .unwrap();
+
+    let download = eyre!("connection refused").wrap_err("failed to download `serde`");
+    let build = eyre!("no space left on device")
+        .wrap_err("failed to build `syn`")
+        .report(eyre!("failed to write `libsyn.rlib`"))
+        .error(StrError("failed to write\nthe build log"));
+
+    let report = eyre!("failed to install dependencies")
+        .report(download)
+        .report(build);
+    let output = format!("{:?}", report);
+
+    assert_eq!(
+        errors_section(&output),
+        "Errors:
+   ├─ 0: failed to download `serde`
+   │  1: connection refused
+   └─ 0: failed to build `syn`
+      1: no space left on device
+      ├─ 0: failed to write `libsyn.rlib`
+      └─ 0: failed to write
+            the build log",
+        "{}",
+        output
+    );
+
+    // A single error without errors of its own keeps the flat section
+    let report = eyre!("failed to install dependencies").error(StrError("disk full"));
+    let output = format!("{:?}", report);
+    assert!(!output.contains("Errors:"), "{}", output);
+    assert!(output.contains("Error:\n   0: disk full"), "{}", output);
+
+    let result: Result<(), _> = Err(StrError("disk full"));
+    let output = format!(
+        "{:?}",
+        result
+            .with_report(|| eyre!("failed to clean up"))
+            .error(StrError("failed to unlock"))
+            .unwrap_err()
+    );
+    assert!(
+        errors_section(&output).ends_with("├─ 0: failed to clean up\n   └─ 0: failed to unlock"),
+        "{}",
+        output
+    );
+}
@@ -0,0 +1,25 @@
+use color_eyre::config::{HookBuilder, Theme};
+use color_eyre::eyre::eyre;
+
+#[test]
+fn fields_rendered_as_table() {
+    HookBuilder::default()
+        .theme(Theme::plain())
+        .display_env_section(false)
+        .install()
+        .unwrap();
+
+    let report = eyre!("failed to load user")
+        .wrap_err_field("user_id", 42)
+        .wrap_err_field("path", "/etc/users");
+
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    let fields: Vec<_> = handler.fields().collect();
+    assert_eq!(fields, [("user_id", "42"), ("path", "/etc/users")]);
+
+    let output = format!("{:?}", report);
+    assert!(output.contains("Fields:\n   user_id = 42\n   path    = /etc/users"));
+}
@@ -0,0 +1,32 @@
+use color_eyre::{
+    eyre,
+    formatter::{Formatted, Json, Plain, Pretty},
+    Section,
+};
+use eyre::eyre;
+
+#[test]
+fn formatter_can_be_selected_globally_and_per_call() {
+    color_eyre::config::HookBuilder::default()
+        .formatter(Plain)
+        .install()
+        .unwrap();
+
+    let report = eyre!("disk full")
+        .wrap_err("failed to write \"config.toml\"")
+        .suggestion("free up some space");
+
+    let plain = format!("{:?}", report);
+    assert!(plain.contains("failed to write \"config.toml\""));
+    assert!(plain.contains("Suggestion: free up some space"));
+    assert!(!plain.contains('\u{1b}'));
+
+    let pretty = Formatted::new(&report, &Pretty).to_string();
+    assert!(pretty.contains('\u{1b}'));
+
+    let json = Formatted::new(&report, &Json).to_string();
+    assert!(
+        json.starts_with(r#"{"error":"failed to write \"config.toml\"","causes":["disk full"]"#)
+    );
+    assert!(json.ends_with(r#","suggestions":["free up some space"]}"#));
+}
@@ -0,0 +1,23 @@
+use color_eyre::{config::Theme, eyre, Section};
+use eyre::eyre;
+
+#[test]
+fn continuation_lines_are_aligned() {
+    color_eyre::config::HookBuilder::default()
+        .theme(Theme::new())
+        .display_env_section(false)
+        .hanging_indent(true)
+        .continuation_prefix("| ")
+        .install()
+        .unwrap();
+
+    let report = eyre!("unexpected token\nat line 3")
+        .wrap_err("failed to parse config")
+        .suggestion("check the syntax\nor regenerate the file")
+        .note("single line");
+
+    let output = format!("{:?}", report);
+    assert!(output.contains("   1: unexpected token\n      | at line 3"));
+    assert!(output.contains("Suggestion: check the syntax\n            | or regenerate the file"));
+    assert!(output.contains("Note: single line"));
+}
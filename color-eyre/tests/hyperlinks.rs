@@ -0,0 +1,41 @@
+use color_eyre::config::{HookBuilder, HyperlinkMode, Theme};
+use color_eyre::eyre::eyre;
+
+#[test]
+fn hyperlinks() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    let builder = HookBuilder::default()
+        .theme(Theme::plain())
+        .hyperlinks(HyperlinkMode::Always)
+        .hyperlink_url("vscode://file{path}:{line}");
+
+    #[cfg(feature = "issue-url")]
+    let builder = builder.issue_url("https://github.com/eyre-rs/eyre/issues/new");
+
+    builder.install().unwrap();
+
+    let report = format!("{:?}", eyre!("error occured"));
+    let (_, backtrace) = report.split_once(" BACKTRACE ").unwrap();
+
+    let link = backtrace
+        .lines()
+        .find(|line| line.contains("tests/hyperlinks.rs"))
+        .unwrap_or_else(|| panic!("{}", backtrace));
+    assert!(
+        link.trim_start().starts_with("at \x1b]8;;vscode://file/"),
+        "{:?}",
+        link
+    );
+    assert!(link.contains("/tests/hyperlinks.rs:"), "{:?}", link);
+    assert!(link.ends_with("\x1b]8;;\x1b\\"), "{:?}", link);
+
+    #[cfg(feature = "issue-url")]
+    assert!(
+        report.contains(
+            "Consider reporting this error using this URL: \x1b]8;;https://github.com/eyre-rs/eyre/issues/new?"
+        ),
+        "{}",
+        report
+    );
+}
@@ -0,0 +1,11 @@
+use color_eyre::eyre;
+use eyre::eyre;
+
+#[test]
+fn installing_both_hooks_separately_succeeds() {
+    color_eyre::install_panic_hook().unwrap();
+    color_eyre::install_eyre_hook().unwrap();
+
+    let report = eyre!("error occured");
+    assert!(report.handler().is::<color_eyre::Handler>());
+}
@@ -0,0 +1,21 @@
+use color_eyre::eyre;
+use eyre::eyre;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static CUSTOM_HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+
+#[test]
+fn eyre_hook_leaves_panic_hook_alone() {
+    std::panic::set_hook(Box::new(|_| {
+        CUSTOM_HOOK_CALLED.store(true, Ordering::SeqCst);
+    }));
+
+    color_eyre::install_eyre_hook().unwrap();
+
+    let report = eyre!("error occured");
+    assert!(report.handler().is::<color_eyre::Handler>());
+
+    let result = std::panic::catch_unwind(|| panic!("boom"));
+    assert!(result.is_err());
+    assert!(CUSTOM_HOOK_CALLED.load(Ordering::SeqCst));
+}
@@ -0,0 +1,26 @@
+#[cfg(feature = "issue-url")]
+#[test]
+fn issue_report_filter_sees_sections_and_tags() {
+    use color_eyre::{eyre, ErrorTag, Section};
+    use eyre::eyre;
+
+    color_eyre::config::HookBuilder::default()
+        .issue_url("https://github.com/eyre-rs/eyre/issues/new")
+        .issue_report_filter(|report| {
+            !report.has_suggestion() && !report.tags().contains(&ErrorTag::Transient)
+        })
+        .install()
+        .unwrap();
+
+    let report = format!("{:?}", eyre!("error occured"));
+    assert!(report.contains("Consider reporting this error"));
+
+    let report = format!(
+        "{:?}",
+        eyre!("error occured").suggestion("try turning it off and on again")
+    );
+    assert!(!report.contains("Consider reporting this error"));
+
+    let report = format!("{:?}", eyre!("error occured").tag(ErrorTag::Transient));
+    assert!(!report.contains("Consider reporting this error"));
+}
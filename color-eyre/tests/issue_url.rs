@@ -0,0 +1,24 @@
+#[cfg(feature = "issue-url")]
+#[test]
+fn issue_url_on_demand() {
+    use color_eyre::eyre;
+    use eyre::eyre;
+
+    color_eyre::config::HookBuilder::default()
+        .issue_url("https://github.com/eyre-rs/eyre/issues/new")
+        .add_issue_metadata("version", "0.1.0")
+        .issue_filter(|_| false)
+        .install()
+        .unwrap();
+
+    let report = eyre!("error occured");
+
+    let url = color_eyre::issue_url(&report).unwrap();
+    assert!(url.starts_with("https://github.com/eyre-rs/eyre/issues/new?title="));
+    assert!(url.contains("error+occured"));
+    assert!(url.contains("version"));
+
+    // The filter still applies to the printed report.
+    let report = format!("{:?}", report);
+    assert!(!report.contains("Consider reporting this error"));
+}
@@ -0,0 +1,31 @@
+#[cfg(feature = "issue-url")]
+#[test]
+fn issue_url_template() {
+    use color_eyre::{eyre, issue_template};
+    use eyre::eyre;
+
+    color_eyre::config::HookBuilder::default()
+        .issue_url("https://gitlab.com/eyre-rs/eyre/-/issues/new")
+        .add_issue_metadata("version", "0.1.0")
+        .issue_url_template(|data| {
+            // check the built-in templates against the same data
+            let jira = issue_template::jira(data);
+            assert!(jira.contains("?summary=%3Cautogenerated-issue%3E&description="));
+
+            issue_template::gitlab(data)
+        })
+        .install()
+        .unwrap();
+
+    let report = eyre!("error occured");
+
+    let url = color_eyre::issue_url(&report).unwrap();
+    assert!(url.starts_with(
+        "https://gitlab.com/eyre-rs/eyre/-/issues/new?issue%5Btitle%5D=%3Cautogenerated-issue%3E&issue%5Bdescription%5D="
+    ));
+    assert!(url.contains("error+occured"));
+    assert!(url.contains("version"));
+
+    let report = format!("{:?}", report);
+    assert!(report.contains(&url));
+}
@@ -0,0 +1,41 @@
+use color_eyre::config::{HookBuilder, Theme};
+use color_eyre::eyre::{eyre, MainResult};
+use std::env;
+use std::process::{Command, Termination};
+
+#[test]
+fn main_result() {
+    let builder = HookBuilder::default()
+        .theme(Theme::plain())
+        .display_env_section(false)
+        .capture_span_trace_by_default(false);
+    #[cfg(feature = "track-caller")]
+    let builder = builder.display_location_section(false);
+    builder.install().unwrap();
+
+    // Re-run this test in a child process, which only reports a failed main
+    if let Some(code) = env::var_os("COLOR_EYRE_TEST_MAIN_RESULT") {
+        let report = match code.to_str().unwrap() {
+            "code" => eyre!(code = "E0425", "oh no!"),
+            _ => eyre!("oh no!"),
+        };
+        let _ = MainResult::from(report).report();
+        return;
+    }
+
+    for (code, expected) in [
+        ("none", "Error:\n   0: oh no!"),
+        ("code", "Error[E0425]:\n   0: oh no!"),
+    ] {
+        let output = Command::new(env::current_exe().unwrap())
+            .args(["main_result", "--exact", "--nocapture"])
+            .env("COLOR_EYRE_TEST_MAIN_RESULT", code)
+            .env("RUST_LIB_BACKTRACE", "0")
+            .output()
+            .unwrap();
+
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains(expected), "{}", stderr);
+        assert!(!stderr.contains("Error: "), "{}", stderr);
+    }
+}
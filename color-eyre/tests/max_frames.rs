@@ -0,0 +1,34 @@
+use color_eyre::eyre::{eyre, Report};
+
+#[inline(never)]
+fn recurse(depth: usize) -> Report {
+    if depth == 0 {
+        return eyre!("recursed too deep");
+    }
+
+    recurse(depth - 1)
+}
+
+#[test]
+fn max_frames() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::plain())
+        .collapse_recursion(false)
+        .max_frames(10)
+        .max_frame_name_length(16)
+        .install()
+        .unwrap();
+
+    let report = format!("{:?}", recurse(50));
+    let (_, backtrace) = report.split_once(" BACKTRACE ").unwrap();
+    assert!(backtrace.contains(" more frames ⋮"), "{}", backtrace);
+    assert!(!backtrace.contains("max_frames::recurse"), "{}", backtrace);
+    assert_eq!(
+        backtrace.matches("max_fram…recurse").count(),
+        10,
+        "{}",
+        backtrace
+    );
+}
@@ -0,0 +1,59 @@
+use color_eyre::config::{HookBuilder, HyperlinkMode};
+use color_eyre::eyre::eyre;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn assert_normalized(output: &str) {
+    assert!(!output.contains('\u{1b}'), "{}", output);
+    assert!(output.contains("normalized_output.rs:LL"), "{}", output);
+    assert!(!output.contains("normalized_output.rs:1"), "{}", output);
+
+    let cwd = std::env::current_dir().unwrap();
+    assert!(!output.contains(&*cwd.to_string_lossy()), "{}", output);
+
+    for (index, _) in output.match_indices("::h") {
+        let hash = output[index + 3..].get(..16).unwrap_or("");
+        assert!(!hash.chars().all(|c| c.is_ascii_hexdigit()), "{}", output);
+    }
+}
+
+#[test]
+fn normalized_output() {
+    let buffer = SharedBuffer::default();
+
+    HookBuilder::default()
+        .normalized_output(true)
+        .hyperlinks(HyperlinkMode::Always)
+        .capture_backtrace_by_default(true)
+        .panic_writer(buffer.clone())
+        .install()
+        .unwrap();
+
+    let report = eyre!("the reactor core melted");
+    let output = format!("{:?}", report);
+    assert!(
+        output.starts_with("\n   0: the reactor core melted"),
+        "{}",
+        output
+    );
+    assert_normalized(&output);
+
+    let _ = std::panic::catch_unwind(|| panic!("the reactor core melted"));
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("the reactor core melted"), "{}", output);
+    assert_normalized(&output);
+}
@@ -0,0 +1,33 @@
+use color_eyre::eyre::{eyre, Report};
+
+#[inline(never)]
+fn fail() -> Report {
+    eyre!("oh no!")
+}
+
+#[test]
+fn only_crate_frames() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::plain())
+        .only_crate_frames(true)
+        .install()
+        .unwrap();
+
+    let report = format!("{:?}", fail());
+    let (_, backtrace) = report.split_once(" BACKTRACE ").unwrap();
+    assert!(
+        backtrace.contains("only_crate_frames::fail"),
+        "{}",
+        backtrace
+    );
+    assert!(
+        backtrace.contains("only_crate_frames::only_crate_frames"),
+        "{}",
+        backtrace
+    );
+    assert!(backtrace.contains("frames hidden"), "{}", backtrace);
+    assert!(!backtrace.contains("core::"), "{}", backtrace);
+    assert!(!backtrace.contains("test::"), "{}", backtrace);
+}
@@ -0,0 +1,88 @@
+#[cfg(feature = "opentelemetry")]
+#[test]
+fn reports_are_recorded_as_exception_events() {
+    use color_eyre::eyre::eyre;
+    use opentelemetry::trace::{mark_span_as_active, Span, SpanContext, Status};
+    use opentelemetry::KeyValue;
+    use std::borrow::Cow;
+    use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
+
+    type Events = Arc<Mutex<Vec<(String, Vec<KeyValue>)>>>;
+
+    struct RecordingSpan(Events);
+
+    impl Span for RecordingSpan {
+        fn add_event_with_timestamp<T>(
+            &mut self,
+            name: T,
+            _timestamp: SystemTime,
+            attributes: Vec<KeyValue>,
+        ) where
+            T: Into<Cow<'static, str>>,
+        {
+            self.0
+                .lock()
+                .unwrap()
+                .push((name.into().into_owned(), attributes));
+        }
+
+        fn span_context(&self) -> &SpanContext {
+            &SpanContext::NONE
+        }
+
+        fn is_recording(&self) -> bool {
+            true
+        }
+
+        fn set_attribute(&mut self, _attribute: KeyValue) {}
+
+        fn set_status(&mut self, _status: Status) {}
+
+        fn update_name<T>(&mut self, _new_name: T)
+        where
+            T: Into<Cow<'static, str>>,
+        {
+        }
+
+        fn end_with_timestamp(&mut self, _timestamp: SystemTime) {}
+    }
+
+    color_eyre::config::HookBuilder::default()
+        .install()
+        .unwrap();
+
+    let events = Events::default();
+    let _guard = mark_span_as_active(RecordingSpan(events.clone()));
+
+    // reports which are never printed aren't recorded
+    let _ = eyre!("disk full").wrap_err("failed to save");
+    assert!(events.lock().unwrap().is_empty());
+
+    let report = eyre!("disk full").wrap_err("failed to save");
+    let rendered = format!("{:?}", report);
+    // printing a report again must not record it a second time
+    let _ = format!("{:?}", report);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+
+    let (name, attributes) = &events[0];
+    assert_eq!(name, "exception");
+
+    let attribute = |key: &str| {
+        attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| kv.value.as_str().into_owned())
+            .unwrap()
+    };
+    assert_eq!(attribute("exception.type"), "eyre::Report");
+    assert_eq!(attribute("exception.message"), "failed to save");
+
+    let stacktrace = attribute("exception.stacktrace");
+    assert!(stacktrace.contains("failed to save"));
+    assert!(stacktrace.contains("disk full"));
+    assert!(!stacktrace.contains('\u{1b}'));
+    assert!(rendered.contains('\u{1b}'));
+}
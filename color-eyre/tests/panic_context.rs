@@ -0,0 +1,43 @@
+use color_eyre::section::{PanicContext, PanicMessage};
+use std::fmt;
+use std::sync::Mutex;
+
+#[allow(deprecated)]
+type PanicInfo<'a> = std::panic::PanicInfo<'a>;
+
+static SEEN_THREAD: Mutex<Option<String>> = Mutex::new(None);
+
+struct RecordingMessage;
+
+impl PanicMessage for RecordingMessage {
+    fn display(&self, _pi: &PanicInfo<'_>, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        unreachable!("display_with_context is overridden")
+    }
+
+    fn display_with_context(
+        &self,
+        _pi: &PanicInfo<'_>,
+        context: &PanicContext<'_>,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        *SEEN_THREAD.lock().unwrap() = context.thread_name.map(String::from);
+        writeln!(f, "custom panic message")
+    }
+}
+
+#[test]
+fn panic_message_receives_context() {
+    color_eyre::config::HookBuilder::default()
+        .panic_message(RecordingMessage)
+        .install()
+        .unwrap();
+
+    let result = std::thread::Builder::new()
+        .name("worker".into())
+        .spawn(|| panic!("oh no"))
+        .unwrap()
+        .join();
+
+    assert!(result.is_err());
+    assert_eq!(SEEN_THREAD.lock().unwrap().as_deref(), Some("worker"));
+}
@@ -0,0 +1,33 @@
+use color_eyre::config::{HookBuilder, Theme};
+use std::io;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn panic_report_written_to_writer() {
+    let buffer = SharedBuffer::default();
+
+    HookBuilder::default()
+        .theme(Theme::plain())
+        .panic_writer(buffer.clone())
+        .install()
+        .unwrap();
+
+    let _ = std::panic::catch_unwind(|| panic!("the reactor core melted"));
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("The application panicked (crashed)."));
+    assert!(output.contains("the reactor core melted"));
+}
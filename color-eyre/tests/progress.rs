@@ -0,0 +1,141 @@
+#![cfg(feature = "indicatif")]
+
+use color_eyre::{eyre::eyre, progress::ReportExt};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle, TermLike};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+fn push(log: &Log, entry: String) -> io::Result<()> {
+    log.lock().unwrap().push(entry);
+    Ok(())
+}
+
+/// A terminal recording what indicatif draws on it
+#[derive(Debug)]
+struct Terminal(Log);
+
+impl TermLike for Terminal {
+    fn width(&self) -> u16 {
+        80
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        push(&self.0, format!("up {}", n))
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        push(&self.0, format!("down {}", n))
+    }
+
+    fn move_cursor_right(&self, _: usize) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn move_cursor_left(&self, _: usize) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        push(&self.0, format!("draw {}", s))
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        push(&self.0, format!("draw {}", s))
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        push(&self.0, "clear".into())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Records when the panic report is written and flushed
+struct Writer(Log);
+
+impl io::Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        push(&self.0, "write".into())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        push(&self.0, "report".into())
+    }
+}
+
+#[test]
+fn progress_suspender() {
+    let log = Log::default();
+    let progress = MultiProgress::with_draw_target(ProgressDrawTarget::term_like(Box::new(
+        Terminal(log.clone()),
+    )));
+    let bar = progress.add(ProgressBar::new(10));
+    bar.set_style(ProgressStyle::with_template("{msg}").unwrap());
+    bar.set_message("copying files");
+    bar.tick();
+    assert!(
+        log.lock()
+            .unwrap()
+            .iter()
+            .any(|entry| entry.contains("copying files")),
+        "{:?}",
+        log
+    );
+
+    color_eyre::config::HookBuilder::default()
+        .with_progress_suspender(progress)
+        .panic_writer(Writer(log.clone()))
+        .install()
+        .unwrap();
+
+    // indicatif doesn't draw on a panicking thread, but bars updated from
+    // other threads must not be drawn into the middle of the panic report
+    let done = Arc::new(AtomicBool::new(false));
+    let ticker = std::thread::spawn({
+        let bar = bar.clone();
+        let done = done.clone();
+        move || {
+            while !done.load(Ordering::SeqCst) {
+                bar.inc(1);
+                bar.tick();
+            }
+        }
+    });
+    let _ = std::panic::catch_unwind(|| panic!("the copy failed"));
+    done.store(true, Ordering::SeqCst);
+    ticker.join().unwrap();
+
+    let entries = log.lock().unwrap().clone();
+    let first_write = entries
+        .iter()
+        .position(|entry| entry.starts_with("write"))
+        .unwrap();
+    let report = entries.iter().position(|entry| entry == "report").unwrap();
+    assert!(
+        entries[first_write..report]
+            .iter()
+            .all(|entry| entry.starts_with("write")),
+        "{:?}",
+        entries
+    );
+
+    let drawn = log.lock().unwrap().len();
+    eyre!("the copy failed").eprint();
+    let entries = log.lock().unwrap()[drawn..].to_vec();
+    assert!(
+        entries.iter().any(|entry| entry == "clear"),
+        "{:?}",
+        entries
+    );
+    assert!(
+        entries.iter().any(|entry| entry.contains("copying files")),
+        "{:?}",
+        entries
+    );
+}
@@ -0,0 +1,49 @@
+use color_eyre::eyre::eyre;
+use std::thread;
+
+#[test]
+fn collects_reports_from_threads() {
+    color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::new())
+        .display_env_section(false)
+        .install()
+        .unwrap();
+
+    let (sink, collector) = color_eyre::sink::channel();
+
+    sink.send(eyre!("early failure"));
+    let drained = collector.drain();
+    assert_eq!(drained.len(), 1);
+    assert_eq!(drained[0].origin(), None);
+
+    let workers: Vec<_> = (0..2)
+        .map(|n| {
+            let sink = sink.clone();
+            thread::spawn(move || {
+                sink.send_from(
+                    format!("worker {}", n),
+                    eyre!("disk full").wrap_err(format!("worker {} failed", n)),
+                );
+            })
+        })
+        .collect();
+    drop(sink);
+
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let report = collector.finish("some workers failed").unwrap_err();
+    let output = format!("{:?}", report);
+    assert!(output.contains("some workers failed"));
+    assert!(output.contains("[worker 0] worker 0 failed"));
+    assert!(output.contains("[worker 1] worker 1 failed"));
+    assert!(output.contains("disk full"));
+}
+
+#[test]
+fn finish_without_reports_is_ok() {
+    let (sink, collector) = color_eyre::sink::channel();
+    drop(sink);
+    assert!(collector.finish("unreachable").is_ok());
+}
@@ -0,0 +1,53 @@
+use color_eyre::eyre;
+use eyre::eyre;
+
+/// The numbers of the frames or spans listed in `section`, in print order
+fn frame_numbers(section: &str) -> Vec<usize> {
+    section
+        .lines()
+        .filter_map(|line| line.trim_start().split_once(": "))
+        .filter_map(|(n, _)| n.parse().ok())
+        .collect()
+}
+
+#[test]
+fn reversed_traces() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    #[cfg(feature = "capture-spantrace")]
+    {
+        use tracing_subscriber::prelude::*;
+
+        tracing_subscriber::registry()
+            .with(tracing_error::ErrorLayer::default())
+            .init();
+    }
+
+    let builder = color_eyre::config::HookBuilder::blank()
+        .theme(color_eyre::config::Theme::plain())
+        .reversed_stacktrace(true);
+    #[cfg(feature = "capture-spantrace")]
+    let builder = builder
+        .capture_span_trace_by_default(true)
+        .reversed_spantrace(true);
+    builder.install().unwrap();
+
+    let report = {
+        let _outer = tracing::info_span!("outer").entered();
+        let _inner = tracing::info_span!("inner").entered();
+        format!("{:?}", eyre!("error occured"))
+    };
+
+    let (_, backtrace) = report.split_once(" BACKTRACE ").unwrap();
+    let frames = frame_numbers(backtrace);
+    assert!(frames.len() > 1, "{}", report);
+    assert!(frames.windows(2).all(|w| w[0] > w[1]), "{:?}", frames);
+
+    #[cfg(feature = "capture-spantrace")]
+    {
+        let (_, spantrace) = report.split_once(" SPANTRACE ").unwrap();
+        let (spantrace, _) = spantrace.split_once(" BACKTRACE ").unwrap();
+        assert_eq!(frame_numbers(spantrace), [1, 0], "{}", report);
+        assert!(spantrace.find("outer").unwrap() < spantrace.find("inner").unwrap());
+    }
+}
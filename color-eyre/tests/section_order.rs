@@ -0,0 +1,28 @@
+use color_eyre::config::{HookBuilder, SectionKind, Theme};
+use color_eyre::{eyre::eyre, Section};
+
+#[test]
+fn section_order() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    HookBuilder::blank()
+        .theme(Theme::plain())
+        .section_order(&[SectionKind::Notes, SectionKind::Env, SectionKind::Notes])
+        .install()
+        .unwrap();
+
+    let report = eyre!("error occured")
+        .section("custom section")
+        .suggestion("try again");
+    let report = format!("{:?}", report);
+
+    let position = |needle: &str| {
+        report
+            .find(needle)
+            .unwrap_or_else(|| panic!("missing {:?} in {}", needle, report))
+    };
+
+    assert!(position("Suggestion: try again") < position("Run with"));
+    assert!(position("Run with") < position("custom section"));
+    assert!(position("custom section") < position(" BACKTRACE "));
+}
@@ -0,0 +1,32 @@
+use color_eyre::config::{HookBuilder, Theme};
+use color_eyre::eyre::{eyre, Severity};
+
+#[test]
+fn severity_rendered_in_header() {
+    HookBuilder::default()
+        .theme(Theme::plain())
+        .display_env_section(false)
+        .install()
+        .unwrap();
+
+    let report = eyre!("disk almost full");
+    let output = format!("{:?}", report);
+    assert!(output.starts_with("\n   0: disk almost full"));
+
+    let mut report = eyre!("disk almost full");
+    report.set_severity(Severity::Warning);
+    assert_eq!(report.severity(), Severity::Warning);
+
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    assert_eq!(handler.severity(), Severity::Warning);
+
+    let output = format!("{:?}", report);
+    assert!(output.starts_with("Warning:\n   0: disk almost full"));
+
+    report.set_severity(Severity::Fatal);
+    let output = format!("{:?}", report);
+    assert!(output.starts_with("Fatal:\n   0: disk almost full"));
+}
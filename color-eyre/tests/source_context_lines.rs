@@ -0,0 +1,29 @@
+use color_eyre::eyre;
+use eyre::eyre;
+
+#[test]
+fn source_context_lines() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "full");
+    std::env::remove_var("COLOR_EYRE_SOURCE");
+
+    color_eyre::config::HookBuilder::blank()
+        .theme(color_eyre::config::Theme::plain())
+        .display_source_snippets(true)
+        .source_context_lines(4)
+        .install()
+        .unwrap();
+
+    let line = line!() + 1;
+    let report = format!("{:?}", eyre!("error occured"));
+
+    // The active line is surrounded by 4 lines of context on either side
+    let active = format!("{:>8} > ", line);
+    let snippet = report
+        .lines()
+        .skip_while(|l| !l.contains(&active))
+        .collect::<Vec<_>>();
+    assert!(!snippet.is_empty(), "missing snippet in {}", report);
+    assert!(report.contains(&format!("{:>8} │ ", line - 4)));
+    assert!(report.contains(&format!("{:>8} │ ", line + 4)));
+    assert!(!report.contains(&format!("{:>8} │ ", line + 5)));
+}
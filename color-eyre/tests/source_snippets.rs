@@ -0,0 +1,23 @@
+use color_eyre::eyre;
+use eyre::eyre;
+
+#[test]
+fn source_snippets() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "full");
+    std::env::remove_var("COLOR_EYRE_SOURCE");
+
+    color_eyre::config::HookBuilder::blank()
+        .display_source_snippets(false)
+        .install()
+        .unwrap();
+
+    let report = format!("{:?}", eyre!("error occured"));
+    assert!(report.contains(" BACKTRACE ━"));
+    assert!(!report.contains(" │ "));
+
+    // The environment variable overrides the builder.
+    std::env::set_var("COLOR_EYRE_SOURCE", "1");
+
+    let report = format!("{:?}", eyre!("error occured"));
+    assert!(report.contains(" │ "));
+}
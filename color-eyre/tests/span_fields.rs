@@ -0,0 +1,32 @@
+#[cfg(feature = "capture-spantrace")]
+#[test]
+fn span_fields_are_rendered() {
+    use color_eyre::eyre::eyre;
+    use tracing_subscriber::prelude::*;
+
+    tracing_subscriber::registry()
+        .with(tracing_error::ErrorLayer::default())
+        .init();
+
+    color_eyre::config::HookBuilder::default()
+        .capture_span_fields(true)
+        .install()
+        .unwrap();
+
+    let report = {
+        let _request = tracing::info_span!("request", id = 42).entered();
+        let _query = tracing::info_span!("query", table = "users").entered();
+        eyre!("connection reset")
+    };
+
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    let fields: Vec<_> = handler.span_fields().collect();
+    assert_eq!(fields, [("query", "table=\"users\""), ("request", "id=42")]);
+
+    let report = format!("{:?}", report);
+    assert!(report.contains("Span context:"));
+    assert!(report.contains("table=\"users\""));
+}
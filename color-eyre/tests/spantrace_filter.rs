@@ -0,0 +1,41 @@
+#[cfg(feature = "capture-spantrace")]
+#[test]
+fn spantrace_filter_and_redaction() {
+    use color_eyre::{config::Theme, eyre::eyre};
+    use tracing_subscriber::prelude::*;
+
+    tracing_subscriber::registry()
+        .with(tracing_error::ErrorLayer::default())
+        .init();
+
+    color_eyre::config::HookBuilder::default()
+        .theme(Theme::plain())
+        .capture_span_fields(true)
+        .spantrace_filter(|metadata| metadata.name() != "pool")
+        .redact_span_fields(|name, _| name == "password")
+        .install()
+        .unwrap();
+
+    let report = {
+        let _login = tracing::info_span!("login", user = "alice", password = "hunter2").entered();
+        let _pool = tracing::info_span!("pool", size = 8).entered();
+        eyre!("connection reset")
+    };
+
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    let fields: Vec<_> = handler.span_fields().collect();
+    assert_eq!(fields, [("login", "user=\"alice\" password=<redacted>")]);
+
+    let report = format!("{:?}", report);
+    assert!(report.contains("SPANTRACE"), "{}", report);
+    assert!(
+        report.contains("spantrace_filter::login with user=\"alice\" password=<redacted>"),
+        "{}",
+        report
+    );
+    assert!(!report.contains("hunter2"), "{}", report);
+    assert!(!report.contains("pool"), "{}", report);
+}
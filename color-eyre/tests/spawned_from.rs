@@ -0,0 +1,72 @@
+#![cfg(feature = "tokio")]
+
+use color_eyre::eyre::{eyre, Report};
+use tracing::Instrument;
+use tracing_subscriber::prelude::*;
+
+#[test]
+fn spawned_from() {
+    tracing_subscriber::registry()
+        .with(tracing_error::ErrorLayer::default())
+        .init();
+
+    color_eyre::config::HookBuilder::default()
+        .theme(color_eyre::config::Theme::plain())
+        .install()
+        .unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    // Spans entered inside a spawned task have no parent, so the span trace
+    // captured in the innermost task only contains the `query` span
+    let report: Report = runtime.block_on(
+        async {
+            color_eyre::task::spawn(async {
+                let worker = tracing::info_span!("worker");
+                async {
+                    color_eyre::task::spawn(async {
+                        let query = tracing::info_span!("query");
+                        async { eyre!("connection reset") }.instrument(query).await
+                    })
+                    .await
+                    .unwrap()
+                }
+                .instrument(worker)
+                .await
+            })
+            .await
+            .unwrap()
+        }
+        .instrument(tracing::info_span!("request", id = 42)),
+    );
+
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    assert_eq!(handler.spawned_from().len(), 2);
+
+    let output = format!("{:?}", report);
+    let (span_trace, spawned_from) = output.split_once(" SPAWNED FROM ").unwrap();
+    assert!(span_trace.contains(" SPANTRACE "), "{}", output);
+    assert!(span_trace.contains("spawned_from::query"), "{}", output);
+    assert!(!span_trace.contains("spawned_from::worker"), "{}", output);
+
+    let (worker, request) = spawned_from.split_once(" SPAWNED FROM ").unwrap();
+    assert!(worker.contains("spawned_from::worker"), "{}", output);
+    assert!(!worker.contains("spawned_from::request"), "{}", output);
+    assert!(
+        request.contains("spawned_from::request with id=42"),
+        "{}",
+        output
+    );
+
+    let report = eyre!("outside of any task");
+    let handler = report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .unwrap();
+    assert!(handler.spawned_from().is_empty());
+}
@@ -0,0 +1,23 @@
+use color_eyre::config::{HookBuilder, Theme};
+use color_eyre::eyre::eyre;
+use color_eyre::Section;
+
+#[test]
+fn plain_theme_has_no_ansi_codes() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    HookBuilder::default()
+        .theme(Theme::plain())
+        .install()
+        .unwrap();
+
+    let report = eyre!("disk full")
+        .wrap_err("failed to save")
+        .note("the disk was full")
+        .suggestion("free up some space");
+
+    let output = format!("{:?}", report);
+    assert!(output.contains("failed to save"));
+    assert!(output.contains(" BACKTRACE ━"));
+    assert!(!output.contains('\u{1b}'));
+}
@@ -0,0 +1,35 @@
+use color_eyre::eyre;
+use color_eyre::throttle::ThrottleExt;
+use eyre::eyre;
+use std::time::Duration;
+
+#[test]
+fn repeated_reports_are_throttled() {
+    color_eyre::config::HookBuilder::default()
+        .throttle_repeated_reports(Duration::from_secs(3600))
+        .install()
+        .unwrap();
+
+    let first = eyre!("connection refused").throttled().to_string();
+    assert!(!first.contains("previous error repeated"));
+
+    let report = eyre!("connection refused");
+    let second = report.throttled();
+    assert_eq!(second.repeats(), Some(1));
+    assert!(second.to_string().contains("connection refused"));
+    assert!(second
+        .to_string()
+        .contains("(previous error repeated 1 time)"));
+    assert!(!second.to_string().contains('\n'));
+
+    // Formatting a report doesn't count as printing it
+    let debug = format!("{:?}", report);
+    assert!(!debug.contains("previous error repeated"));
+    assert_eq!(format!("{:?}", report), debug);
+
+    let third = eyre!("connection refused").throttled().to_string();
+    assert!(third.contains("(previous error repeated 2 times)"));
+
+    let other = eyre!("permission denied").throttled().to_string();
+    assert!(!other.contains("previous error repeated"));
+}
@@ -0,0 +1,29 @@
+use color_eyre::config::{HookBuilder, Verbosity};
+use color_eyre::{eyre, Handler};
+use eyre::eyre;
+
+#[test]
+fn error_verbosity() {
+    std::env::remove_var("RUST_BACKTRACE");
+    std::env::remove_var("RUST_LIB_BACKTRACE");
+    std::env::remove_var("COLOR_EYRE_ERROR_VERBOSITY");
+
+    HookBuilder::default()
+        .panic_verbosity(Verbosity::Minimal)
+        .error_verbosity(Verbosity::Medium)
+        .install()
+        .unwrap();
+
+    let report = eyre!("error occured");
+    let handler = report.handler().downcast_ref::<Handler>().unwrap();
+    assert!(handler.backtrace().is_some());
+    assert!(format!("{:?}", report).contains(" BACKTRACE ━"));
+
+    // The dedicated environment variable overrides the configured verbosity.
+    std::env::set_var("COLOR_EYRE_ERROR_VERBOSITY", "minimal");
+
+    let report = eyre!("error occured");
+    let handler = report.handler().downcast_ref::<Handler>().unwrap();
+    assert!(handler.backtrace().is_none());
+    assert!(!format!("{:?}", report).contains(" BACKTRACE ━"));
+}
@@ -0,0 +1,29 @@
+use color_eyre::config::{set_verbosity, HookBuilder, Verbosity};
+use color_eyre::{eyre, Handler};
+use eyre::eyre;
+
+#[test]
+fn verbosity_from_occurrences() {
+    std::env::remove_var("RUST_BACKTRACE");
+    std::env::remove_var("RUST_LIB_BACKTRACE");
+    std::env::remove_var("COLOR_EYRE_ERROR_VERBOSITY");
+
+    assert_eq!(Verbosity::from_occurrences(0), Verbosity::Minimal);
+    assert_eq!(Verbosity::from_occurrences(1), Verbosity::Medium);
+    assert_eq!(Verbosity::from_occurrences(5), Verbosity::Full);
+
+    HookBuilder::default()
+        .verbosity_from_occurrences(0)
+        .install()
+        .unwrap();
+
+    let report = eyre!("error occured");
+    let handler = report.handler().downcast_ref::<Handler>().unwrap();
+    assert!(handler.backtrace().is_none());
+
+    set_verbosity(Verbosity::from_occurrences(1));
+
+    let report = eyre!("error occured");
+    let handler = report.handler().downcast_ref::<Handler>().unwrap();
+    assert!(handler.backtrace().is_some());
+}
@@ -87,16 +87,18 @@
 )]
 use once_cell::sync::OnceCell;
 use owo_colors::{style, Style};
+use std::borrow::Cow;
 use std::env;
 use std::fmt;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
+use tracing_core::Metadata;
 use tracing_error::SpanTrace;
 
 static THEME: OnceCell<Theme> = OnceCell::new();
 
 /// A struct that represents theme that is used by `color_spantrace`
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct Theme {
     file: Style,
     line_number: Style,
@@ -181,11 +183,16 @@ impl std::error::Error for InstallThemeError {}
 ///
 /// # Details
 ///
-/// This can only be set once and otherwise fails.
+/// This can only be set once. Setting the same theme again succeeds, setting
+/// a different theme fails.
 ///
-/// **Note:** `colorize` sets the global theme implicitly, if it was not set already. So calling `colorize` and then `set_theme` fails
+/// **Note:** `colorize` sets the global theme implicitly, if it was not set already. So calling `colorize` and then `set_theme` with anything but the default dark theme fails
 pub fn set_theme(theme: Theme) -> Result<(), InstallThemeError> {
-    THEME.set(theme).map_err(|_| InstallThemeError)
+    if *THEME.get_or_init(|| theme) == theme {
+        Ok(())
+    } else {
+        Err(InstallThemeError)
+    }
 }
 
 /// Display a [`SpanTrace`] with colors and source
@@ -206,13 +213,219 @@ pub fn set_theme(theme: Theme) -> Result<(), InstallThemeError> {
 ///
 /// [`SpanTrace`]: https://docs.rs/tracing-error/*/tracing_error/struct.SpanTrace.html
 pub fn colorize(span_trace: &SpanTrace) -> impl fmt::Display + '_ {
-    let theme = *THEME.get_or_init(Theme::dark);
-    ColorSpanTrace { span_trace, theme }
+    ColorSpanTrace::new(span_trace)
+}
+
+/// Display a [`SpanTrace`] with colors and source, starting with the outermost span
+///
+/// This works like [`colorize`], except that the spans are printed in reverse order, so the
+/// innermost span, which is usually the most relevant one, ends up at the bottom of the output.
+/// Spans keep the numbers they are displayed with by [`colorize`].
+///
+/// [`SpanTrace`]: https://docs.rs/tracing-error/*/tracing_error/struct.SpanTrace.html
+pub fn colorize_reversed(span_trace: &SpanTrace) -> impl fmt::Display + '_ {
+    ColorSpanTrace::new(span_trace).reversed(true)
 }
 
-struct ColorSpanTrace<'a> {
+/// A callback deciding whether a span is displayed, see [`ColorSpanTrace::span_filter`]
+pub type SpanFilter = dyn Fn(&Metadata<'static>) -> bool + Send + Sync;
+
+/// A callback deciding whether the value of a span field is redacted, see
+/// [`ColorSpanTrace::field_redactor`]
+pub type FieldRedactor = dyn Fn(&str, &str) -> bool + Send + Sync;
+
+/// A [`SpanTrace`] which can be displayed with colors and source
+///
+/// This is the type behind [`colorize`] and [`colorize_reversed`], and additionally allows
+/// skipping spans and redacting the values of span fields before they are displayed.
+///
+/// # Example
+///
+/// ```rust
+/// use color_spantrace::ColorSpanTrace;
+/// use tracing_error::SpanTrace;
+///
+/// let span_trace = SpanTrace::capture();
+/// let skip_hyper = |metadata: &tracing_core::Metadata<'static>| {
+///     !metadata.target().starts_with("hyper")
+/// };
+/// let redact_secrets = |name: &str, _value: &str| name.contains("password");
+///
+/// println!(
+///     "{}",
+///     ColorSpanTrace::new(&span_trace)
+///         .span_filter(&skip_hyper)
+///         .field_redactor(&redact_secrets)
+/// );
+/// ```
+///
+/// **Note:** this sets the global theme implicitly, if it was not set already, just like
+/// [`colorize`].
+///
+/// [`SpanTrace`]: https://docs.rs/tracing-error/*/tracing_error/struct.SpanTrace.html
+pub struct ColorSpanTrace<'a> {
     span_trace: &'a SpanTrace,
     theme: Theme,
+    reversed: bool,
+    title: &'a str,
+    span_filter: Option<&'a SpanFilter>,
+    field_redactor: Option<&'a FieldRedactor>,
+}
+
+impl<'a> ColorSpanTrace<'a> {
+    /// Wrap a [`SpanTrace`] for displaying it with the global theme
+    ///
+    /// [`SpanTrace`]: https://docs.rs/tracing-error/*/tracing_error/struct.SpanTrace.html
+    pub fn new(span_trace: &'a SpanTrace) -> Self {
+        ColorSpanTrace {
+            span_trace,
+            theme: *THEME.get_or_init(Theme::dark),
+            reversed: false,
+            title: "SPANTRACE",
+            span_filter: None,
+            field_redactor: None,
+        }
+    }
+
+    /// Print the outermost span first, like [`colorize_reversed`]
+    pub fn reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// Use `title` instead of `SPANTRACE` in the header line
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Only display the spans for which `filter` returns `true`
+    ///
+    /// Spans which are skipped still count towards the numbers of the remaining spans.
+    pub fn span_filter(mut self, filter: &'a SpanFilter) -> Self {
+        self.span_filter = Some(filter);
+        self
+    }
+
+    /// Replace the value of every span field for which `redactor` returns `true` with
+    /// `<redacted>`
+    ///
+    /// The redactor is called with the name and the formatted value of each field, see
+    /// [`redact_fields`].
+    pub fn field_redactor(mut self, redactor: &'a FieldRedactor) -> Self {
+        self.field_redactor = Some(redactor);
+        self
+    }
+
+    fn frame<'b>(
+        &self,
+        metadata: &'b Metadata<'static>,
+        fields: &'b str,
+    ) -> Option<(&'b Metadata<'static>, Cow<'b, str>)> {
+        if !self.span_filter.map_or(true, |filter| filter(metadata)) {
+            return None;
+        }
+
+        let fields = match self.field_redactor {
+            Some(redactor) => Cow::Owned(redact_fields(fields, redactor)),
+            None => Cow::Borrowed(fields),
+        };
+
+        Some((metadata, fields))
+    }
+}
+
+impl fmt::Debug for ColorSpanTrace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColorSpanTrace")
+            .field("span_trace", &self.span_trace)
+            .field("theme", &self.theme)
+            .field("reversed", &self.reversed)
+            .field("title", &self.title)
+            .field("span_filter", &self.span_filter.is_some())
+            .field("field_redactor", &self.field_redactor.is_some())
+            .finish()
+    }
+}
+
+/// Replace the values of the fields for which `redact` returns `true` with `<redacted>`
+///
+/// `fields` is expected to be formatted like the fields recorded by `tracing-subscriber`'s
+/// default field formatter, i.e. space separated `name=value` pairs. `redact` is called with
+/// the name and the formatted value of each field, string values include their quotes.
+/// Anything which is not a `name=value` pair, such as a `message` field, is kept as is.
+///
+/// # Example
+///
+/// ```rust
+/// let fields = r#"user="alice" password="hunter 2" attempt=3"#;
+/// let redacted = color_spantrace::redact_fields(fields, &|name, _| name == "password");
+/// assert_eq!(redacted, r#"user="alice" password=<redacted> attempt=3"#);
+/// ```
+pub fn redact_fields(fields: &str, redact: &FieldRedactor) -> String {
+    let mut out = String::with_capacity(fields.len());
+    let mut rest = fields;
+
+    while !rest.is_empty() {
+        let name_end = rest.find(['=', ' ']).unwrap_or(rest.len());
+        let (name, after_name) = rest.split_at(name_end);
+
+        if !after_name.starts_with('=') {
+            // Not a `name=value` pair, keep the token and its separator
+            let end = after_name.find(|c| c != ' ').unwrap_or(after_name.len());
+            out.push_str(name);
+            out.push_str(&after_name[..end]);
+            rest = &after_name[end..];
+            continue;
+        }
+
+        let value_len = field_value_len(&after_name[1..]);
+        let (value, after_value) = after_name[1..].split_at(value_len);
+
+        out.push_str(name);
+        out.push('=');
+        if redact(name, value) {
+            out.push_str("<redacted>");
+        } else {
+            out.push_str(value);
+        }
+
+        let end = after_value.find(|c| c != ' ').unwrap_or(after_value.len());
+        out.push_str(&after_value[..end]);
+        rest = &after_value[end..];
+    }
+
+    out
+}
+
+/// The length of the formatted field value at the start of `s`, which ends at the first space
+/// outside of quotes and brackets
+fn field_value_len(s: &str) -> usize {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in s.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ' ' if depth == 0 => return i,
+            _ => {}
+        }
+    }
+
+    s.len()
 }
 
 macro_rules! try_bool {
@@ -228,7 +441,7 @@ macro_rules! try_bool {
 }
 
 struct Frame<'a> {
-    metadata: &'a tracing_core::Metadata<'static>,
+    metadata: &'a Metadata<'static>,
     fields: &'a str,
     theme: Theme,
 }
@@ -347,30 +560,77 @@ impl Frame<'_> {
     }
 }
 
+impl ColorSpanTrace<'_> {
+    fn print_frame(&self, frame: &Frame<'_>, span: u32, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        frame.print(span, f)?;
+
+        if Verbosity::lib_from_env() == Verbosity::Full {
+            frame.print_source_if_avail(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Display for ColorSpanTrace<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut err = Ok(());
         let mut span = 0;
+        let mut printed = false;
+
+        writeln!(f, "{:━^80}\n", format!(" {} ", self.title))?;
+
+        if self.reversed {
+            // Spans can only be visited innermost first
+            let mut spans = Vec::new();
+            self.span_trace.with_spans(|metadata, fields| {
+                spans.push((metadata, fields.to_owned()));
+                true
+            });
+
+            for (span, (metadata, fields)) in spans.iter().enumerate().rev() {
+                let (metadata, fields) = match self.frame(metadata, fields) {
+                    Some(frame) => frame,
+                    None => continue,
+                };
+                let frame = Frame {
+                    metadata,
+                    fields: &fields,
+                    theme: self.theme,
+                };
+
+                if printed {
+                    writeln!(f)?;
+                }
+
+                self.print_frame(&frame, span as u32, f)?;
+                printed = true;
+            }
+
+            return Ok(());
+        }
 
-        writeln!(f, "{:━^80}\n", " SPANTRACE ")?;
         self.span_trace.with_spans(|metadata, fields| {
+            let index = span;
+            span += 1;
+
+            let (metadata, fields) = match self.frame(metadata, fields) {
+                Some(frame) => frame,
+                None => return true,
+            };
             let frame = Frame {
                 metadata,
-                fields,
+                fields: &fields,
                 theme: self.theme,
             };
 
-            if span > 0 {
+            if printed {
                 try_bool!(write!(f, "\n",), err);
             }
 
-            try_bool!(frame.print(span, f), err);
+            try_bool!(self.print_frame(&frame, index, f), err);
 
-            if Verbosity::lib_from_env() == Verbosity::Full {
-                try_bool!(frame.print_source_if_avail(f), err);
-            }
-
-            span += 1;
+            printed = true;
             true
         });
 
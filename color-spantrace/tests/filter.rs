@@ -0,0 +1,64 @@
+use color_spantrace::{ColorSpanTrace, Theme};
+use tracing::instrument;
+use tracing_core::Metadata;
+use tracing_error::{ErrorLayer, SpanTrace};
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+#[instrument]
+fn login(user: &str, password: &str) -> SpanTrace {
+    connect(3)
+}
+
+#[instrument(target = "noisy")]
+fn connect(attempt: u8) -> SpanTrace {
+    SpanTrace::capture()
+}
+
+#[test]
+fn filter_and_redact() {
+    Registry::default().with(ErrorLayer::default()).init();
+    color_spantrace::set_theme(Theme::new()).unwrap();
+
+    let span_trace = login("alice", "hunter 2");
+
+    let skip_noisy = |metadata: &Metadata<'static>| metadata.target() != "noisy";
+    let redact_password = |name: &str, _: &str| name == "password";
+
+    let output = ColorSpanTrace::new(&span_trace)
+        .span_filter(&skip_noisy)
+        .field_redactor(&redact_password)
+        .to_string();
+
+    assert!(!output.contains("connect"), "{}", output);
+    assert!(!output.contains("hunter"), "{}", output);
+    assert!(
+        output.contains(r#" 1: filter::login with user="alice" password=<redacted>"#),
+        "{}",
+        output
+    );
+
+    let output = color_spantrace::colorize(&span_trace).to_string();
+    assert!(
+        output.contains(" 0: noisy::connect with attempt=3"),
+        "{}",
+        output
+    );
+    assert!(output.contains(r#"password="hunter 2""#), "{}", output);
+}
+
+#[test]
+fn redact_fields() {
+    let redact = |name: &str, _: &str| name == "secret";
+
+    assert_eq!(
+        color_spantrace::redact_fields(
+            r#"config=Config { secret: "a b" } secret=Some("c d") n=1"#,
+            &redact
+        ),
+        r#"config=Config { secret: "a b" } secret=<redacted> n=1"#
+    );
+    assert_eq!(
+        color_spantrace::redact_fields(r#"a message secret="x\" y""#, &redact),
+        "a message secret=<redacted>"
+    );
+}
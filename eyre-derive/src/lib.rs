@@ -0,0 +1,441 @@
+//! Derive macro for error types that carry help text and error codes for
+//! their [`eyre::Report`]s.
+//!
+//! `#[derive(EyreDiagnostic)]` implements `Display`, `std::error::Error` and
+//! [`eyre::Diagnostic`] for an enum or struct from its `#[eyre(...)]`
+//! attributes:
+//!
+//! ```rust
+//! use eyre::Diagnostic;
+//! use eyre_derive::EyreDiagnostic;
+//!
+//! #[derive(Debug, EyreDiagnostic)]
+//! enum ConfigError {
+//!     #[eyre(
+//!         "config file `{path}` not found",
+//!         code = "CFG001",
+//!         help = "config files are looked up relative to the working directory",
+//!         suggestion = "run `init` to create `{path}`",
+//!     )]
+//!     NotFound { path: String },
+//!     #[eyre("failed to parse line {1}", code = "CFG002")]
+//!     Parse(#[source] std::num::ParseIntError, usize),
+//!     #[eyre("failed to read config")]
+//!     Io(#[from] std::io::Error),
+//! }
+//!
+//! let error = ConfigError::NotFound { path: "app.toml".into() };
+//! assert_eq!(error.to_string(), "config file `app.toml` not found");
+//! assert_eq!(error.suggestion().as_deref(), Some("run `init` to create `app.toml`"));
+//!
+//! let report = error.into_report();
+//! assert_eq!(report.code(), Some("CFG001"));
+//! ```
+//!
+//! # Attributes
+//!
+//! Every variant of an enum, or the struct itself, needs an `#[eyre(...)]`
+//! attribute starting with the format string of its `Display`
+//! implementation. Named fields can be referred to by name and tuple fields
+//! by index. The format string can be followed by
+//!
+//! - `code = "..."`, a stable error code, see `eyre::Report::with_code`
+//! - `help = "..."`, additional information that helps understand the error
+//! - `suggestion = "..."`, a suggestion on how to fix the error
+//!
+//! where `help` and `suggestion` are format strings as well.
+//!
+//! A field marked `#[source]`, `#[from]` or named `source` is returned from
+//! `Error::source`. `#[from]` additionally implements `From` for the field's
+//! type, and requires it to be the only field.
+//!
+//! The generated code refers to eyre as `::eyre`. If eyre is only available
+//! under a different path, e.g. via color-eyre, set it on the type with
+//! `#[eyre(crate = "color_eyre::eyre")]`.
+//!
+//! # Reporting
+//!
+//! The help text, suggestion and error code are handed to the report
+//! handler when the error is turned into a report via
+//! `eyre::Diagnostic::into_report`. Converting the error with `?` treats it
+//! like any other error.
+#![warn(missing_docs, rust_2018_idioms, unreachable_pub)]
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Path, Result,
+    Type,
+};
+
+/// Implement `Display`, `std::error::Error` and `eyre::Diagnostic` from
+/// `#[eyre(...)]` attributes, see the [crate level documentation](crate).
+#[proc_macro_derive(EyreDiagnostic, attributes(eyre, source, from))]
+pub fn derive_eyre_diagnostic(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The parsed contents of an `#[eyre(...)]` attribute
+#[derive(Default)]
+struct Attrs {
+    message: Option<LitStr>,
+    code: Option<LitStr>,
+    help: Option<LitStr>,
+    suggestion: Option<LitStr>,
+    krate: Option<Path>,
+}
+
+impl Attrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Attrs::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("eyre")) {
+            attr.parse_args_with(|input: syn::parse::ParseStream<'_>| {
+                if input.peek(LitStr) {
+                    parsed.message = Some(input.parse()?);
+                    if input.is_empty() {
+                        return Ok(());
+                    }
+                    input.parse::<syn::Token![,]>()?;
+                }
+
+                while !input.is_empty() {
+                    let key: Ident = input.parse()?;
+                    input.parse::<syn::Token![=]>()?;
+                    let value: LitStr = input.parse()?;
+                    match key.to_string().as_str() {
+                        "code" => parsed.code = Some(value),
+                        "help" => parsed.help = Some(value),
+                        "suggestion" => parsed.suggestion = Some(value),
+                        "crate" => parsed.krate = Some(value.parse()?),
+                        _ => {
+                            return Err(Error::new(
+                                key.span(),
+                                "expected one of `code`, `help`, `suggestion` or `crate`",
+                            ))
+                        }
+                    }
+                    if !input.is_empty() {
+                        input.parse::<syn::Token![,]>()?;
+                    }
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// A struct or a single enum variant
+struct Variant<'a> {
+    /// The path constructing this variant, `Self` or `Self::Variant`
+    path: TokenStream,
+    /// The pattern matching `self` and binding all fields
+    pattern: TokenStream,
+    attrs: Attrs,
+    fields: &'a Fields,
+    span: Span,
+}
+
+/// The field returned from `Error::source`
+struct Source<'a> {
+    binding: Ident,
+    ty: &'a Type,
+    from: bool,
+}
+
+impl Variant<'_> {
+    fn source(&self) -> Result<Option<Source<'_>>> {
+        let mut marked = None;
+        let mut named = None;
+
+        for (index, field) in self.fields.iter().enumerate() {
+            let has_attr = |name| field.attrs.iter().any(|attr| attr.path().is_ident(name));
+            let source = Source {
+                binding: binding(field.ident.as_ref(), index),
+                ty: &field.ty,
+                from: has_attr("from"),
+            };
+
+            if source.from && self.fields.len() != 1 {
+                return Err(Error::new(
+                    field.span(),
+                    "`#[from]` is only supported on the only field of a variant",
+                ));
+            }
+
+            if source.from || has_attr("source") {
+                if marked.is_some() {
+                    return Err(Error::new(field.span(), "duplicate source field"));
+                }
+                marked = Some(source);
+            } else if source.binding == "source" {
+                named = Some(source);
+            }
+        }
+
+        Ok(marked.or(named))
+    }
+}
+
+/// The name a field is bound to in the generated `match` arms
+fn binding(ident: Option<&Ident>, index: usize) -> Ident {
+    match ident {
+        Some(ident) => ident.clone(),
+        None => format_ident!("_{}", index),
+    }
+}
+
+fn pattern(path: TokenStream, fields: &Fields) -> TokenStream {
+    match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names),* })
+        }
+        Fields::Unnamed(fields) => {
+            let names = (0..fields.unnamed.len()).map(|index| binding(None, index));
+            quote!(#path ( #(#names),* ))
+        }
+        Fields::Unit => path,
+    }
+}
+
+/// Rewrite positional arguments such as `{0}` to the `_0` bindings of tuple
+/// fields, so that every argument is captured from the `match` arm
+fn format_string(lit: &LitStr) -> LitStr {
+    let value = lit.value();
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        out.push(c);
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            out.push(chars.next().unwrap());
+            continue;
+        }
+        if chars.peek().map_or(false, char::is_ascii_digit) {
+            out.push('_');
+        }
+    }
+
+    LitStr::new(&out, lit.span())
+}
+
+fn variants(input: &DeriveInput) -> Result<Vec<Variant<'_>>> {
+    match &input.data {
+        Data::Struct(data) => {
+            let attrs = Attrs::parse(&input.attrs)?;
+            Ok(vec![Variant {
+                path: quote!(Self),
+                pattern: pattern(quote!(Self), &data.fields),
+                attrs,
+                fields: &data.fields,
+                span: input.ident.span(),
+            }])
+        }
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                Ok(Variant {
+                    path: quote!(Self::#ident),
+                    pattern: pattern(quote!(Self::#ident), &variant.fields),
+                    attrs: Attrs::parse(&variant.attrs)?,
+                    fields: &variant.fields,
+                    span: variant.ident.span(),
+                })
+            })
+            .collect(),
+        Data::Union(_) => Err(Error::new(
+            input.ident.span(),
+            "`EyreDiagnostic` can't be derived for unions",
+        )),
+    }
+}
+
+/// A `match self` over all variants that evaluates to `Some(format!(..))`
+/// for the variants that set `attr`, or `None` if no variant does
+fn optional_strings(
+    variants: &[Variant<'_>],
+    attr: impl Fn(&Attrs) -> Option<&LitStr>,
+) -> Option<TokenStream> {
+    if variants
+        .iter()
+        .all(|variant| attr(&variant.attrs).is_none())
+    {
+        return None;
+    }
+
+    let arms = variants.iter().map(|variant| {
+        let pattern = &variant.pattern;
+        match attr(&variant.attrs) {
+            Some(lit) => {
+                let lit = format_string(lit);
+                quote! {
+                    #[allow(unused_variables)]
+                    #pattern => ::core::option::Option::Some(::std::format!(#lit)),
+                }
+            }
+            None => quote! {
+                #[allow(unused_variables)]
+                #pattern => ::core::option::Option::None,
+            },
+        }
+    });
+
+    Some(quote!(match self { #(#arms)* }))
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let container = match &input.data {
+        Data::Enum(_) => Attrs::parse(&input.attrs)?,
+        _ => Attrs::default(),
+    };
+    let variants = variants(input)?;
+    let krate = variants
+        .iter()
+        .map(|variant| &variant.attrs)
+        .chain(Some(&container))
+        .find_map(|attrs| attrs.krate.clone())
+        .unwrap_or_else(|| syn::parse_quote!(::eyre));
+
+    let ty = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let display_arms = variants
+        .iter()
+        .map(|variant| {
+            let pattern = &variant.pattern;
+            let message = variant.attrs.message.as_ref().ok_or_else(|| {
+                Error::new(
+                    variant.span,
+                    "missing `#[eyre(\"...\")]` attribute with the error message",
+                )
+            })?;
+            let message = format_string(message);
+            Ok(quote! {
+                #[allow(unused_variables)]
+                #pattern => ::core::write!(__formatter, #message),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut source_arms = Vec::new();
+    let mut from_impls = Vec::new();
+    for variant in &variants {
+        let pattern = &variant.pattern;
+        let Source {
+            binding,
+            ty: source_ty,
+            from,
+        } = match variant.source()? {
+            Some(source) => source,
+            None => continue,
+        };
+
+        source_arms.push(quote! {
+            #[allow(unused_variables)]
+            #pattern => ::core::option::Option::Some(
+                #binding as &(dyn ::std::error::Error + 'static)
+            ),
+        });
+
+        if from {
+            let path = &variant.path;
+            let construct = match variant.fields {
+                Fields::Named(_) => quote!(#path { #binding: source }),
+                _ => quote!(#path(source)),
+            };
+            from_impls.push(quote! {
+                impl #impl_generics ::core::convert::From<#source_ty> for #ty #ty_generics #where_clause {
+                    fn from(source: #source_ty) -> Self {
+                        #construct
+                    }
+                }
+            });
+        }
+    }
+
+    let source = if source_arms.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            fn source(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#source_arms)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        })
+    };
+
+    let code = if variants.iter().all(|variant| variant.attrs.code.is_none()) {
+        None
+    } else {
+        let arms = variants.iter().map(|variant| {
+            let pattern = &variant.pattern;
+            let code = match &variant.attrs.code {
+                Some(code) => quote!(::core::option::Option::Some(#code)),
+                None => quote!(::core::option::Option::None),
+            };
+            quote! {
+                #[allow(unused_variables)]
+                #pattern => #code,
+            }
+        });
+        Some(quote! {
+            fn code(&self) -> ::core::option::Option<&'static str> {
+                match self { #(#arms)* }
+            }
+        })
+    };
+
+    let help = optional_strings(&variants, |attrs| attrs.help.as_ref()).map(|body| {
+        quote! {
+            fn help(&self) -> ::core::option::Option<::std::string::String> {
+                #body
+            }
+        }
+    });
+
+    let suggestion = optional_strings(&variants, |attrs| attrs.suggestion.as_ref()).map(|body| {
+        quote! {
+            fn suggestion(&self) -> ::core::option::Option<::std::string::String> {
+                #body
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Display for #ty #ty_generics #where_clause {
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #(#display_arms)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::error::Error for #ty #ty_generics #where_clause {
+            #source
+        }
+
+        impl #impl_generics #krate::Diagnostic for #ty #ty_generics #where_clause {
+            #code
+            #help
+            #suggestion
+        }
+
+        #(#from_impls)*
+    })
+}
@@ -0,0 +1,110 @@
+use eyre::Diagnostic;
+use eyre_derive::EyreDiagnostic;
+use std::error::Error as StdError;
+use std::io;
+
+#[derive(Debug, EyreDiagnostic)]
+enum ConfigError {
+    #[eyre(
+        "config file `{path}` not found",
+        code = "CFG001",
+        help = "config files are looked up in `{{cwd}}`",
+        suggestion = "run `init` to create `{path}`"
+    )]
+    NotFound { path: String },
+    #[eyre("failed to parse line {1}: {0}", code = "CFG002")]
+    Parse(#[source] std::num::ParseIntError, usize),
+    #[eyre("failed to read config", help = "check the file permissions")]
+    Io(#[from] io::Error),
+    #[eyre("invalid {{braces}}")]
+    Braces,
+    #[eyre("failed to load `{name}`")]
+    Load { name: String, source: io::Error },
+}
+
+#[derive(Debug, EyreDiagnostic)]
+#[eyre("connection to {host} refused", code = "NET001")]
+struct ConnectionRefused {
+    host: String,
+}
+
+#[test]
+fn test_display() {
+    let error = ConfigError::NotFound {
+        path: "app.toml".into(),
+    };
+    assert_eq!(error.to_string(), "config file `app.toml` not found");
+
+    let error = ConfigError::Parse("x".parse::<u8>().unwrap_err(), 3);
+    assert_eq!(
+        error.to_string(),
+        "failed to parse line 3: invalid digit found in string"
+    );
+
+    assert_eq!(ConfigError::Braces.to_string(), "invalid {braces}");
+
+    let error = ConnectionRefused {
+        host: "localhost".into(),
+    };
+    assert_eq!(error.to_string(), "connection to localhost refused");
+}
+
+#[test]
+fn test_source_and_from() {
+    let error = ConfigError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert!(matches!(error, ConfigError::Io(_)));
+    assert_eq!(error.source().unwrap().to_string(), "oh no!");
+
+    let error = ConfigError::Parse("x".parse::<u8>().unwrap_err(), 3);
+    assert!(error.source().is_some());
+
+    let error = ConfigError::Load {
+        name: "app.toml".into(),
+        source: io::Error::new(io::ErrorKind::Other, "denied"),
+    };
+    assert_eq!(error.source().unwrap().to_string(), "denied");
+
+    assert!(ConfigError::Braces.source().is_none());
+}
+
+#[test]
+fn test_diagnostic() {
+    let error = ConfigError::NotFound {
+        path: "app.toml".into(),
+    };
+    assert_eq!(error.code(), Some("CFG001"));
+    assert_eq!(
+        error.help().as_deref(),
+        Some("config files are looked up in `{cwd}`")
+    );
+    assert_eq!(
+        error.suggestion().as_deref(),
+        Some("run `init` to create `app.toml`")
+    );
+
+    let error = ConfigError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!(error.code(), None);
+    assert_eq!(error.help().as_deref(), Some("check the file permissions"));
+    assert_eq!(error.suggestion(), None);
+
+    let error = ConnectionRefused {
+        host: "localhost".into(),
+    };
+    assert_eq!(error.code(), Some("NET001"));
+    assert_eq!(error.help(), None);
+}
+
+#[test]
+fn test_into_report() {
+    let report = ConfigError::NotFound {
+        path: "app.toml".into(),
+    }
+    .into_report()
+    .wrap_err("failed to start");
+
+    assert_eq!(report.code(), Some("CFG001"));
+    assert!(report.downcast_ref::<ConfigError>().is_some());
+
+    let report = eyre::Report::new(ConfigError::Braces);
+    assert_eq!(report.code(), None);
+}
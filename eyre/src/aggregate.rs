@@ -0,0 +1,85 @@
+use crate::Report;
+use core::fmt::{self, Debug, Display};
+use std::error::Error as StdError;
+
+/// An error made up of several independent failures
+///
+/// Each failure is stored as a `Report` along with the index of the item that
+/// produced it. Aggregates are created by [`Report::aggregate`], and with the
+/// `iter` feature by [`join_all`](crate::join_all) and
+/// [`IteratorExt::try_collect_all`](crate::IteratorExt::try_collect_all).
+/// Reports created from an `Aggregate` can be downcast back to it to access
+/// the individual failures:
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{eyre, Aggregate, Report};
+///
+/// let report = Report::aggregate(vec![eyre!("disk full"), eyre!("network down")]);
+///
+/// let aggregate = report.downcast_ref::<Aggregate>().unwrap();
+/// let messages: Vec<_> = aggregate
+///     .iter()
+///     .map(|(_, report)| report.to_string())
+///     .collect();
+/// assert_eq!(messages, ["disk full", "network down"]);
+/// ```
+pub struct Aggregate {
+    failures: Vec<(usize, Report)>,
+}
+
+impl Aggregate {
+    pub(crate) fn new(failures: Vec<(usize, Report)>) -> Self {
+        Aggregate { failures }
+    }
+
+    /// The number of failures
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Returns `true` if there are no failures
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Iterate over the failures along with the index of the item that
+    /// produced each of them
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Report)> {
+        self.failures.iter().map(|(index, report)| (*index, report))
+    }
+
+    /// Take ownership of the failures and the indices of the items that
+    /// produced them
+    pub fn into_failures(self) -> Vec<(usize, Report)> {
+        self.failures
+    }
+}
+
+impl Display for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} error{} occurred",
+            self.failures.len(),
+            if self.failures.len() == 1 { "" } else { "s" }
+        )?;
+
+        for (index, report) in &self.failures {
+            write!(f, "\n[{}] {:#}", index, report)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.failures.iter().map(|(index, report)| (index, report)))
+            .finish()
+    }
+}
+
+impl StdError for Aggregate {}
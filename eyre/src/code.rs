@@ -0,0 +1,100 @@
+//! Stable error codes for reports
+//!
+//! Libraries can attach a code such as `E0425` to the reports they return, so
+//! that handlers can display it prominently and external tooling can match on
+//! it without parsing messages, which may change between releases.
+//!
+//! # Example
+//!
+//! ```rust
+//! use eyre::{eyre, Result};
+//!
+//! fn resolve(name: &str) -> Result<()> {
+//!     Err(eyre!(code = "E0425", "cannot find value `{}` in this scope", name))
+//! }
+//!
+//! let report = resolve("x").unwrap_err().wrap_err("failed to compile");
+//! assert_eq!(report.code(), Some("E0425"));
+//! ```
+use crate::Report;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt;
+
+/// A stable error code attached to a [`Report`] via [`Report::with_code`].
+///
+/// `ErrorCode` is stored as a regular report extension, so it can also be
+/// read with [`Report::get`] and survives wrapping the report with additional
+/// context.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ErrorCode(Cow<'static, str>);
+
+impl ErrorCode {
+    /// Create a new error code.
+    pub fn new(code: impl Into<Cow<'static, str>>) -> Self {
+        ErrorCode(code.into())
+    }
+
+    /// The error code as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&'static str> for ErrorCode {
+    fn from(code: &'static str) -> Self {
+        ErrorCode::new(code)
+    }
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        ErrorCode::new(code)
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Report {
+    /// Attach a stable error code to this report.
+    ///
+    /// Handlers are informed about the code via
+    /// [`EyreHandler::set_code`](crate::EyreHandler::set_code), so they can
+    /// display it e.g. as `error[E0425]: ...`. Attaching a code replaces any
+    /// code attached before. The same can be achieved with
+    /// `eyre!(code = "E0425", "...")`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eyre::eyre;
+    ///
+    /// let report = eyre!("connection refused")
+    ///     .with_code("NET001")
+    ///     .wrap_err("failed to fetch index");
+    ///
+    /// assert_eq!(report.code(), Some("NET001"));
+    /// ```
+    pub fn with_code(mut self, code: impl Into<ErrorCode>) -> Self {
+        self.set_code(code);
+        self
+    }
+
+    /// Attach a stable error code to this report, see
+    /// [`with_code`](Report::with_code).
+    pub fn set_code(&mut self, code: impl Into<ErrorCode>) {
+        let code = code.into();
+        self.handler_mut().set_code(code.as_str());
+        self.insert(code);
+    }
+
+    /// The error code attached to this report via
+    /// [`with_code`](Report::with_code), if any.
+    pub fn code(&self) -> Option<&str> {
+        self.get::<ErrorCode>().map(ErrorCode::as_str)
+    }
+}
@@ -0,0 +1,426 @@
+//! Conversions between eyre and other error reporting libraries
+//!
+//! Converting a report from another library through its `std::error::Error`
+//! implementation loses everything that library tracks beyond the error
+//! message and its sources. The conversions in this module keep the original
+//! error around so that its metadata remains accessible from the `Report`,
+//! and [`IntoAnyhow`] does the same for `Report`s handed to `anyhow` users.
+use crate::Report;
+#[cfg(any(feature = "miette-compat", feature = "anyhow-compat"))]
+use core::fmt::Debug;
+use core::fmt::{self, Display};
+#[cfg(feature = "miette-compat")]
+use miette::Diagnostic;
+#[cfg(any(feature = "miette-compat", feature = "anyhow-compat"))]
+use std::error::Error as StdError;
+
+/// Convert errors from other reporting libraries into eyre [`Report`]s
+/// without losing their metadata.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "miette-compat")] {
+/// use eyre::compat::{self, IntoEyre};
+///
+/// fn parse() -> miette::Result<()> {
+///     Err(miette::miette!(help = "try quoting the value", "unexpected token"))
+/// }
+///
+/// let report = parse().into_eyre().unwrap_err();
+///
+/// let diagnostic = compat::diagnostic(&report).unwrap();
+/// assert_eq!(diagnostic.help().unwrap().to_string(), "try quoting the value");
+/// # }
+/// ```
+pub trait IntoEyre {
+    /// The type produced by the conversion.
+    type Output;
+
+    /// Performs the conversion.
+    fn into_eyre(self) -> Self::Output;
+}
+
+#[cfg(feature = "miette-compat")]
+impl IntoEyre for miette::Report {
+    type Output = Report;
+
+    #[cfg_attr(track_caller, track_caller)]
+    fn into_eyre(self) -> Report {
+        Report::new(DiagnosticError(self.into()))
+    }
+}
+
+#[cfg(feature = "miette-compat")]
+impl<T> IntoEyre for Result<T, miette::Report> {
+    type Output = Result<T, Report>;
+
+    #[cfg_attr(track_caller, track_caller)]
+    fn into_eyre(self) -> Result<T, Report> {
+        match self {
+            Ok(ok) => Ok(ok),
+            Err(report) => Err(report.into_eyre()),
+        }
+    }
+}
+
+/// Create a [`Report`] from a miette [`Diagnostic`], keeping its labels,
+/// source code and help text accessible via [`diagnostic`].
+#[cfg(feature = "miette-compat")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "miette-compat")))]
+#[cfg_attr(track_caller, track_caller)]
+pub fn from_diagnostic<D>(diagnostic: D) -> Report
+where
+    D: Diagnostic + Send + Sync + 'static,
+{
+    Report::new(DiagnosticError(Box::new(diagnostic)))
+}
+
+/// Get the miette [`Diagnostic`] a [`Report`] was converted from, if any.
+///
+/// This also finds diagnostics that were wrapped with additional context
+/// after being converted.
+#[cfg(feature = "miette-compat")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "miette-compat")))]
+pub fn diagnostic(report: &Report) -> Option<&(dyn Diagnostic + Send + Sync + 'static)> {
+    report
+        .downcast_ref::<DiagnosticError>()
+        .map(DiagnosticError::diagnostic)
+}
+
+/// A miette [`Diagnostic`] stored inside a [`Report`].
+///
+/// Reports created by [`IntoEyre`] and [`from_diagnostic`] wrap this type, and
+/// can be downcast to it to recover the original diagnostic.
+#[cfg(feature = "miette-compat")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "miette-compat")))]
+pub struct DiagnosticError(Box<dyn Diagnostic + Send + Sync + 'static>);
+
+#[cfg(feature = "miette-compat")]
+impl DiagnosticError {
+    /// The wrapped diagnostic.
+    pub fn diagnostic(&self) -> &(dyn Diagnostic + Send + Sync + 'static) {
+        &*self.0
+    }
+
+    /// Take ownership of the wrapped diagnostic.
+    pub fn into_diagnostic(self) -> Box<dyn Diagnostic + Send + Sync + 'static> {
+        self.0
+    }
+}
+
+#[cfg(feature = "miette-compat")]
+impl Debug for DiagnosticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "miette-compat")]
+impl Display for DiagnosticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "miette-compat")]
+impl StdError for DiagnosticError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl IntoEyre for anyhow::Error {
+    type Output = Report;
+
+    #[cfg_attr(track_caller, track_caller)]
+    fn into_eyre(self) -> Report {
+        // Hand back the original report if this error is an unmodified
+        // conversion of one
+        if (*self).is::<EyreError>() {
+            if let Ok(error) = self.downcast::<EyreError>() {
+                return error.0;
+            }
+            unreachable!("anyhow::Error checked to hold an EyreError");
+        }
+
+        Report::new(AnyhowError(self))
+    }
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl<T> IntoEyre for Result<T, anyhow::Error> {
+    type Output = Result<T, Report>;
+
+    #[cfg_attr(track_caller, track_caller)]
+    fn into_eyre(self) -> Result<T, Report> {
+        match self {
+            Ok(ok) => Ok(ok),
+            Err(error) => Err(error.into_eyre()),
+        }
+    }
+}
+
+/// An [`anyhow::Error`] stored inside a [`Report`].
+///
+/// Reports created by [`IntoEyre`] wrap this type. Besides downcasting to
+/// `AnyhowError` itself, [`Report::downcast_ref`], [`Report::downcast_mut`]
+/// and [`Report::downcast`] look for the requested type inside of the
+/// `anyhow::Error`, so the errors it was created from remain accessible.
+///
+/// # Example
+///
+/// ```
+/// use eyre::compat::IntoEyre;
+/// use std::io;
+///
+/// let error = anyhow::Error::new(io::Error::from(io::ErrorKind::NotFound))
+///     .context("failed to read config");
+///
+/// let report = error.into_eyre().wrap_err("failed to start");
+/// let io_error = report.downcast_ref::<io::Error>().unwrap();
+/// assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+/// ```
+#[cfg(feature = "anyhow-compat")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "anyhow-compat")))]
+pub struct AnyhowError(anyhow::Error);
+
+#[cfg(feature = "anyhow-compat")]
+impl AnyhowError {
+    /// The wrapped error.
+    pub fn error(&self) -> &anyhow::Error {
+        &self.0
+    }
+
+    /// The wrapped error, mutably.
+    pub fn error_mut(&mut self) -> &mut anyhow::Error {
+        &mut self.0
+    }
+
+    /// Take ownership of the wrapped error.
+    pub fn into_error(self) -> anyhow::Error {
+        self.0
+    }
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl Debug for AnyhowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl Display for AnyhowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only the outermost message, the rest is part of the source chain
+        Display::fmt(&*self.0, f)
+    }
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl StdError for AnyhowError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Convert eyre [`Report`]s into [`anyhow::Error`]s without flattening them to
+/// a string.
+///
+/// The resulting error displays the same message and has the same chain of
+/// sources as the report. On nightly, the backtrace captured by the report's
+/// [`DefaultHandler`](crate::DefaultHandler), or by the errors in its chain,
+/// is provided to `anyhow` via generic member access. The report itself can be
+/// recovered by downcasting to [`EyreError`], and converting the error back
+/// with [`IntoEyre`] returns the original report, as long as no context was
+/// added on the `anyhow` side.
+///
+/// # Example
+///
+/// ```
+/// use eyre::compat::IntoAnyhow;
+/// use eyre::WrapErr;
+/// use std::io;
+///
+/// fn eyre_library() -> eyre::Result<()> {
+///     Err(io::Error::from(io::ErrorKind::NotFound)).wrap_err("failed to read config")
+/// }
+///
+/// fn anyhow_application() -> anyhow::Result<()> {
+///     eyre_library().into_anyhow()?;
+///     Ok(())
+/// }
+///
+/// let error = anyhow_application().unwrap_err();
+/// let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+/// assert_eq!(chain, ["failed to read config", "entity not found"]);
+/// ```
+#[cfg(feature = "anyhow-compat")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "anyhow-compat")))]
+pub trait IntoAnyhow {
+    /// The type produced by the conversion.
+    type Output;
+
+    /// Performs the conversion.
+    fn into_anyhow(self) -> Self::Output;
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl IntoAnyhow for Report {
+    type Output = anyhow::Error;
+
+    fn into_anyhow(self) -> anyhow::Error {
+        // Hand back the original error if this report is an unmodified
+        // conversion of one
+        if (*self).is::<AnyhowError>() {
+            if let Ok(error) = self.downcast::<AnyhowError>() {
+                return error.0;
+            }
+            unreachable!("Report checked to hold an AnyhowError");
+        }
+
+        anyhow::Error::new(EyreError(self))
+    }
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl<T> IntoAnyhow for Result<T, Report> {
+    type Output = anyhow::Result<T>;
+
+    fn into_anyhow(self) -> anyhow::Result<T> {
+        self.map_err(IntoAnyhow::into_anyhow)
+    }
+}
+
+/// A [`Report`] stored inside an [`anyhow::Error`].
+///
+/// Errors created by [`IntoAnyhow`] wrap this type, and can be downcast to it
+/// to recover the original report.
+#[cfg(feature = "anyhow-compat")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "anyhow-compat")))]
+pub struct EyreError(Report);
+
+#[cfg(feature = "anyhow-compat")]
+impl EyreError {
+    /// The wrapped report.
+    pub fn report(&self) -> &Report {
+        &self.0
+    }
+
+    /// Take ownership of the wrapped report.
+    pub fn into_report(self) -> Report {
+        self.0
+    }
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl Debug for EyreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl Display for EyreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only the outermost message, the rest is part of the source chain
+        Display::fmt(&*self.0, f)
+    }
+}
+
+#[cfg(feature = "anyhow-compat")]
+impl StdError for EyreError {
+    #[cfg(generic_member_access)]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        if let Some(backtrace) = self
+            .0
+            .handler()
+            .downcast_ref::<crate::DefaultHandler>()
+            .and_then(crate::DefaultHandler::backtrace)
+        {
+            request.provide_ref(backtrace);
+        }
+
+        (*self.0).provide(request);
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(feature = "pyo3")]
+impl IntoEyre for pyo3::PyErr {
+    type Output = Report;
+
+    #[cfg_attr(track_caller, track_caller)]
+    fn into_eyre(self) -> Report {
+        let traceback = pyo3::Python::with_gil(|py| {
+            self.traceback(py)
+                .and_then(|traceback| traceback.format().ok())
+        });
+
+        let mut report = Report::new(self);
+        if let Some(traceback) = traceback {
+            report = report.wrap_err_field("traceback", &traceback);
+            report.insert(PythonTraceback(traceback));
+        }
+
+        report
+    }
+}
+
+#[cfg(feature = "pyo3")]
+impl<T> IntoEyre for pyo3::PyResult<T> {
+    type Output = Result<T, Report>;
+
+    #[cfg_attr(track_caller, track_caller)]
+    fn into_eyre(self) -> Result<T, Report> {
+        match self {
+            Ok(ok) => Ok(ok),
+            Err(error) => Err(error.into_eyre()),
+        }
+    }
+}
+
+/// The formatted traceback of a Python exception converted with
+/// [`IntoEyre`].
+///
+/// The traceback is attached to the [`Report`] as an extension, which can be
+/// read with [`Report::get`], and as the `traceback` field, so handlers
+/// which support fields render it along with the report.
+///
+/// # Example
+///
+/// ```
+/// use eyre::compat::{IntoEyre, PythonTraceback};
+/// use pyo3::Python;
+///
+/// let report = Python::with_gil(|py| py.run("1 / 0", None, None))
+///     .into_eyre()
+///     .unwrap_err();
+///
+/// let traceback = report.get::<PythonTraceback>().unwrap();
+/// assert!(traceback.as_str().starts_with("Traceback (most recent call last):"));
+/// ```
+#[cfg(feature = "pyo3")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "pyo3")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonTraceback(String);
+
+#[cfg(feature = "pyo3")]
+impl PythonTraceback {
+    /// The formatted traceback.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "pyo3")]
+impl Display for PythonTraceback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
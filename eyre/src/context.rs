@@ -1,5 +1,5 @@
 use crate::error::{ContextError, ErrorImpl};
-use crate::{Report, StdError, WrapErr};
+use crate::{Report, StdError, WrapBoxedErr, WrapErr, WrapReportErr};
 use core::fmt::{self, Debug, Display, Write};
 
 mod ext {
@@ -10,6 +10,16 @@ mod ext {
         fn ext_report<D>(self, msg: D) -> Report
         where
             D: Display + Send + Sync + 'static;
+
+        #[cfg_attr(track_caller, track_caller)]
+        fn ext_report_dedup<D>(self, msg: D) -> Report
+        where
+            D: Display + Send + Sync + 'static;
+
+        #[cfg_attr(track_caller, track_caller)]
+        fn ext_report_change<D>(self, msg: D) -> Report
+        where
+            D: Display + Send + Sync + 'static;
     }
 
     impl<E> StdError for E
@@ -22,6 +32,25 @@ mod ext {
         {
             Report::from_msg(msg, self)
         }
+
+        fn ext_report_dedup<D>(self, msg: D) -> Report
+        where
+            D: Display + Send + Sync + 'static,
+        {
+            if self.to_string() == msg.to_string() {
+                Report::from_std(self)
+            } else {
+                Report::from_msg(msg, self)
+            }
+        }
+
+        fn ext_report_change<D>(self, msg: D) -> Report
+        where
+            D: Display + Send + Sync + 'static,
+        {
+            // A plain error has no context message to replace yet
+            Report::from_msg(msg, self)
+        }
     }
 
     impl StdError for Report {
@@ -31,6 +60,20 @@ mod ext {
         {
             self.wrap_err(msg)
         }
+
+        fn ext_report_dedup<D>(self, msg: D) -> Report
+        where
+            D: Display + Send + Sync + 'static,
+        {
+            self.wrap_err_once(msg)
+        }
+
+        fn ext_report_change<D>(self, msg: D) -> Report
+        where
+            D: Display + Send + Sync + 'static,
+        {
+            self.change_context(msg)
+        }
     }
 }
 
@@ -58,6 +101,75 @@ where
             Err(e) => Err(e.ext_report(msg())),
         }
     }
+
+    fn wrap_err_dedup<D>(self, msg: D) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(e.ext_report_dedup(msg)),
+        }
+    }
+
+    fn change_context<D>(self, msg: D) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(e.ext_report_change(msg)),
+        }
+    }
+}
+
+impl<T> WrapBoxedErr<T> for Result<T, Box<dyn StdError + Send + Sync>> {
+    fn wrap_boxed_err<D>(self, msg: D) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(Report::from_boxed(e).wrap_err(msg)),
+        }
+    }
+
+    fn wrap_boxed_err_with<D, F>(self, msg: F) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(Report::from_boxed(e).wrap_err(msg())),
+        }
+    }
+}
+
+impl<T, E> WrapReportErr<T> for Result<T, E>
+where
+    Report: From<E>,
+{
+    fn wrap_report_err<D>(self, msg: D) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(Report::from(e).wrap_err(msg)),
+        }
+    }
+
+    fn wrap_report_err_with<D, F>(self, msg: F) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(Report::from(e).wrap_err(msg())),
+        }
+    }
 }
 
 #[cfg(feature = "anyhow")]
@@ -182,4 +294,12 @@ pub(crate) mod private {
 
     impl<T, E> Sealed for Result<T, E> where E: ext::StdError {}
     impl<T> Sealed for Option<T> {}
+
+    pub trait SealedBoxed {}
+
+    impl<T> SealedBoxed for Result<T, Box<dyn StdError + Send + Sync>> {}
+
+    pub trait SealedReport {}
+
+    impl<T, E> SealedReport for Result<T, E> where Report: From<E> {}
 }
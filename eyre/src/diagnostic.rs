@@ -0,0 +1,111 @@
+//! Error types that carry help text and error codes for their reports
+//!
+//! With the `derive` feature enabled, [`Diagnostic`] can be derived together
+//! with `Display` and `std::error::Error`:
+//!
+//! ```rust
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use eyre::{Diagnostic, EyreDiagnostic};
+//!
+//! #[derive(Debug, EyreDiagnostic)]
+//! enum ConfigError {
+//!     #[eyre("config file `{path}` not found", code = "CFG001", help = "run `init` to create it")]
+//!     NotFound { path: String },
+//!     #[eyre("failed to read config", code = "CFG002")]
+//!     Io(#[from] std::io::Error),
+//! }
+//!
+//! let error = ConfigError::NotFound { path: "app.toml".into() };
+//! assert_eq!(error.to_string(), "config file `app.toml` not found");
+//!
+//! let report = error.into_report();
+//! assert_eq!(report.code(), Some("CFG001"));
+//! # }
+//! ```
+use crate::Report;
+use alloc::string::String;
+use std::error::Error as StdError;
+
+/// An error type that knows how it should be reported.
+///
+/// The help text, suggestion and error code of a diagnostic are handed to the
+/// report's handler when the diagnostic is turned into a [`Report`] via
+/// [`into_report`](Diagnostic::into_report) or [`Report::from_diagnostic`],
+/// see [`EyreHandler::diagnostic`](crate::EyreHandler::diagnostic).
+///
+/// Converting a diagnostic with `?` or [`Report::new`] treats it like any
+/// other error and doesn't look at this metadata.
+pub trait Diagnostic: StdError + Send + Sync + 'static {
+    /// A stable error code for this error, see
+    /// [`Report::with_code`](crate::Report::with_code)
+    fn code(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Additional information that helps understand this error
+    fn help(&self) -> Option<String> {
+        None
+    }
+
+    /// A suggestion on how to fix this error
+    fn suggestion(&self) -> Option<String> {
+        None
+    }
+
+    /// Turn this error into a [`Report`] that carries its help text and error
+    /// code, see [`Report::from_diagnostic`].
+    #[cfg_attr(track_caller, track_caller)]
+    fn into_report(self) -> Report
+    where
+        Self: Sized,
+    {
+        Report::from_diagnostic(self)
+    }
+}
+
+impl Report {
+    /// Create a new report from a [`Diagnostic`], handing its help text and
+    /// suggestion to the handler and attaching its error code.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eyre::{Diagnostic, Report};
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct Timeout;
+    ///
+    /// impl fmt::Display for Timeout {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("request timed out")
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for Timeout {}
+    ///
+    /// impl Diagnostic for Timeout {
+    ///     fn code(&self) -> Option<&'static str> {
+    ///         Some("NET002")
+    ///     }
+    /// }
+    ///
+    /// let report = Report::from_diagnostic(Timeout);
+    /// assert_eq!(report.code(), Some("NET002"));
+    /// ```
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn from_diagnostic<D>(diagnostic: D) -> Self
+    where
+        D: Diagnostic,
+    {
+        let mut handler = crate::capture_handler(&diagnostic);
+        handler.diagnostic(&diagnostic);
+        let code = diagnostic.code();
+        let report = Report::from_std_with_handler(diagnostic, handler);
+        match code {
+            Some(code) => report.with_code(code),
+            None => report,
+        }
+    }
+}
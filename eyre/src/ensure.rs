@@ -0,0 +1,89 @@
+#![allow(missing_debug_implementations, missing_docs)]
+// Support for `ensure!(left == right)` without a message.
+//
+// The macro splits the condition at its top level comparison operator and
+// evaluates both operands once, like `assert_eq!`. When the comparison fails
+// and both operands implement Debug their values are included in the report,
+// otherwise the report only contains the stringified condition as before.
+//
+// Whether the operands implement Debug is decided with the same autoref based
+// tagged dispatch used for `eyre!($expr)` in kind.rs:
+//
+//     #[allow(unused_imports)]
+//     use $crate::private::ensure::{DebugOperand, OpaqueOperand};
+//     (&Operand(left)).eyre_render()
+//
+// DebugOperand is implemented for Operand<T> only when T: Debug, while
+// OpaqueOperand is implemented for every &Operand<T>, needing an extra autoref
+// which gives it lower precedence.
+
+use crate::Report;
+use core::fmt::{self, Debug, Display};
+
+pub struct Operand<'a, T: ?Sized>(pub &'a T);
+
+pub trait DebugOperand {
+    fn eyre_render(&self) -> Option<String>;
+}
+
+impl<T> DebugOperand for Operand<'_, T>
+where
+    T: Debug + ?Sized,
+{
+    fn eyre_render(&self) -> Option<String> {
+        Some(format!("{:?}", self.0))
+    }
+}
+
+pub trait OpaqueOperand {
+    fn eyre_render(&self) -> Option<String>;
+}
+
+impl<T> OpaqueOperand for &Operand<'_, T>
+where
+    T: ?Sized,
+{
+    fn eyre_render(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The message of a report created by `ensure!`, `ensure_eq!` or `ensure_ne!`
+/// when a comparison fails
+struct ComparisonFailed<M> {
+    message: M,
+    left: String,
+    right: String,
+}
+
+impl<M: Display> Display for ComparisonFailed<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (left: {}, right: {})",
+            self.message, self.left, self.right
+        )
+    }
+}
+
+impl<M: Display> Debug for ComparisonFailed<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cold]
+#[cfg_attr(track_caller, track_caller)]
+pub fn comparison_failed<M>(message: M, left: Option<String>, right: Option<String>) -> Report
+where
+    M: Debug + Display + Send + Sync + 'static,
+{
+    match (left, right) {
+        (Some(left), Some(right)) => Report::from_adhoc(ComparisonFailed {
+            message,
+            left,
+            right,
+        }),
+        _ => Report::msg(message),
+    }
+}
@@ -1,13 +1,20 @@
+use crate::aggregate::Aggregate;
 use crate::chain::Chain;
+use crate::extensions::Extensions;
+use crate::fingerprint::FingerprintComponent;
+use crate::frame::{ChainMut, ContextMessageMut, FrameSource, FrameSourceMut, Frames};
+use crate::key::ReportKey;
 use crate::ptr::{MutPtr, OwnedPtr, RefPtr};
+use crate::wrapper::{RenderedError, SourceError};
 use crate::EyreHandler;
 use crate::{Report, StdError};
-use core::any::TypeId;
+use core::any::{Any, TypeId};
 use core::fmt::{self, Debug, Display};
 use core::mem::{self, ManuallyDrop};
 use core::ptr::{self, NonNull};
 
 use core::ops::{Deref, DerefMut};
+use std::panic::{RefUnwindSafe, UnwindSafe};
 
 impl Report {
     /// Create a new error object from any error type.
@@ -70,26 +77,85 @@ impl Report {
         Report::from_adhoc(message)
     }
 
+    /// Create a new error object from a static message.
+    ///
+    /// This is equivalent to `Report::msg(message)`, and the resulting report
+    /// can be downcast to `&'static str`. Unlike [`Report::msg`] it is not
+    /// generic, so every call shares a single instantiation of the
+    /// constructor rather than adding one per message type. This is what
+    /// `eyre!("literal")`, `bail!("literal")` and `ensure!(cond, "literal")`
+    /// use.
+    ///
+    /// ```
+    /// use eyre::Report;
+    ///
+    /// const UNEXPECTED_EOF: &str = "unexpected end of input";
+    ///
+    /// let report = Report::msg_static(UNEXPECTED_EOF);
+    /// let message = *report.downcast_ref::<&str>().unwrap();
+    /// assert!(core::ptr::eq(message, UNEXPECTED_EOF));
+    /// ```
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn msg_static(message: &'static str) -> Self {
+        Report::from_adhoc(message)
+    }
+
+    /// Create a new error object by eagerly formatting a borrowed error.
+    ///
+    /// This is intended for errors that cannot be stored in a `Report`
+    /// because they are not `Send`, `Sync` or `'static`. The `Display` and
+    /// `Debug` output of the error and of every error in its source chain are
+    /// captured as owned strings, so the report prints the same chain of
+    /// messages as the original error would have. The original value is not
+    /// retained and therefore cannot be recovered by downcasting.
+    ///
+    /// ```
+    /// use eyre::Report;
+    /// use std::fmt;
+    /// use std::rc::Rc;
+    ///
+    /// #[derive(Debug)]
+    /// struct LocalError(Rc<str>);
+    ///
+    /// impl fmt::Display for LocalError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str(&self.0)
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for LocalError {}
+    ///
+    /// let error = LocalError(Rc::from("connection reset"));
+    /// let report = Report::msg_from_error(&error);
+    /// assert_eq!(report.to_string(), "connection reset");
+    /// ```
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn msg_from_error<E>(error: &E) -> Self
+    where
+        E: StdError + ?Sized,
+    {
+        Report::from_std(RenderedError::with_debug(error))
+    }
+
     #[cfg_attr(track_caller, track_caller)]
     /// Creates a new error from an implementor of [`std::error::Error`]
     pub(crate) fn from_std<E>(error: E) -> Self
     where
         E: StdError + Send + Sync + 'static,
     {
-        let vtable = &ErrorVTable {
-            object_drop: object_drop::<E>,
-            object_ref: object_ref::<E>,
-            object_mut: object_mut::<E>,
-            object_boxed: object_boxed::<E>,
-            object_downcast: object_downcast::<E>,
-            object_downcast_mut: object_downcast_mut::<E>,
-            object_drop_rest: object_drop_front::<E>,
-        };
+        let handler = crate::capture_handler(&error);
+        Report::from_std_with_handler(error, handler)
+    }
 
+    #[cfg_attr(track_caller, track_caller)]
+    pub(crate) fn from_std_with_handler<E>(error: E, handler: Box<dyn EyreHandler>) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
         // Safety: passing vtable that operates on the right type E.
-        let handler = Some(crate::capture_handler(&error));
-
-        unsafe { Report::construct(error, vtable, handler) }
+        let report = unsafe { Report::construct(error, std_vtable::<E>(), Some(handler)) };
+        crate::observer::created(&report);
+        report
     }
 
     #[cfg_attr(track_caller, track_caller)]
@@ -99,6 +165,19 @@ impl Report {
     {
         use crate::wrapper::MessageError;
         let error: MessageError<M> = MessageError(message);
+        let handler = crate::capture_handler(&error);
+        Report::from_adhoc_with_handler(error, handler)
+    }
+
+    #[cfg_attr(track_caller, track_caller)]
+    pub(crate) fn from_adhoc_with_handler<M>(
+        error: crate::wrapper::MessageError<M>,
+        handler: Box<dyn EyreHandler>,
+    ) -> Self
+    where
+        M: Display + Debug + Send + Sync + 'static,
+    {
+        use crate::wrapper::MessageError;
         let vtable = &ErrorVTable {
             object_drop: object_drop::<MessageError<M>>,
             object_ref: object_ref::<MessageError<M>>,
@@ -107,13 +186,18 @@ impl Report {
             object_downcast: object_downcast::<M>,
             object_downcast_mut: object_downcast_mut::<M>,
             object_drop_rest: object_drop_front::<M>,
+            object_context: no_context,
+            object_context_mut: no_context_mut,
+            object_type_id: object_type_id::<M>,
+            object_unwrap_context: None,
+            object_with_source: root_with_source,
         };
 
         // Safety: MessageError is repr(transparent) so it is okay for the
         // vtable to allow casting the MessageError<M> to M.
-        let handler = Some(crate::capture_handler(&error));
-
-        unsafe { Report::construct(error, vtable, handler) }
+        let report = unsafe { Report::construct(error, vtable, Some(handler)) };
+        crate::observer::created(&report);
+        report
     }
 
     #[cfg(feature = "anyhow")]
@@ -132,13 +216,20 @@ impl Report {
             object_downcast: object_downcast::<M>,
             object_downcast_mut: object_downcast_mut::<M>,
             object_drop_rest: object_drop_front::<M>,
+            object_context: no_context,
+            object_context_mut: no_context_mut,
+            object_type_id: object_type_id::<M>,
+            object_unwrap_context: None,
+            object_with_source: root_with_source,
         };
 
         // Safety: DisplayError is repr(transparent) so it is okay for the
         // vtable to allow casting the DisplayError<M> to M.
         let handler = Some(crate::capture_handler(&NoneError));
 
-        unsafe { Report::construct(error, vtable, handler) }
+        let report = unsafe { Report::construct(error, vtable, handler) };
+        crate::observer::created(&report);
+        report
     }
 
     #[cfg_attr(track_caller, track_caller)]
@@ -157,12 +248,25 @@ impl Report {
             object_downcast: context_downcast::<D, E>,
             object_downcast_mut: context_downcast_mut::<D, E>,
             object_drop_rest: context_drop_rest::<D, E>,
+            object_context: context_context::<D, E>,
+            object_context_mut: context_context_mut::<D, E>,
+            object_type_id: object_type_id::<E>,
+            object_unwrap_context: Some(context_unwrap::<D, E>),
+            object_with_source: context_with_source::<D, E>,
         };
 
-        // Safety: passing vtable that operates on the right type.
-        let handler = Some(crate::capture_handler(&error));
+        let mut handler = crate::capture_handler(&error);
+        // The error and its context message share a single layer, which was
+        // wrapped where it was created.
+        #[cfg(track_caller)]
+        handler.on_wrap(&error.msg, Some(core::panic::Location::caller()));
+        #[cfg(not(track_caller))]
+        handler.on_wrap(&error.msg, None);
 
-        unsafe { Report::construct(error, vtable, handler) }
+        // Safety: passing vtable that operates on the right type.
+        let report = unsafe { Report::construct(error, vtable, Some(handler)) };
+        crate::observer::created(&report);
+        report
     }
 
     #[cfg_attr(track_caller, track_caller)]
@@ -179,11 +283,18 @@ impl Report {
             object_downcast: object_downcast::<Box<dyn StdError + Send + Sync>>,
             object_downcast_mut: object_downcast_mut::<Box<dyn StdError + Send + Sync>>,
             object_drop_rest: object_drop_front::<Box<dyn StdError + Send + Sync>>,
+            object_context: no_context,
+            object_context_mut: no_context_mut,
+            object_type_id: object_type_id::<Box<dyn StdError + Send + Sync>>,
+            object_unwrap_context: None,
+            object_with_source: root_with_source,
         };
 
         // Safety: BoxedError is repr(transparent) so it is okay for the vtable
         // to allow casting to Box<dyn StdError + Send + Sync>.
-        unsafe { Report::construct(error, vtable, handler) }
+        let report = unsafe { Report::construct(error, vtable, handler) };
+        crate::observer::created(&report);
+        report
     }
 
     // Takes backtrace as argument rather than capturing it here so that the
@@ -191,6 +302,7 @@ impl Report {
     //
     // Unsafe because the given vtable must have sensible behavior on the error
     // value of type E.
+    #[cfg_attr(track_caller, track_caller)]
     unsafe fn construct<E>(
         error: E,
         vtable: &'static ErrorVTable,
@@ -200,7 +312,15 @@ impl Report {
         E: StdError + Send + Sync + 'static,
     {
         let inner = ErrorImpl {
-            header: ErrorHeader { vtable, handler },
+            header: ErrorHeader {
+                vtable,
+                handler,
+                extensions: Extensions::default(),
+                #[cfg(track_caller)]
+                location: Some(core::panic::Location::caller()),
+                #[cfg(not(track_caller))]
+                location: None,
+            },
             _object: error,
         };
 
@@ -215,6 +335,34 @@ impl Report {
         Report { inner: ptr }
     }
 
+    /// Create a new error combining several independent failures.
+    ///
+    /// The returned report wraps an [`Aggregate`][crate::Aggregate] that
+    /// numbers each report by its position in `reports`, and can be downcast
+    /// back to it to access the individual failures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{eyre, Report};
+    ///
+    /// let report = Report::aggregate(vec![eyre!("disk full"), eyre!("network down")]);
+    ///
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "2 errors occurred\n\
+    ///      [0] disk full\n\
+    ///      [1] network down",
+    /// );
+    /// ```
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn aggregate<I>(reports: I) -> Self
+    where
+        I: IntoIterator<Item = Report>,
+    {
+        Report::new(Aggregate::new(reports.into_iter().enumerate().collect()))
+    }
+
     /// Create a new error from an error message to wrap the existing error.
     ///
     /// For attaching a higher level error message to a `Result` as it is propagated, the
@@ -268,29 +416,258 @@ impl Report {
     ///     })
     /// }
     /// ```
-    pub fn wrap_err<D>(mut self, msg: D) -> Self
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn wrap_err<D>(self, msg: D) -> Self
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        #[cfg(track_caller)]
+        let location = Some(core::panic::Location::caller());
+        #[cfg(not(track_caller))]
+        let location = None;
+
+        self.wrap_err_at(msg, location)
+    }
+
+    /// Wrap the error value with additional context that was added at
+    /// `location`, rather than by the caller of this function.
+    pub(crate) fn wrap_err_at<D>(
+        mut self,
+        msg: D,
+        location: Option<&'static core::panic::Location<'static>>,
+    ) -> Self
     where
         D: Display + Send + Sync + 'static,
     {
+        let (handler, extensions) = self.take_layer_state(&msg, location);
+
+        let error: ContextError<D, Report> = ContextError { msg, error: self };
+
+        // Safety: passing vtable that operates on the right type.
+        let report = unsafe { Report::construct(error, context_chain_vtable::<D>(), handler) };
+        report.finish_layer(extensions, location)
+    }
+
+    // The following two functions hold the header bookkeeping of
+    // `wrap_err_at`, which doesn't depend on the type of the context message,
+    // so it is compiled once rather than for every message type. Constructing
+    // the layer itself is still generic.
+
+    /// Moves the handler and extensions out of this report, which is about to
+    /// become the source of a new context layer.
+    #[inline(never)]
+    fn take_layer_state(
+        &mut self,
+        msg: &dyn Display,
+        location: Option<&'static core::panic::Location<'static>>,
+    ) -> (Option<Box<dyn EyreHandler>>, Extensions) {
         // Safety: this access a `ErrorImpl<unknown>` as a valid reference to a `ErrorImpl<()>`
         //
         // As the generic is at the end of the struct and the struct is `repr(C)` this reference
         // will be within bounds of the original pointer, and the field will have the same offset
-        let handler = header_mut(self.inner.as_mut()).handler.take();
-        let error: ContextError<D, Report> = ContextError { msg, error: self };
+        let mut handler = header_mut(self.inner.as_mut()).handler.take();
+        let extensions = mem::take(&mut header_mut(self.inner.as_mut()).extensions);
 
-        let vtable = &ErrorVTable {
-            object_drop: object_drop::<ContextError<D, Report>>,
-            object_ref: object_ref::<ContextError<D, Report>>,
-            object_mut: object_mut::<ContextError<D, Report>>,
-            object_boxed: object_boxed::<ContextError<D, Report>>,
-            object_downcast: context_chain_downcast::<D>,
-            object_downcast_mut: context_chain_downcast_mut::<D>,
-            object_drop_rest: context_chain_drop_rest::<D>,
+        if let Some(handler) = handler.as_mut() {
+            handler.on_wrap(msg, location);
+        }
+
+        (handler, extensions)
+    }
+
+    /// Completes a new context layer with the state taken from its source by
+    /// [`take_layer_state`][Report::take_layer_state].
+    #[inline(never)]
+    fn finish_layer(
+        mut self,
+        extensions: Extensions,
+        location: Option<&'static core::panic::Location<'static>>,
+    ) -> Self {
+        header_mut(self.inner.as_mut()).extensions = extensions;
+        header_mut(self.inner.as_mut()).location = location;
+        crate::observer::wrapped(&self);
+        self
+    }
+
+    /// Wrap the error value with additional context that is evaluated lazily.
+    ///
+    /// This is the `Report` counterpart of
+    /// [`WrapErr::wrap_err_with`][crate::WrapErr::wrap_err_with], for when a
+    /// report is already at hand.
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn wrap_err_with<D, F>(self, msg: F) -> Self
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        self.wrap_err(msg())
+    }
+
+    /// Attach a structured key-value field to this Report.
+    ///
+    /// Fields are a structured alternative to formatting values into context
+    /// messages. They are handed to the report's [`EyreHandler`] via
+    /// [`EyreHandler::field`], and handlers which support them render them
+    /// separately from the chain of errors, e.g. the default handler lists
+    /// them as a table in its `Debug` output. Attaching a field with a key
+    /// that is already present replaces its value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::eyre;
+    ///
+    /// let report = eyre!("failed to load user")
+    ///     .wrap_err_field("user_id", 42)
+    ///     .wrap_err_field("path", "/etc/users");
+    ///
+    /// assert_eq!(report.to_string(), "failed to load user");
+    /// ```
+    pub fn wrap_err_field<V>(mut self, key: &'static str, value: V) -> Self
+    where
+        V: Display,
+    {
+        if let Some(handler) = header_mut(self.inner.as_mut()).handler.as_mut() {
+            handler.field(key, &value);
+        }
+
+        self
+    }
+
+    /// Wrap the error value with additional context, unless the outermost
+    /// message of the report is already identical to it.
+    ///
+    /// Generic retry and backoff helpers often pass the same report through
+    /// the same `wrap_err` call on every attempt, stacking identical context
+    /// messages on top of each other. This only adds `msg` if the report's
+    /// `Display` output differs from it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::eyre;
+    ///
+    /// let mut report = eyre!("connection refused");
+    /// for _ in 0..3 {
+    ///     report = report.wrap_err_once("failed to reach the server");
+    /// }
+    ///
+    /// assert_eq!(report.chain().count(), 2);
+    /// ```
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn wrap_err_once<D>(self, msg: D) -> Self
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        if self.to_string() == msg.to_string() {
+            self
+        } else {
+            self.wrap_err(msg)
+        }
+    }
+
+    /// Replace the outermost context message of this Report with `msg`.
+    ///
+    /// If the report was not wrapped with a context message yet, `msg` is
+    /// attached as with [`wrap_err`][Report::wrap_err]. Otherwise the
+    /// previous message is dropped, so code which retries or re-throws the
+    /// same report in a loop doesn't accumulate a new layer of context on
+    /// every iteration. The handler and extensions of the report are kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::eyre;
+    ///
+    /// let mut report = eyre!("connection refused").wrap_err("retrying request");
+    /// for attempt in 1..=3 {
+    ///     report = report.change_context(format!("attempt {} failed", attempt));
+    /// }
+    ///
+    /// assert_eq!(
+    ///     format!("{:#}", report),
+    ///     "attempt 3 failed: connection refused",
+    /// );
+    /// ```
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn change_context<D>(mut self, msg: D) -> Self
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        let unwrap = match self.vtable().object_unwrap_context {
+            Some(unwrap) => unwrap,
+            None => return self.wrap_err(msg),
         };
 
-        // Safety: passing vtable that operates on the right type.
-        unsafe { Report::construct(error, vtable, handler) }
+        let handler = header_mut(self.inner.as_mut()).handler.take();
+        let extensions = mem::take(&mut header_mut(self.inner.as_mut()).extensions);
+
+        // Safety: the vtable entry belongs to this report's layer, which
+        // gives up ownership of its allocation without running `Drop`.
+        let inner = ManuallyDrop::new(self).inner;
+        let mut source = unsafe { unwrap(inner) };
+
+        header_mut(source.inner.as_mut()).handler = handler;
+        header_mut(source.inner.as_mut()).extensions = extensions;
+        source.wrap_err(msg)
+    }
+
+    /// Attach `source` as the lowest level cause of this Report.
+    ///
+    /// This is for causes that are only discovered after the report was
+    /// created, such as the output of a child process that is read after it
+    /// exited with a failure status. `source` becomes the source of the error
+    /// the report was created from, so it is visited last by
+    /// [`chain()`][Report::chain], is the new
+    /// [`root_cause()`][Report::root_cause] and can be found by
+    /// [`downcast_ref`][Report::downcast_ref]. Every further source is
+    /// appended below the previous one. The context messages, handler and
+    /// extensions of the report are kept.
+    ///
+    /// If the error the report was created from already has sources of its
+    /// own, `source` is appended after them. Those sources keep their
+    /// `Display` and `Debug` output, but can't be downcast while iterating
+    /// over the chain anymore. They can still be reached by downcasting to
+    /// the error the report was created from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::eyre;
+    /// use std::io;
+    ///
+    /// let report = eyre!("`make` exited with status 2").wrap_err("failed to build");
+    /// let stderr = io::Error::new(io::ErrorKind::Other, "no targets specified");
+    /// let report = report.with_source(stderr);
+    ///
+    /// let chain: Vec<_> = report.chain().map(|e| e.to_string()).collect();
+    /// assert_eq!(
+    ///     chain,
+    ///     [
+    ///         "failed to build",
+    ///         "`make` exited with status 2",
+    ///         "no targets specified",
+    ///     ],
+    /// );
+    /// assert!(report.downcast_ref::<io::Error>().is_some());
+    /// ```
+    pub fn with_source<E>(self, source: E) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        // The source is rendered through the handler of the outermost layer,
+        // so it doesn't need one of its own.
+        //
+        // Safety: passing vtable that operates on the right type E.
+        let source = unsafe { Report::construct(source, std_vtable::<E>(), None) };
+        self.attach_source(source)
+    }
+
+    fn attach_source(self, source: Report) -> Self {
+        let outer = ManuallyDrop::new(self);
+        // Safety: the vtable entry belongs to this report's layer, which
+        // gives up ownership of its allocation without running `Drop`.
+        unsafe { (outer.vtable().object_with_source)(outer.inner, source) }
     }
 
     /// Access the vtable for the current error object.
@@ -323,6 +700,189 @@ impl Report {
         ErrorImpl::chain(self.inner.as_ref())
     }
 
+    /// A mutable iterator over the chain of this Report.
+    ///
+    /// This visits every message attached via [`wrap_err`][Report::wrap_err]
+    /// as a [`ChainFrameMut::ContextMessage`], followed by the error the report
+    /// was created from as a [`ChainFrameMut::Error`]. It is intended for
+    /// middleware that needs to modify errors in place, e.g. to redact
+    /// sensitive fields before a report is logged.
+    ///
+    /// [`std::error::Error`] only provides shared access to an error's
+    /// sources, so unlike [`chain()`][Report::chain] this does not continue
+    /// into the sources of the error the report was created from. Downcast
+    /// that error to its concrete type to modify its sources.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{eyre, ChainFrameMut};
+    ///
+    /// let mut report = eyre!("connection failed").wrap_err(String::from("password=hunter2"));
+    ///
+    /// for frame in report.chain_mut() {
+    ///     if let ChainFrameMut::ContextMessage(mut msg) = frame {
+    ///         if let Some(msg) = msg.downcast_mut::<String>() {
+    ///             *msg = msg.replace("hunter2", "<redacted>");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(report.to_string(), "password=<redacted>");
+    /// ```
+    ///
+    /// [`ChainFrameMut::ContextMessage`]: crate::ChainFrameMut::ContextMessage
+    /// [`ChainFrameMut::Error`]: crate::ChainFrameMut::Error
+    pub fn chain_mut(&mut self) -> ChainMut<'_> {
+        ChainMut::new(self.inner.as_mut())
+    }
+
+    /// An iterator over the chain of this Report which distinguishes context
+    /// messages from error values.
+    ///
+    /// This visits the same elements as [`chain()`][Report::chain], but
+    /// messages attached via [`wrap_err`][Report::wrap_err] or the
+    /// [`WrapErr`][crate::WrapErr] trait are yielded as
+    /// [`ChainFrame::ContextMessage`] rather than as errors, so formatters can
+    /// treat human-added context differently from the errors it describes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{ChainFrame, Report, WrapErr};
+    /// use std::io;
+    ///
+    /// let error = io::Error::new(io::ErrorKind::NotFound, "oh no!");
+    /// let report = Err::<(), _>(error)
+    ///     .wrap_err("failed to read config")
+    ///     .unwrap_err();
+    ///
+    /// let errors: Vec<String> = report
+    ///     .frames()
+    ///     .filter_map(|frame| match frame {
+    ///         ChainFrame::Error(error) => Some(error.to_string()),
+    ///         ChainFrame::ContextMessage(_) => None,
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(errors, ["oh no!"]);
+    /// ```
+    pub fn frames(&self) -> Frames<'_> {
+        Frames::new(self.inner.as_ref())
+    }
+
+    /// A key identifying the failure this Report describes, for deduplication.
+    ///
+    /// Two reports produce equal keys if their chains print the same messages
+    /// and they were created from errors of the same type, regardless of the
+    /// handlers or backtraces they captured. This makes it possible to store
+    /// keys in a `HashSet` and only report each distinct failure once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{eyre, Report};
+    /// use std::collections::HashSet;
+    ///
+    /// let mut seen = HashSet::new();
+    ///
+    /// for _ in 0..3 {
+    ///     let error: Report = eyre!("connection refused").wrap_err("failed to sync");
+    ///     if seen.insert(error.dedup_key()) {
+    ///         eprintln!("{:?}", error);
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(seen.len(), 1);
+    /// ```
+    pub fn dedup_key(&self) -> ReportKey {
+        // Safety: the vtable belongs to this report's error object.
+        let root_type = unsafe { (self.vtable().object_type_id)(self.inner.as_ref()) };
+        let messages = self.chain().map(|error| error.to_string()).collect();
+        ReportKey::new(messages, root_type)
+    }
+
+    /// A hash identifying the kind of failure this Report describes, for
+    /// grouping reports before alerting.
+    ///
+    /// Unlike [`dedup_key`][Report::dedup_key], reports whose messages only
+    /// differ in interpolated values, such as ids or counts, are given the
+    /// same fingerprint where possible. Context messages and ad-hoc error
+    /// messages are identified by the location they were created at, and the
+    /// digits in all other messages are ignored. See
+    /// [`fingerprint_components`][Report::fingerprint_components] for the
+    /// values that are hashed.
+    ///
+    /// Fingerprints are only stable within a single build of a program, as
+    /// they include the hash of `TypeId`s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{eyre, Report};
+    ///
+    /// fn fetch(id: u32) -> Report {
+    ///     eyre!("user {} not found", id).wrap_err("failed to load profile")
+    /// }
+    ///
+    /// assert_eq!(fetch(1).fingerprint(), fetch(2).fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        use core::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.fingerprint_components().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The values hashed by [`fingerprint`][Report::fingerprint], outermost
+    /// first.
+    ///
+    /// Handlers and applications which want to group reports differently
+    /// can use these to build their own keys.
+    pub fn fingerprint_components(&self) -> Vec<FingerprintComponent> {
+        crate::fingerprint::components(self.inner.as_ref())
+    }
+
+    /// The locations at which this report and each context layer wrapped
+    /// around it were created, outermost first.
+    ///
+    /// Every call to [`wrap_err`][Report::wrap_err] and friends records where
+    /// it was made, so together with the location of the original report
+    /// these form a cheap "logical stack" that is available even when
+    /// backtraces are disabled. Locations are only captured when the
+    /// `track-caller` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "auto-install"))]
+    /// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+    /// use eyre::{eyre, WrapErr};
+    ///
+    /// let report = Err::<(), _>(eyre!("connection refused"))
+    ///     .wrap_err("failed to connect")
+    ///     .unwrap_err();
+    ///
+    /// for location in report.locations() {
+    ///     assert_eq!(location.file(), file!());
+    /// }
+    /// ```
+    pub fn locations(&self) -> impl Iterator<Item = &'static core::panic::Location<'static>> {
+        let mut locations = Vec::new();
+        let mut next = Some(self.inner.as_ref());
+
+        while let Some(report) = next.take() {
+            locations.extend(ErrorImpl::location(report));
+
+            if let Some((_, FrameSource::Report(source))) = ErrorImpl::context(report) {
+                next = Some(source);
+            }
+        }
+
+        locations.into_iter()
+    }
+
     /// The lowest level cause of this error &mdash; this error's cause's
     /// cause's cause etc.
     ///
@@ -362,6 +922,9 @@ impl Report {
             // somewhere inside the data structure.
             let addr = match (self.vtable().object_downcast)(self.inner.as_ref(), target) {
                 Some(addr) => addr,
+                #[cfg(feature = "anyhow-compat")]
+                None => return self.downcast_anyhow(),
+                #[cfg(not(feature = "anyhow-compat"))]
                 None => return Err(self),
             };
 
@@ -383,6 +946,35 @@ impl Report {
         }
     }
 
+    /// Take `E` out of the chain of an anyhow error stored in this report,
+    /// which is opaque to the vtable.
+    #[cfg(feature = "anyhow-compat")]
+    fn downcast_anyhow<E>(self) -> Result<E, Self>
+    where
+        E: Display + Debug + Send + Sync + 'static,
+    {
+        use crate::compat::AnyhowError;
+
+        // Only take the anyhow error out of the report once it's known to
+        // hold an `E`, the report can't be put back together afterwards
+        let holds_target = match self.downcast_ref::<AnyhowError>() {
+            Some(error) => {
+                TypeId::of::<E>() != TypeId::of::<AnyhowError>() && error.error().is::<E>()
+            }
+            None => false,
+        };
+        if !holds_target {
+            return Err(self);
+        }
+
+        if let Ok(error) = self.downcast::<AnyhowError>() {
+            if let Ok(error) = error.into_error().downcast::<E>() {
+                return Ok(error);
+            }
+        }
+        unreachable!("anyhow::Error checked to hold the target type");
+    }
+
     /// Downcast this error object by reference.
     ///
     /// # Example
@@ -430,9 +1022,23 @@ impl Report {
         unsafe {
             // Use vtable to find NonNull<()> which points to a value of type E
             // somewhere inside the data structure.
-            let addr = (self.vtable().object_downcast)(self.inner.as_ref(), target)?;
-            Some(addr.cast::<E>().as_ref())
+            if let Some(addr) = (self.vtable().object_downcast)(self.inner.as_ref(), target) {
+                return Some(addr.cast::<E>().as_ref());
+            }
         }
+
+        // The chain of an anyhow error is opaque to the vtable, so ask it to
+        // do the downcast instead
+        #[cfg(feature = "anyhow-compat")]
+        unsafe {
+            let target = TypeId::of::<crate::compat::AnyhowError>();
+            if let Some(addr) = (self.vtable().object_downcast)(self.inner.as_ref(), target) {
+                let error = addr.cast::<crate::compat::AnyhowError>().as_ref();
+                return error.error().downcast_ref();
+            }
+        }
+
+        None
     }
 
     /// Downcast this error object by mutable reference.
@@ -444,9 +1050,104 @@ impl Report {
         unsafe {
             // Use vtable to find NonNull<()> which points to a value of type E
             // somewhere inside the data structure.
-            let addr = (self.vtable().object_downcast_mut)(self.inner.as_mut(), target)?;
-            Some(addr.cast::<E>().as_mut())
+            if let Some(addr) = (self.vtable().object_downcast_mut)(self.inner.as_mut(), target) {
+                return Some(addr.cast::<E>().as_mut());
+            }
+        }
+
+        #[cfg(feature = "anyhow-compat")]
+        unsafe {
+            let target = TypeId::of::<crate::compat::AnyhowError>();
+            if let Some(addr) = (self.vtable().object_downcast_mut)(self.inner.as_mut(), target) {
+                let error = addr.cast::<crate::compat::AnyhowError>().as_mut();
+                return error.error_mut().downcast_mut();
+            }
         }
+
+        None
+    }
+
+    /// Find the first error of type `E` in the chain of this Report.
+    ///
+    /// Unlike [`downcast_ref`][Report::downcast_ref], which only considers the
+    /// error the report was created from and the messages attached via
+    /// [`wrap_err`][Report::wrap_err], this also visits the sources of that
+    /// error, from the outermost to the innermost.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{Report, WrapErr};
+    /// use std::io;
+    ///
+    /// #[derive(Debug)]
+    /// struct ConfigError(io::Error);
+    ///
+    /// impl std::fmt::Display for ConfigError {
+    ///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         f.write_str("failed to load config")
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for ConfigError {
+    ///     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    ///         Some(&self.0)
+    ///     }
+    /// }
+    ///
+    /// let error = ConfigError(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+    /// let report = Err::<(), _>(error)
+    ///     .wrap_err("failed to start")
+    ///     .unwrap_err();
+    ///
+    /// assert!(report.downcast_ref::<io::Error>().is_none());
+    /// let io_error = report.find::<io::Error>().unwrap();
+    /// assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+    /// ```
+    pub fn find<E>(&self) -> Option<&E>
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        self.downcast_ref::<E>()
+            .or_else(|| self.chain().find_map(|error| error.downcast_ref::<E>()))
+    }
+
+    /// Find the first error of type `E` in the chain of this Report by mutable
+    /// reference.
+    ///
+    /// [`std::error::Error`] only provides shared access to an error's
+    /// sources, so unlike [`find`][Report::find] this can only find the error
+    /// the report was created from and the messages attached via
+    /// [`wrap_err`][Report::wrap_err], just like
+    /// [`downcast_mut`][Report::downcast_mut].
+    pub fn find_mut<E>(&mut self) -> Option<&mut E>
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        self.downcast_mut::<E>()
+    }
+
+    /// Downcast the [`root_cause`][Report::root_cause] of this Report.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::WrapErr;
+    /// use std::io;
+    ///
+    /// let error = io::Error::new(io::ErrorKind::NotFound, "oh no!");
+    /// let report = Err::<(), _>(error)
+    ///     .wrap_err("failed to read config")
+    ///     .unwrap_err();
+    ///
+    /// let root_cause = report.root_cause_downcast::<io::Error>().unwrap();
+    /// assert_eq!(root_cause.kind(), io::ErrorKind::NotFound);
+    /// ```
+    pub fn root_cause_downcast<E>(&self) -> Option<&E>
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        self.root_cause().downcast_ref::<E>()
     }
 
     /// Get a reference to the Handler for this Report.
@@ -467,6 +1168,145 @@ impl Report {
             .as_mut()
     }
 
+    /// Attach a typed value to this Report, returning the value of the same
+    /// type that was previously attached, if any.
+    ///
+    /// Extensions let applications carry data such as HTTP status codes, exit
+    /// codes or retry hints along with a report without writing a custom
+    /// [`EyreHandler`]. At most one value of each type is stored, and values
+    /// are kept when the report is wrapped with additional context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{eyre, Report};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct StatusCode(u16);
+    ///
+    /// let mut report = eyre!("user not found");
+    /// report.insert(StatusCode(404));
+    ///
+    /// let report = report.wrap_err("failed to handle request");
+    /// assert_eq!(report.get::<StatusCode>(), Some(&StatusCode(404)));
+    /// ```
+    pub fn insert<T>(&mut self, value: T) -> Option<T>
+    where
+        T: Any + Send + Sync,
+    {
+        header_mut(self.inner.as_mut()).extensions.insert(value)
+    }
+
+    /// Get a reference to the value of type `T` attached to this Report via
+    /// [`insert`][Report::insert].
+    pub fn get<T>(&self) -> Option<&T>
+    where
+        T: Any + Send + Sync,
+    {
+        header(self.inner.as_ref()).extensions.get()
+    }
+
+    /// Get a mutable reference to the value of type `T` attached to this
+    /// Report via [`insert`][Report::insert].
+    pub fn get_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: Any + Send + Sync,
+    {
+        header_mut(self.inner.as_mut()).extensions.get_mut()
+    }
+
+    /// Remove the value of type `T` attached to this Report via
+    /// [`insert`][Report::insert] and return it.
+    pub fn remove<T>(&mut self) -> Option<T>
+    where
+        T: Any + Send + Sync,
+    {
+        header_mut(self.inner.as_mut()).extensions.remove()
+    }
+
+    /// Convert this `Report` into a lightweight, cloneable error holding only
+    /// the rendered messages of its chain.
+    ///
+    /// The handler, and with it any captured backtrace or attached sections,
+    /// is dropped. The returned error displays the same message as the report
+    /// and its [`source`][StdError::source] chain yields the same messages as
+    /// [`Report::chain`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{eyre, RenderedError};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// enum TaskError {
+    ///     Cancelled,
+    ///     Failed(RenderedError),
+    /// }
+    ///
+    /// let report = eyre!("disk full").wrap_err("failed to save");
+    /// let error = TaskError::Failed(report.into_display_error());
+    ///
+    /// if let TaskError::Failed(error) = error.clone() {
+    ///     let chain: Vec<_> = eyre::Chain::new(&error).map(|e| e.to_string()).collect();
+    ///     assert_eq!(chain, ["failed to save", "disk full"]);
+    /// }
+    /// ```
+    pub fn into_display_error(self) -> RenderedError {
+        RenderedError::new(ErrorImpl::error(self.inner.as_ref()))
+    }
+
+    /// Decompose this `Report` into its error and the handler it captured.
+    ///
+    /// Converting a `Report` into a `Box<dyn Error>` discards its handler,
+    /// along with any state the handler captured such as backtraces or
+    /// attached sections. This instead hands ownership of both to the caller,
+    /// e.g. for rendering the report with a custom frontend.
+    ///
+    /// The returned error formats itself without the handler.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{eyre, Report};
+    ///
+    /// let report: Report = eyre!("failed to connect");
+    /// let (error, handler) = report.into_parts();
+    ///
+    /// assert_eq!(error.to_string(), "failed to connect");
+    /// assert!(handler.downcast_ref::<eyre::DefaultHandler>().is_some());
+    /// ```
+    pub fn into_parts(
+        self,
+    ) -> (
+        Box<dyn StdError + Send + Sync + 'static>,
+        Box<dyn EyreHandler>,
+    ) {
+        let mut outer = ManuallyDrop::new(self);
+        let handler = header_mut(outer.inner.as_mut()).handler.take().unwrap();
+        // Safety: the vtable was created for the concrete type of the error
+        // and can be used to move it into a box with the right StdError
+        // vtable. Report's Drop impl is not run as `outer` is `ManuallyDrop`.
+        let error = unsafe { (header(outer.inner.as_ref()).vtable.object_boxed)(outer.inner) };
+
+        (error, handler)
+    }
+
+    /// Attribute the creation of this Report to `location`, rather than to
+    /// the place it was actually constructed at.
+    #[cfg(all(track_caller, feature = "future"))]
+    pub(crate) fn set_location(&mut self, location: &'static core::panic::Location<'static>) {
+        let header = header_mut(self.inner.as_mut());
+        header.location = Some(location);
+        if let Some(handler) = header.handler.as_mut() {
+            handler.track_caller(location);
+        }
+    }
+
+    /// Replace the Handler of this Report.
+    pub(crate) fn set_handler(&mut self, handler: Box<dyn EyreHandler>) {
+        header_mut(self.inner.as_mut()).handler = Some(handler);
+    }
+
     /// Get a reference to the Handler for this Report.
     #[doc(hidden)]
     pub fn context(&self) -> &dyn EyreHandler {
@@ -542,6 +1382,62 @@ struct ErrorVTable {
     object_downcast: unsafe fn(RefPtr<'_, ErrorImpl<()>>, TypeId) -> Option<NonNull<()>>,
     object_downcast_mut: unsafe fn(MutPtr<'_, ErrorImpl<()>>, TypeId) -> Option<NonNull<()>>,
     object_drop_rest: unsafe fn(OwnedPtr<ErrorImpl<()>>, TypeId),
+    #[allow(clippy::type_complexity)]
+    object_context: unsafe fn(
+        RefPtr<'_, ErrorImpl<()>>,
+    )
+        -> Option<(&(dyn Display + Send + Sync + 'static), FrameSource<'_>)>,
+    #[allow(clippy::type_complexity)]
+    object_context_mut:
+        unsafe fn(MutPtr<'_, ErrorImpl<()>>) -> Option<(ContextMessageMut<'_>, FrameSourceMut<'_>)>,
+    object_type_id: unsafe fn(RefPtr<'_, ErrorImpl<()>>) -> TypeId,
+    #[allow(clippy::type_complexity)]
+    object_unwrap_context: Option<unsafe fn(OwnedPtr<ErrorImpl<()>>) -> Report>,
+    object_with_source: unsafe fn(OwnedPtr<ErrorImpl<()>>, Report) -> Report,
+}
+
+/// The vtable of a context layer added by [`Report::wrap_err`] on top of
+/// another report.
+fn context_chain_vtable<D>() -> &'static ErrorVTable
+where
+    D: Display + Send + Sync + 'static,
+{
+    &ErrorVTable {
+        object_drop: object_drop::<ContextError<D, Report>>,
+        object_ref: object_ref::<ContextError<D, Report>>,
+        object_mut: object_mut::<ContextError<D, Report>>,
+        object_boxed: object_boxed::<ContextError<D, Report>>,
+        object_downcast: context_chain_downcast::<D>,
+        object_downcast_mut: context_chain_downcast_mut::<D>,
+        object_drop_rest: context_chain_drop_rest::<D>,
+        object_context: context_chain_context::<D>,
+        object_context_mut: context_chain_context_mut::<D>,
+        object_type_id: context_chain_type_id::<D>,
+        object_unwrap_context: Some(context_chain_unwrap::<D>),
+        object_with_source: context_chain_with_source::<D>,
+    }
+}
+
+/// The vtable of a report created from an implementor of
+/// [`std::error::Error`], with no context messages or sources attached.
+fn std_vtable<E>() -> &'static ErrorVTable
+where
+    E: StdError + Send + Sync + 'static,
+{
+    &ErrorVTable {
+        object_drop: object_drop::<E>,
+        object_ref: object_ref::<E>,
+        object_mut: object_mut::<E>,
+        object_boxed: object_boxed::<E>,
+        object_downcast: object_downcast::<E>,
+        object_downcast_mut: object_downcast_mut::<E>,
+        object_drop_rest: object_drop_front::<E>,
+        object_context: no_context,
+        object_context_mut: no_context_mut,
+        object_type_id: object_type_id::<E>,
+        object_unwrap_context: None,
+        object_with_source: root_with_source,
+    }
 }
 
 /// # Safety
@@ -687,6 +1583,7 @@ where
         None
     }
 }
+
 /// # Safety
 ///
 /// Requires layout of *e to match ErrorImpl<ContextError<D, E>>.
@@ -711,6 +1608,164 @@ where
     }
 }
 
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<ContextError<D, E>>.
+#[allow(clippy::type_complexity)]
+unsafe fn context_context<D, E>(
+    e: RefPtr<'_, ErrorImpl<()>>,
+) -> Option<(&(dyn Display + Send + Sync + 'static), FrameSource<'_>)>
+where
+    D: Display + Send + Sync + 'static,
+    E: StdError + 'static,
+{
+    let unerased = unsafe { e.cast::<ErrorImpl<ContextError<D, E>>>().as_ref() };
+    let source = FrameSource::Error(&unerased._object.error);
+    Some((&unerased._object.msg, source))
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<ContextError<D, E>>.
+#[allow(clippy::type_complexity)]
+unsafe fn context_context_mut<D, E>(
+    e: MutPtr<'_, ErrorImpl<()>>,
+) -> Option<(ContextMessageMut<'_>, FrameSourceMut<'_>)>
+where
+    D: Display + Send + Sync + 'static,
+    E: StdError + Send + Sync + 'static,
+{
+    let unerased = unsafe { e.cast::<ErrorImpl<ContextError<D, E>>>().into_mut() };
+    let ContextError { msg, error } = &mut unerased._object;
+    Some((ContextMessageMut::new(msg), FrameSourceMut::Error(error)))
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<ContextError<D, E>>.
+unsafe fn context_unwrap<D, E>(e: OwnedPtr<ErrorImpl<()>>) -> Report
+where
+    D: 'static,
+    E: StdError + Send + Sync + 'static,
+{
+    // Drop the context message along with the rest of this layer and return
+    // a report of the error it was attached to.
+    let unerased = unsafe { e.cast::<ErrorImpl<ContextError<D, E>>>().into_box() };
+    let location = unerased.header.location;
+    let error = unerased._object.error;
+
+    // Safety: passing vtable that operates on the right type E.
+    let mut report = unsafe { Report::construct(error, std_vtable::<E>(), None) };
+    header_mut(report.inner.as_mut()).location = location;
+    report
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<ContextError<D, E>>.
+unsafe fn context_with_source<D, E>(e: OwnedPtr<ErrorImpl<()>>, source: Report) -> Report
+where
+    D: Display + Send + Sync + 'static,
+    E: StdError + Send + Sync + 'static,
+{
+    // Split the layer into the context message and a report of the error it
+    // was attached to, so the source can be attached below that error.
+    let mut unerased = unsafe { e.cast::<ErrorImpl<ContextError<D, E>>>().into_box() };
+    let handler = unerased.header.handler.take();
+    let extensions = mem::take(&mut unerased.header.extensions);
+    let location = unerased.header.location;
+    let ContextError { msg, error } = unerased._object;
+
+    // Safety: passing vtable that operates on the right type E.
+    let mut error = unsafe { Report::construct(error, std_vtable::<E>(), None) };
+    header_mut(error.inner.as_mut()).location = location;
+
+    let error = ContextError {
+        msg,
+        error: error.attach_source(source),
+    };
+    // Safety: passing vtable that operates on the right type.
+    let mut report = unsafe { Report::construct(error, context_chain_vtable::<D>(), handler) };
+    header_mut(report.inner.as_mut()).extensions = extensions;
+    header_mut(report.inner.as_mut()).location = location;
+    report
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<ContextError<D, Report>>.
+#[allow(clippy::type_complexity)]
+unsafe fn context_chain_context<D>(
+    e: RefPtr<'_, ErrorImpl<()>>,
+) -> Option<(&(dyn Display + Send + Sync + 'static), FrameSource<'_>)>
+where
+    D: Display + Send + Sync + 'static,
+{
+    let unerased = unsafe { e.cast::<ErrorImpl<ContextError<D, Report>>>().as_ref() };
+    let source = FrameSource::Report(unerased._object.error.inner.as_ref());
+    Some((&unerased._object.msg, source))
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<ContextError<D, Report>>.
+#[allow(clippy::type_complexity)]
+unsafe fn context_chain_context_mut<D>(
+    e: MutPtr<'_, ErrorImpl<()>>,
+) -> Option<(ContextMessageMut<'_>, FrameSourceMut<'_>)>
+where
+    D: Display + Send + Sync + 'static,
+{
+    let unerased = unsafe { e.cast::<ErrorImpl<ContextError<D, Report>>>().into_mut() };
+    // Borrow the message and the wrapped report separately so both can be
+    // handed out mutably at the same time.
+    let ContextError { msg, error } = &mut unerased._object;
+    Some((
+        ContextMessageMut::new(msg),
+        FrameSourceMut::Report(error.inner.as_mut()),
+    ))
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<ContextError<D, Report>>.
+unsafe fn context_chain_type_id<D>(e: RefPtr<'_, ErrorImpl<()>>) -> TypeId
+where
+    D: 'static,
+{
+    let unerased = unsafe { e.cast::<ErrorImpl<ContextError<D, Report>>>().as_ref() };
+    // Recurse down the context chain to the error the report was created from.
+    let source = &unerased._object.error;
+    unsafe { (source.vtable().object_type_id)(source.inner.as_ref()) }
+}
+
+/// Returns the type id of the error object, ignoring `e`.
+unsafe fn object_type_id<E>(e: RefPtr<'_, ErrorImpl<()>>) -> TypeId
+where
+    E: 'static,
+{
+    let _ = e;
+    TypeId::of::<E>()
+}
+
+/// Used for error objects which do not carry a context message of their own.
+#[allow(clippy::type_complexity)]
+unsafe fn no_context(
+    e: RefPtr<'_, ErrorImpl<()>>,
+) -> Option<(&(dyn Display + Send + Sync + 'static), FrameSource<'_>)> {
+    let _ = e;
+    None
+}
+
+/// Used for error objects which do not carry a context message of their own.
+#[allow(clippy::type_complexity)]
+unsafe fn no_context_mut(
+    e: MutPtr<'_, ErrorImpl<()>>,
+) -> Option<(ContextMessageMut<'_>, FrameSourceMut<'_>)> {
+    let _ = e;
+    None
+}
+
 /// # Safety
 ///
 /// Requires layout of *e to match ErrorImpl<ContextError<D, Report>>.
@@ -753,6 +1808,19 @@ where
     }
 }
 
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<ContextError<D, Report>>.
+unsafe fn context_chain_unwrap<D>(e: OwnedPtr<ErrorImpl<()>>) -> Report
+where
+    D: 'static,
+{
+    // Drop the context message along with the rest of this layer and return
+    // the report it was attached to.
+    let unerased = unsafe { e.cast::<ErrorImpl<ContextError<D, Report>>>().into_box() };
+    unerased._object.error
+}
+
 /// # Safety
 ///
 /// Requires layout of *e to match ErrorImpl<ContextError<D, Report>>.
@@ -783,10 +1851,140 @@ where
     }
 }
 
+/// Wraps the layer holding the error a report was created from, so that the
+/// wrapped error is followed by `source` in the chain.
+unsafe fn root_with_source(e: OwnedPtr<ErrorImpl<()>>, source: Report) -> Report {
+    let mut error = Report { inner: e };
+
+    // The new layer takes the place of `error`, including its header.
+    let handler = header_mut(error.inner.as_mut()).handler.take();
+    let extensions = mem::take(&mut header_mut(error.inner.as_mut()).extensions);
+    let location = header(error.inner.as_ref()).location;
+
+    let vtable = &ErrorVTable {
+        object_drop: object_drop::<SourceError>,
+        object_ref: object_ref::<SourceError>,
+        object_mut: object_mut::<SourceError>,
+        object_boxed: object_boxed::<SourceError>,
+        object_downcast: source_chain_downcast,
+        object_downcast_mut: source_chain_downcast_mut,
+        object_drop_rest: source_chain_drop_rest,
+        object_context: no_context,
+        object_context_mut: no_context_mut,
+        object_type_id: source_chain_type_id,
+        object_unwrap_context: None,
+        object_with_source: source_chain_with_source,
+    };
+
+    let error = SourceError::new(error, source);
+    // Safety: passing vtable that operates on the right type.
+    let mut report = unsafe { Report::construct(error, vtable, handler) };
+    header_mut(report.inner.as_mut()).extensions = extensions;
+    header_mut(report.inner.as_mut()).location = location;
+    report
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<ContextError<D, Report>>.
+unsafe fn context_chain_with_source<D>(e: OwnedPtr<ErrorImpl<()>>, source: Report) -> Report
+where
+    D: 'static,
+{
+    // Attach the source to the report this context message was attached to.
+    let mut unerased = unsafe { e.cast::<ErrorImpl<ContextError<D, Report>>>().into_box() };
+    let error = unerased._object.error;
+    unerased._object.error = error.attach_source(source);
+    Report {
+        inner: OwnedPtr::from_boxed(unerased).cast(),
+    }
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<SourceError>.
+unsafe fn source_chain_with_source(e: OwnedPtr<ErrorImpl<()>>, source: Report) -> Report {
+    // Append below the source that was attached last.
+    let mut unerased = unsafe { e.cast::<ErrorImpl<SourceError>>().into_box() };
+    unerased._object.invalidate_links();
+    let previous = unerased._object.source;
+    unerased._object.source = previous.attach_source(source);
+    Report {
+        inner: OwnedPtr::from_boxed(unerased).cast(),
+    }
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<SourceError>.
+unsafe fn source_chain_downcast(
+    e: RefPtr<'_, ErrorImpl<()>>,
+    target: TypeId,
+) -> Option<NonNull<()>> {
+    let unerased = unsafe { e.cast::<ErrorImpl<SourceError>>().as_ref() };
+    let SourceError { error, source, .. } = &unerased._object;
+    unsafe {
+        (error.vtable().object_downcast)(error.inner.as_ref(), target)
+            .or_else(|| (source.vtable().object_downcast)(source.inner.as_ref(), target))
+    }
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<SourceError>.
+unsafe fn source_chain_downcast_mut(
+    e: MutPtr<'_, ErrorImpl<()>>,
+    target: TypeId,
+) -> Option<NonNull<()>> {
+    let unerased = unsafe { e.cast::<ErrorImpl<SourceError>>().into_mut() };
+    // The caller may modify the value it finds.
+    unerased._object.invalidate_links();
+    let SourceError { error, source, .. } = &mut unerased._object;
+    unsafe {
+        (error.vtable().object_downcast_mut)(error.inner.as_mut(), target)
+            .or_else(|| (source.vtable().object_downcast_mut)(source.inner.as_mut(), target))
+    }
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<SourceError>.
+unsafe fn source_chain_drop_rest(e: OwnedPtr<ErrorImpl<()>>, target: TypeId) {
+    // Called after downcasting by value to a value in either of the reports
+    // and doing a ptr::read to take ownership of that value. Downcasting
+    // looks at the wrapped error first, so it holds the value if it has one
+    // of the right type.
+    let unerased = unsafe { e.cast::<ErrorImpl<SourceError>>().into_box() };
+    let SourceError { error, source, .. } = unerased._object;
+    let found_in_error =
+        unsafe { (error.vtable().object_downcast)(error.inner.as_ref(), target) }.is_some();
+    let (rest, other) = if found_in_error {
+        (ManuallyDrop::new(error), source)
+    } else {
+        (ManuallyDrop::new(source), error)
+    };
+
+    drop(other);
+    // Recursively drop the report holding the value using the same target typeid.
+    unsafe { (rest.vtable().object_drop_rest)(rest.inner, target) }
+}
+
+/// # Safety
+///
+/// Requires layout of *e to match ErrorImpl<SourceError>.
+unsafe fn source_chain_type_id(e: RefPtr<'_, ErrorImpl<()>>) -> TypeId {
+    let unerased = unsafe { e.cast::<ErrorImpl<SourceError>>().as_ref() };
+    // The type of the error the report was created from.
+    let error = &unerased._object.error;
+    unsafe { (error.vtable().object_type_id)(error.inner.as_ref()) }
+}
+
 #[repr(C)]
 pub(crate) struct ErrorHeader {
     vtable: &'static ErrorVTable,
     pub(crate) handler: Option<Box<dyn EyreHandler>>,
+    extensions: Extensions,
+    location: Option<&'static core::panic::Location<'static>>,
 }
 
 // repr C to ensure that E remains in the final position.
@@ -846,9 +2044,38 @@ impl ErrorImpl<()> {
         Chain::new(Self::error(this))
     }
 
+    /// Returns the context message attached by this layer, along with the
+    /// error it was attached to, if this layer was created by `wrap_err`.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn context(
+        this: RefPtr<'_, Self>,
+    ) -> Option<(&(dyn Display + Send + Sync + 'static), FrameSource<'_>)> {
+        unsafe { (header(this).vtable.object_context)(this) }
+    }
+
+    /// Mutable counterpart of [`ErrorImpl::context`].
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn context_mut(
+        this: MutPtr<'_, Self>,
+    ) -> Option<(ContextMessageMut<'_>, FrameSourceMut<'_>)> {
+        unsafe { (header_mut(this).vtable.object_context_mut)(this) }
+    }
+
     pub(crate) fn header(this: RefPtr<'_, ErrorImpl>) -> &ErrorHeader {
         header(this)
     }
+
+    /// The type of the error this layer's report was created from.
+    pub(crate) fn type_id(this: RefPtr<'_, Self>) -> TypeId {
+        unsafe { (header(this).vtable.object_type_id)(this) }
+    }
+
+    /// Where this layer was created, if known.
+    pub(crate) fn location(
+        this: RefPtr<'_, Self>,
+    ) -> Option<&'static core::panic::Location<'static>> {
+        header(this).location
+    }
 }
 
 impl<E> StdError for ErrorImpl<E>
@@ -914,5 +2141,13 @@ impl AsRef<dyn StdError> for Report {
     }
 }
 
+// The auto traits are not inferred for `Report` because it holds the error and
+// handler as type-erased trait objects. eyre never leaves either of them
+// partially updated across a call that could panic, so a report observed after
+// `catch_unwind` is as consistent as any other owned value. This matches the
+// behaviour of `anyhow::Error`.
+impl UnwindSafe for Report {}
+impl RefUnwindSafe for Report {}
+
 #[cfg(feature = "pyo3")]
 mod pyo3_compat;
@@ -1,7 +1,35 @@
 use crate::Report;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::{PyErr, Python};
 
-impl From<Report> for pyo3::PyErr {
+// Each error of the report's chain becomes a `RuntimeError` whose `__cause__`
+// is the exception of the next one, so Python tracebacks show the chain the
+// same way as for exceptions raised with `raise ... from ...`. Python
+// exceptions that were converted into the report are reused as they are,
+// keeping their own type, traceback and causes.
+impl From<Report> for PyErr {
     fn from(error: Report) -> Self {
-        pyo3::exceptions::PyRuntimeError::new_err(format!("{:?}", error))
+        Python::with_gil(|py| {
+            let mut cause = None;
+            let mut messages = Vec::new();
+
+            for error in error.chain() {
+                if let Some(exception) = error.downcast_ref::<PyErr>() {
+                    cause = Some(exception.clone_ref(py));
+                    break;
+                }
+                messages.push(error.to_string());
+            }
+
+            messages
+                .into_iter()
+                .rev()
+                .fold(cause, |cause, message| {
+                    let exception = PyRuntimeError::new_err(message);
+                    exception.set_cause(py, cause);
+                    Some(exception)
+                })
+                .expect("the chain of a report is never empty")
+        })
     }
 }
@@ -0,0 +1,158 @@
+use crate::{Report, Result, StdError};
+use core::convert::TryFrom;
+use core::fmt;
+use std::process;
+
+/// The process exit code attached to a [`Report`] via
+/// [`Report::with_exit_code`].
+///
+/// `ExitCode` is stored as a regular report extension, so it can also be read
+/// with [`Report::get`] and survives wrapping the report with additional
+/// context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExitCode(pub i32);
+
+impl Report {
+    /// Attach the exit code the process should exit with if this report
+    /// reaches [`eyre::run`][crate::run].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eyre::eyre;
+    ///
+    /// let report = eyre!("config file not found")
+    ///     .with_exit_code(78)
+    ///     .wrap_err("failed to start");
+    ///
+    /// assert_eq!(report.exit_code(), Some(78));
+    /// ```
+    pub fn with_exit_code(mut self, code: i32) -> Self {
+        self.insert(ExitCode(code));
+        self
+    }
+
+    /// The exit code attached to this report via
+    /// [`with_exit_code`][Report::with_exit_code], if any.
+    pub fn exit_code(&self) -> Option<i32> {
+        self.get::<ExitCode>().map(|code| code.0)
+    }
+}
+
+/// Run `main` and exit the process with a code determined by its result.
+///
+/// If `main` fails, the report is printed to stderr and the process exits
+/// with a code chosen like for [`MainResult`]. Otherwise the process exits
+/// with `0`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use eyre::eyre;
+///
+/// fn main() {
+///     eyre::run(|| {
+///         let path = std::env::args().nth(1).ok_or_else(|| {
+///             eyre!("usage: cat <path>").with_exit_code(64)
+///         })?;
+///         print!("{}", std::fs::read_to_string(path)?);
+///         Ok(())
+///     })
+/// }
+/// ```
+pub fn run<F>(main: F) -> !
+where
+    F: FnOnce() -> Result<()>,
+{
+    let code = match main() {
+        Ok(()) => 0,
+        Err(report) => report_exit(&report),
+    };
+
+    process::exit(code)
+}
+
+/// The return type of a `main` function whose error report is printed by the
+/// installed handler.
+///
+/// Returning an `eyre::Result` from `main` prints the report with
+/// `Error: {:?}` and always exits with `1`. `MainResult` instead lets the
+/// handler of the report format it via
+/// [`EyreHandler::exit_report`](crate::EyreHandler::exit_report), so handlers
+/// whose reports already start with a label don't print it twice. The
+/// process exits with the code attached via [`Report::with_exit_code`], the
+/// code chosen by [`EyreHandler::exit_code`](crate::EyreHandler::exit_code),
+/// or `1`, in this order.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use eyre::{eyre, MainResult, Result};
+///
+/// fn main() -> MainResult {
+///     try_main().into()
+/// }
+///
+/// fn try_main() -> Result<()> {
+///     let path = std::env::args()
+///         .nth(1)
+///         .ok_or_else(|| eyre!("usage: cat <path>").with_exit_code(64))?;
+///     print!("{}", std::fs::read_to_string(path)?);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+#[must_use]
+pub struct MainResult(Result<()>);
+
+impl<E> From<core::result::Result<(), E>> for MainResult
+where
+    E: Into<Report>,
+{
+    fn from(result: core::result::Result<(), E>) -> Self {
+        MainResult(result.map_err(Into::into))
+    }
+}
+
+impl From<Report> for MainResult {
+    fn from(report: Report) -> Self {
+        MainResult(Err(report))
+    }
+}
+
+impl process::Termination for MainResult {
+    fn report(self) -> process::ExitCode {
+        match self.0 {
+            Ok(()) => process::ExitCode::SUCCESS,
+            Err(report) => {
+                let code = report_exit(&report);
+                // `ExitCode` can only hold codes that fit into a `u8`
+                match u8::try_from(code) {
+                    Ok(code) => process::ExitCode::from(code),
+                    Err(_) => process::exit(code),
+                }
+            }
+        }
+    }
+}
+
+/// Print the report of a failed `main` and return the code to exit with.
+fn report_exit(report: &Report) -> i32 {
+    let error: &(dyn StdError + 'static) = &**report;
+    let handler = report.handler();
+
+    eprintln!("{}", ExitReport(report));
+
+    report
+        .exit_code()
+        .or_else(|| handler.exit_code(error))
+        .unwrap_or(1)
+}
+
+struct ExitReport<'a>(&'a Report);
+
+impl fmt::Display for ExitReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.handler().exit_report(&**self.0, f)
+    }
+}
@@ -0,0 +1,57 @@
+use alloc::boxed::Box;
+use core::any::{Any, TypeId};
+use std::collections::HashMap;
+
+type AnyMap = HashMap<TypeId, Box<dyn Any + Send + Sync>>;
+
+/// Typed values attached to a [`Report`][crate::Report] via
+/// [`Report::insert`][crate::Report::insert].
+///
+/// The map is only allocated once the first value is inserted, so reports
+/// which never carry extensions pay for a single null pointer.
+#[derive(Default)]
+pub(crate) struct Extensions {
+    map: Option<Box<AnyMap>>,
+}
+
+impl Extensions {
+    pub(crate) fn insert<T>(&mut self, value: T) -> Option<T>
+    where
+        T: Any + Send + Sync,
+    {
+        self.map
+            .get_or_insert_with(Box::default)
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|previous| previous.downcast().ok())
+            .map(|previous| *previous)
+    }
+
+    pub(crate) fn get<T>(&self) -> Option<&T>
+    where
+        T: Any + Send + Sync,
+    {
+        self.map.as_ref()?.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    pub(crate) fn get_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: Any + Send + Sync,
+    {
+        self.map
+            .as_mut()?
+            .get_mut(&TypeId::of::<T>())?
+            .downcast_mut()
+    }
+
+    pub(crate) fn remove<T>(&mut self) -> Option<T>
+    where
+        T: Any + Send + Sync,
+    {
+        self.map
+            .as_mut()?
+            .remove(&TypeId::of::<T>())?
+            .downcast()
+            .ok()
+            .map(|value| *value)
+    }
+}
@@ -0,0 +1,343 @@
+//! Passing reports across dynamic library boundaries
+//!
+//! The layout of [`Report`] depends on the compiler and the version of eyre a
+//! library was built with, so plugins compiled as `cdylib`s can't hand a
+//! `Report` to their host directly. [`EyreReportRef`] is a C-compatible handle
+//! which owns a report and only accesses it through a table of `extern "C"`
+//! functions provided by the side that created it, see
+//! [`EyreReportVTable`].
+//!
+//! The host can read the chain of the report through the handle, or turn it
+//! back into a `Report` with [`EyreReportRef::into_report`], whose chain
+//! formats the foreign errors on demand instead of copying them to strings.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(not(feature = "auto-install"))]
+//! # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+//! use eyre::ffi::EyreReportRef;
+//! use eyre::{eyre, Report};
+//!
+//! // In the plugin
+//! extern "C" fn load_plugin() -> EyreReportRef {
+//!     EyreReportRef::new(eyre!("missing symbol").wrap_err("failed to load plugin"))
+//! }
+//!
+//! // In the host
+//! let handle = load_plugin();
+//! assert_eq!(handle.chain(), ["failed to load plugin", "missing symbol"]);
+//!
+//! let report: Report = unsafe { handle.into_report() };
+//! assert_eq!(report.to_string(), "failed to load plugin");
+//! assert_eq!(report.root_cause().to_string(), "missing symbol");
+//! ```
+use crate::Report;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Display, Write};
+use std::error::Error as StdError;
+use std::sync::Arc;
+
+/// The version of [`EyreReportVTable`] created by this version of eyre.
+///
+/// Later versions only append fields to the table, so a table with a higher
+/// version can be used in place of an older one.
+pub const EYRE_REPORT_VTABLE_VERSION: u32 = 1;
+
+/// The callback the text of an error is written to by
+/// [`EyreReportVTable::write_message`], in UTF-8 encoded chunks.
+///
+/// `context` is the pointer passed to `write_message`. Returning `false`
+/// aborts writing.
+pub type EyreWriteFn =
+    unsafe extern "C" fn(context: *mut c_void, ptr: *const u8, len: usize) -> bool;
+
+/// The functions accessing the report behind an [`EyreReportRef`].
+///
+/// The functions are provided by the library which created the handle, so
+/// they operate on the report with the layout that library was compiled
+/// with.
+#[repr(C)]
+#[derive(Debug)]
+pub struct EyreReportVTable {
+    /// The version of this table, see [`EYRE_REPORT_VTABLE_VERSION`]
+    pub version: u32,
+    /// The number of errors in the chain of the report, or `1` if walking
+    /// the chain panicked
+    pub chain_len: unsafe extern "C" fn(data: *const c_void) -> usize,
+    /// Write the `Display` output of the error at `index` in the chain of the
+    /// report to `write`, returning `false` if `index` is out of bounds or
+    /// `write` failed
+    pub write_message: unsafe extern "C" fn(
+        data: *const c_void,
+        index: usize,
+        write: EyreWriteFn,
+        context: *mut c_void,
+    ) -> bool,
+    /// Write the `Debug` output of the report, as rendered by its handler, to
+    /// `write`, returning `false` if `write` failed
+    pub write_debug:
+        unsafe extern "C" fn(data: *const c_void, write: EyreWriteFn, context: *mut c_void) -> bool,
+    /// Drop the report
+    pub drop: unsafe extern "C" fn(data: *mut c_void),
+}
+
+/// A C-compatible handle owning a [`Report`], see the
+/// [module documentation](self).
+#[repr(C)]
+pub struct EyreReportRef {
+    data: *mut c_void,
+    vtable: &'static EyreReportVTable,
+}
+
+// Safety: the data is a `Report`, which is `Send` and `Sync`, and the vtable
+// functions only access it through shared references, or when dropping it.
+unsafe impl Send for EyreReportRef {}
+unsafe impl Sync for EyreReportRef {}
+
+static VTABLE: EyreReportVTable = EyreReportVTable {
+    version: EYRE_REPORT_VTABLE_VERSION,
+    chain_len: report_chain_len,
+    write_message: report_write_message,
+    write_debug: report_write_debug,
+    drop: report_drop,
+};
+
+impl EyreReportRef {
+    /// Move `report` into a handle which can be passed to other libraries.
+    pub fn new(report: Report) -> Self {
+        EyreReportRef {
+            data: Box::into_raw(Box::new(report)).cast(),
+            vtable: &VTABLE,
+        }
+    }
+
+    /// Create a handle from its raw parts, e.g. a handle received from a
+    /// library using a different version of eyre.
+    ///
+    /// # Safety
+    ///
+    /// The functions of `vtable` must be safe to call with `data` until its
+    /// `drop` function was called, from any thread, and must not unwind.
+    pub unsafe fn from_raw_parts(data: *mut c_void, vtable: &'static EyreReportVTable) -> Self {
+        EyreReportRef { data, vtable }
+    }
+
+    /// Split the handle into its raw parts without dropping the report.
+    pub fn into_raw_parts(self) -> (*mut c_void, &'static EyreReportVTable) {
+        let parts = (self.data, self.vtable);
+        core::mem::forget(self);
+        parts
+    }
+
+    /// The number of errors in the chain of the report.
+    pub fn chain_len(&self) -> usize {
+        // Safety: `data` is valid for the functions of `vtable` until dropped
+        unsafe { (self.vtable.chain_len)(self.data) }
+    }
+
+    /// The message of the error at `index` in the chain of the report, or
+    /// `None` if `index` is out of bounds.
+    pub fn message(&self, index: usize) -> Option<String> {
+        let mut message = String::new();
+        let written = self.write_message(index, &mut message);
+        written.ok().map(|_| message)
+    }
+
+    /// The messages of all errors in the chain of the report, outermost
+    /// first.
+    pub fn chain(&self) -> Vec<String> {
+        (0..self.chain_len())
+            .filter_map(|index| self.message(index))
+            .collect()
+    }
+
+    /// The `Debug` output of the report, as rendered by the handler of the
+    /// library which created it.
+    pub fn debug(&self) -> String {
+        let mut debug = String::new();
+        let _ = self.write_debug(&mut debug);
+        debug
+    }
+
+    fn write_message(&self, index: usize, out: &mut dyn Write) -> fmt::Result {
+        let mut out = out;
+        let context: *mut &mut dyn Write = &mut out;
+        // Safety: `data` is valid for the functions of `vtable` until dropped,
+        // and `write_to` is called with the `context` it expects
+        let written =
+            unsafe { (self.vtable.write_message)(self.data, index, write_to, context.cast()) };
+        if written {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+
+    fn write_debug(&self, out: &mut dyn Write) -> fmt::Result {
+        let mut out = out;
+        let context: *mut &mut dyn Write = &mut out;
+        // Safety: as in `write_message`
+        let written = unsafe { (self.vtable.write_debug)(self.data, write_to, context.cast()) };
+        if written {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+
+    /// Turn the handle back into a [`Report`].
+    ///
+    /// Handles created by this copy of eyre give back the original report.
+    /// Reports of other libraries are wrapped in a report whose chain reads
+    /// the messages of the foreign errors through the handle, and whose
+    /// handler is created by the hook installed in this library.
+    ///
+    /// # Safety
+    ///
+    /// The handle must have been created by [`EyreReportRef::new`] in this
+    /// or another library, or fulfill the requirements of
+    /// [`from_raw_parts`](EyreReportRef::from_raw_parts).
+    #[cfg_attr(track_caller, track_caller)]
+    pub unsafe fn into_report(self) -> Report {
+        if core::ptr::eq(self.vtable, &VTABLE) {
+            let (data, _) = self.into_raw_parts();
+            // Safety: handles with our vtable were created by `new`
+            return *unsafe { Box::from_raw(data.cast::<Report>()) };
+        }
+
+        let handle = Arc::new(self);
+        let len = handle.chain_len().max(1);
+        let error = (0..len).rev().fold(None, |source, index| {
+            Some(ForeignError {
+                handle: handle.clone(),
+                index,
+                source: source.map(Box::new),
+            })
+        });
+        Report::from_std(error.expect("chain is never empty"))
+    }
+}
+
+impl From<Report> for EyreReportRef {
+    fn from(report: Report) -> Self {
+        EyreReportRef::new(report)
+    }
+}
+
+impl Debug for EyreReportRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_debug(f)
+    }
+}
+
+impl Drop for EyreReportRef {
+    fn drop(&mut self) {
+        // Safety: `data` is valid until dropped, which only happens here
+        unsafe { (self.vtable.drop)(self.data) }
+    }
+}
+
+/// An error in the chain of a report behind an [`EyreReportRef`] of another
+/// library
+struct ForeignError {
+    handle: Arc<EyreReportRef>,
+    index: usize,
+    source: Option<Box<ForeignError>>,
+}
+
+impl Display for ForeignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.handle.write_message(self.index, f)
+    }
+}
+
+impl Debug for ForeignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.index == 0 {
+            self.handle.write_debug(f)
+        } else {
+            Display::fmt(self, f)
+        }
+    }
+}
+
+impl StdError for ForeignError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source.as_deref().map(|source| source as _)
+    }
+}
+
+unsafe extern "C" fn write_to(context: *mut c_void, ptr: *const u8, len: usize) -> bool {
+    // Safety: `context` is the `&mut dyn Write` passed by `write_message` or
+    // `write_debug`, and `ptr` points to `len` bytes of UTF-8
+    let (out, chunk) = unsafe {
+        let out = &mut *context.cast::<&mut dyn Write>();
+        let chunk = core::slice::from_raw_parts(ptr, len);
+        (out, core::str::from_utf8_unchecked(chunk))
+    };
+    out.write_str(chunk).is_ok()
+}
+
+/// Writes to an [`EyreWriteFn`]
+struct Writer {
+    write: EyreWriteFn,
+    context: *mut c_void,
+}
+
+impl Write for Writer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // Safety: `write` is called with the `context` it was passed with
+        if unsafe { (self.write)(self.context, s.as_ptr(), s.len()) } {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
+
+unsafe extern "C" fn report_chain_len(data: *const c_void) -> usize {
+    // Safety: `data` is the report boxed by `EyreReportRef::new`
+    let report = unsafe { &*data.cast::<Report>() };
+    // The report itself always counts, even if one of its sources panics.
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| report.chain().len())).unwrap_or(1)
+}
+
+unsafe extern "C" fn report_write_message(
+    data: *const c_void,
+    index: usize,
+    write: EyreWriteFn,
+    context: *mut c_void,
+) -> bool {
+    // Safety: `data` is the report boxed by `EyreReportRef::new`
+    let report = unsafe { &*data.cast::<Report>() };
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        match report.chain().nth(index) {
+            Some(error) => write!(Writer { write, context }, "{}", error).is_ok(),
+            None => false,
+        }
+    }))
+    .unwrap_or(false)
+}
+
+unsafe extern "C" fn report_write_debug(
+    data: *const c_void,
+    write: EyreWriteFn,
+    context: *mut c_void,
+) -> bool {
+    // Safety: `data` is the report boxed by `EyreReportRef::new`
+    let report = unsafe { &*data.cast::<Report>() };
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        write!(Writer { write, context }, "{:?}", report).is_ok()
+    }))
+    .unwrap_or(false)
+}
+
+unsafe extern "C" fn report_drop(data: *mut c_void) {
+    // Safety: `data` is the report boxed by `EyreReportRef::new`
+    let report = unsafe { Box::from_raw(data.cast::<Report>()) };
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(report)));
+}
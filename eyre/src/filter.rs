@@ -0,0 +1,259 @@
+use crate::{DefaultHandler, ErrorHook, InstallError};
+use core::fmt;
+use std::sync::Arc;
+
+/// A callback which removes uninteresting frames from a backtrace printed by
+/// [`DefaultHandler`].
+///
+/// Filters receive the frames that previous filters retained and remove the
+/// frames they want hidden, e.g. with [`Vec::retain`]. The remaining frames
+/// are always printed in their original order.
+pub type FrameFilter = dyn Fn(&mut Vec<&BacktraceFrame>) + Send + Sync + 'static;
+
+/// A single frame of a backtrace printed by [`DefaultHandler`].
+#[derive(Debug)]
+pub struct BacktraceFrame {
+    /// The position of the frame in the backtrace, starting from 1.
+    pub n: usize,
+    /// The demangled symbol name of the frame, if it was resolved.
+    pub name: Option<String>,
+    /// The line number of the frame, if known.
+    pub lineno: Option<u32>,
+    /// The source file of the frame, if known.
+    pub filename: Option<String>,
+    text: String,
+}
+
+impl BacktraceFrame {
+    /// Heuristically determine whether the frame is part of the panic and
+    /// backtrace capturing machinery rather than the code that failed.
+    pub fn is_post_panic_code(&self) -> bool {
+        const SYM_PREFIXES: &[&str] = &[
+            "_rust_begin_unwind",
+            "rust_begin_unwind",
+            "core::result::unwrap_failed",
+            "core::option::expect_failed",
+            "core::panicking::",
+            "std::panicking::",
+            "std::backtrace::Backtrace::capture",
+            "std::backtrace::Backtrace::create",
+            "std::backtrace_rs::",
+        ];
+
+        match self.name.as_ref() {
+            Some(name) => SYM_PREFIXES.iter().any(|x| name.starts_with(x)),
+            None => false,
+        }
+    }
+
+    /// Heuristically determine whether the frame is part of the language
+    /// runtime which calls `main` or runs tests.
+    pub fn is_runtime_init_code(&self) -> bool {
+        const SYM_PREFIXES: &[&str] = &[
+            "std::rt::lang_start",
+            "std::sys_common::backtrace::__rust_begin_short_backtrace",
+            "std::sys::backtrace::__rust_begin_short_backtrace",
+            "test::run_test::run_test_inner::",
+            "test::__rust_begin_short_backtrace",
+        ];
+
+        match self.name.as_ref() {
+            Some(name) => SYM_PREFIXES.iter().any(|x| name.starts_with(x)),
+            None => false,
+        }
+    }
+
+    /// Split the `Display` output of a `std::backtrace::Backtrace` into
+    /// frames, as std does not provide stable access to them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eyre::BacktraceFrame;
+    ///
+    /// let frames = BacktraceFrame::parse(
+    ///     "   0: app::main\n             at ./src/main.rs:4:5\n   1: std::rt::lang_start",
+    /// );
+    ///
+    /// assert_eq!(frames.len(), 2);
+    /// assert_eq!(frames[0].name.as_deref(), Some("app::main"));
+    /// assert_eq!(frames[0].filename.as_deref(), Some("./src/main.rs"));
+    /// assert_eq!(frames[0].lineno, Some(4));
+    /// assert_eq!(frames[1].lineno, None);
+    /// ```
+    pub fn parse(trace: &str) -> Vec<BacktraceFrame> {
+        let mut frames: Vec<BacktraceFrame> = Vec::new();
+
+        for line in trace.lines() {
+            let trimmed = line.trim_start();
+
+            if let Some(location) = trimmed.strip_prefix("at ") {
+                if let Some(frame) = frames.last_mut() {
+                    // Locations look like `path:line:column`, though the column
+                    // and line may be missing
+                    let mut rest = location;
+                    let mut numbers = Vec::new();
+                    while let Some((head, tail)) = rest.rsplit_once(':') {
+                        match tail.parse::<u32>() {
+                            Ok(number) if numbers.len() < 2 => numbers.push(number),
+                            _ => break,
+                        }
+                        rest = head;
+                    }
+
+                    frame.filename = Some(rest.into());
+                    frame.lineno = numbers.last().copied();
+                }
+            } else if let Some((index, name)) = trimmed.split_once(": ") {
+                if index.parse::<usize>().is_ok() {
+                    frames.push(BacktraceFrame {
+                        n: frames.len() + 1,
+                        name: Some(name.to_string()),
+                        lineno: None,
+                        filename: None,
+                        text: String::new(),
+                    });
+                }
+            }
+
+            if let Some(frame) = frames.last_mut() {
+                if !frame.text.is_empty() {
+                    frame.text.push('\n');
+                }
+                frame.text.push_str(line);
+            }
+        }
+
+        frames
+    }
+}
+
+/// Builder for a hook which constructs [`DefaultHandler`]s that filter the
+/// frames of the backtraces they print.
+///
+/// # Example
+///
+/// ```rust
+/// use eyre::DefaultHook;
+///
+/// DefaultHook::new()
+///     .add_default_filters()
+///     .add_frame_filter(Box::new(|frames| {
+///         frames.retain(|frame| {
+///             !matches!(&frame.name, Some(name) if name.starts_with("tokio::"))
+///         })
+///     }))
+///     .install()
+///     .expect("handler already installed");
+/// ```
+#[derive(Default)]
+pub struct DefaultHook {
+    filters: Vec<Box<FrameFilter>>,
+}
+
+impl DefaultHook {
+    /// Construct a `DefaultHook` without any frame filters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a custom filter to the set of frame filters.
+    pub fn add_frame_filter(mut self, filter: Box<FrameFilter>) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Add the default set of filters, which hide the frames of the language
+    /// runtime, the frames after a panic or backtrace capture started, and
+    /// the frames of `eyre` itself.
+    pub fn add_default_filters(self) -> Self {
+        self.add_frame_filter(Box::new(default_frame_filter))
+            .add_frame_filter(Box::new(eyre_frame_filter))
+    }
+
+    /// Convert the builder into a hook which can be passed to
+    /// [`set_hook`](crate::set_hook).
+    pub fn into_hook(self) -> ErrorHook {
+        let filters: Option<Arc<[Box<FrameFilter>]>> = if self.filters.is_empty() {
+            None
+        } else {
+            Some(self.filters.into())
+        };
+
+        Box::new(move |error| DefaultHandler::with_filters(error, filters.clone()))
+    }
+
+    /// Install the hook as the global error report hook.
+    pub fn install(self) -> Result<(), InstallError> {
+        crate::set_hook(self.into_hook())
+    }
+}
+
+impl fmt::Debug for DefaultHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultHook")
+            .field("filters", &self.filters.len())
+            .finish()
+    }
+}
+
+fn default_frame_filter(frames: &mut Vec<&BacktraceFrame>) {
+    let bottom_cutoff = frames
+        .iter()
+        .position(|x| x.is_runtime_init_code())
+        .unwrap_or(frames.len());
+
+    // Only look above the runtime init code, the runtime catches panics too
+    let top_cutoff = frames[..bottom_cutoff]
+        .iter()
+        .rposition(|x| x.is_post_panic_code())
+        .map(|x| x + 1)
+        .unwrap_or(0);
+
+    frames.truncate(bottom_cutoff);
+    frames.drain(..top_cutoff);
+}
+
+fn eyre_frame_filter(frames: &mut Vec<&BacktraceFrame>) {
+    const SYM_PREFIXES: &[&str] = &["eyre::", "<eyre::"];
+
+    frames.retain(|frame| match frame.name.as_ref() {
+        Some(name) => !SYM_PREFIXES.iter().any(|x| name.starts_with(x)),
+        None => true,
+    });
+}
+
+/// Formats a backtrace with the frames rejected by `filters` replaced by a
+/// note saying how many were hidden.
+#[cfg_attr(not(generic_member_access), allow(dead_code))]
+pub(crate) struct FilteredBacktrace<'a> {
+    pub(crate) trace: String,
+    pub(crate) filters: &'a [Box<FrameFilter>],
+}
+
+impl fmt::Display for FilteredBacktrace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frames = BacktraceFrame::parse(&self.trace);
+        let mut retained: Vec<&BacktraceFrame> = frames.iter().collect();
+        for filter in self.filters {
+            filter(&mut retained);
+        }
+
+        // Don't let filters mess with the order
+        retained.sort_by_key(|frame| frame.n);
+
+        let hidden = |f: &mut fmt::Formatter<'_>, count: usize| match count {
+            0 => Ok(()),
+            1 => writeln!(f, "      ... 1 frame hidden ..."),
+            _ => writeln!(f, "      ... {} frames hidden ...", count),
+        };
+
+        let mut next = 1;
+        for frame in retained {
+            hidden(f, frame.n - next)?;
+            writeln!(f, "{}", frame.text)?;
+            next = frame.n + 1;
+        }
+        hidden(f, frames.len() + 1 - next)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::error::ErrorImpl;
+use crate::frame::FrameSource;
+use crate::ptr::RefPtr;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::TypeId;
+use core::fmt::Display;
+use core::panic::Location;
+
+/// A component of a [`Report`][crate::Report]'s fingerprint.
+///
+/// These are returned by
+/// [`Report::fingerprint_components`][crate::Report::fingerprint_components],
+/// one or two for each element of the report's chain, outermost first.
+/// Elements are identified by where they were created rather than by their
+/// messages where possible, so that reports which only differ in the values
+/// interpolated into their messages produce the same components.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FingerprintComponent {
+    /// The type of the error a report was created from.
+    Type(TypeId),
+    /// Where a context message or ad-hoc error message was created.
+    Location(&'static Location<'static>),
+    /// The message of an element without a known location, with every run of
+    /// ASCII digits replaced by `#`.
+    Message(String),
+}
+
+pub(crate) fn components(report: RefPtr<'_, ErrorImpl<()>>) -> Vec<FingerprintComponent> {
+    let mut components = Vec::new();
+    let mut next = Some(FrameSource::Report(report));
+
+    while let Some(source) = next.take() {
+        let error = match source {
+            FrameSource::Report(report) => {
+                let location = ErrorImpl::location(report);
+
+                if let Some((msg, source)) = ErrorImpl::context(report) {
+                    components.push(match location {
+                        Some(location) => FingerprintComponent::Location(location),
+                        None => FingerprintComponent::Message(normalize(msg)),
+                    });
+                    if let FrameSource::Error(_) = source {
+                        // The message was attached directly to the error the
+                        // report was created from
+                        components.push(FingerprintComponent::Type(ErrorImpl::type_id(report)));
+                    }
+                    next = Some(source);
+                    continue;
+                }
+
+                let type_id = ErrorImpl::type_id(report);
+                components.push(FingerprintComponent::Type(type_id));
+
+                let error = ErrorImpl::error(report);
+                // Ad-hoc messages from the same call site only differ in their
+                // interpolated values, while the message of any other error
+                // type may tell different failures apart
+                let adhoc = type_id == TypeId::of::<String>() || type_id == TypeId::of::<&str>();
+                components.push(match location {
+                    Some(location) if adhoc => FingerprintComponent::Location(location),
+                    _ => FingerprintComponent::Message(normalize(&error)),
+                });
+
+                next = error.source().map(FrameSource::Error);
+                continue;
+            }
+            FrameSource::Error(error) => error,
+        };
+
+        components.push(FingerprintComponent::Message(normalize(&error)));
+        next = error.source().map(FrameSource::Error);
+    }
+
+    components
+}
+
+fn normalize(message: &dyn Display) -> String {
+    let mut normalized = String::new();
+    let mut in_number = false;
+
+    for c in message.to_string().chars() {
+        if c.is_ascii_digit() {
+            if !in_number {
+                normalized.push('#');
+            }
+            in_number = true;
+        } else {
+            normalized.push(c);
+            in_number = false;
+        }
+    }
+
+    normalized
+}
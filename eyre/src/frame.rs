@@ -0,0 +1,227 @@
+use crate::error::ErrorImpl;
+use crate::ptr::{MutPtr, RefPtr};
+use crate::StdError;
+use core::any::TypeId;
+use core::fmt::{self, Debug, Display};
+
+/// A single element of a [`Report`][crate::Report]'s chain, as yielded by
+/// [`Report::frames`][crate::Report::frames].
+#[derive(Clone, Copy)]
+pub enum ChainFrame<'a> {
+    /// A message attached to an error via `wrap_err` and friends.
+    ContextMessage(&'a (dyn Display + Send + Sync + 'static)),
+    /// An error value, either the one the report was created from or one of
+    /// its sources.
+    Error(&'a (dyn StdError + 'static)),
+}
+
+impl Debug for ChainFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainFrame::ContextMessage(msg) => f
+                .debug_tuple("ContextMessage")
+                .field(&msg.to_string())
+                .finish(),
+            ChainFrame::Error(error) => f.debug_tuple("Error").field(error).finish(),
+        }
+    }
+}
+
+impl Display for ChainFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainFrame::ContextMessage(msg) => Display::fmt(msg, f),
+            ChainFrame::Error(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+/// Iterator over the elements of a [`Report`][crate::Report]'s chain.
+///
+/// This type is the iterator returned by [`Report::frames`][crate::Report::frames].
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct Frames<'a> {
+    next: Option<FrameSource<'a>>,
+}
+
+/// The next element of the chain to be visited by `Frames`.
+#[derive(Clone, Copy)]
+pub(crate) enum FrameSource<'a> {
+    /// A report whose vtable knows whether it carries a context message.
+    Report(RefPtr<'a, ErrorImpl<()>>),
+    /// A plain error whose sources are followed via `source`.
+    Error(&'a (dyn StdError + 'static)),
+}
+
+/// The next element of the chain to be visited by `ChainMut`.
+pub(crate) enum FrameSourceMut<'a> {
+    /// A report whose vtable knows whether it carries a context message.
+    Report(MutPtr<'a, ErrorImpl<()>>),
+    /// The error a report was created from, which ends the iteration.
+    Error(&'a mut (dyn StdError + Send + Sync + 'static)),
+}
+
+impl<'a> Frames<'a> {
+    pub(crate) fn new(report: RefPtr<'a, ErrorImpl<()>>) -> Self {
+        Frames {
+            next: Some(FrameSource::Report(report)),
+        }
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = ChainFrame<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let error = match self.next.take()? {
+            FrameSource::Report(report) => {
+                if let Some((msg, source)) = ErrorImpl::context(report) {
+                    self.next = Some(source);
+                    return Some(ChainFrame::ContextMessage(msg));
+                }
+
+                ErrorImpl::error(report)
+            }
+            FrameSource::Error(error) => error,
+        };
+
+        self.next = error.source().map(FrameSource::Error);
+        Some(ChainFrame::Error(error))
+    }
+}
+
+/// A single element of a [`Report`][crate::Report]'s chain with mutable
+/// access, as yielded by [`Report::chain_mut`][crate::Report::chain_mut].
+pub enum ChainFrameMut<'a> {
+    /// A message attached to an error via `wrap_err` and friends.
+    ContextMessage(ContextMessageMut<'a>),
+    /// The error value the report was created from.
+    Error(&'a mut (dyn StdError + Send + Sync + 'static)),
+}
+
+impl Debug for ChainFrameMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainFrameMut::ContextMessage(msg) => f
+                .debug_tuple("ContextMessage")
+                .field(&msg.to_string())
+                .finish(),
+            ChainFrameMut::Error(error) => f.debug_tuple("Error").field(error).finish(),
+        }
+    }
+}
+
+impl Display for ChainFrameMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainFrameMut::ContextMessage(msg) => Display::fmt(msg, f),
+            ChainFrameMut::Error(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+/// Mutable access to a context message of a [`Report`][crate::Report].
+///
+/// The message can be modified by downcasting it to the type it was attached
+/// as.
+pub struct ContextMessageMut<'a> {
+    msg: &'a mut (dyn Display + Send + Sync + 'static),
+    type_id: TypeId,
+}
+
+impl<'a> ContextMessageMut<'a> {
+    pub(crate) fn new<D>(msg: &'a mut D) -> Self
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        ContextMessageMut {
+            msg,
+            type_id: TypeId::of::<D>(),
+        }
+    }
+
+    /// Returns true if the message is of type `D`.
+    pub fn is<D>(&self) -> bool
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        self.type_id == TypeId::of::<D>()
+    }
+
+    /// Downcast this message by reference.
+    pub fn downcast_ref<D>(&self) -> Option<&D>
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        if self.is::<D>() {
+            // Safety: the type id was recorded from the message's concrete
+            // type when this value was created.
+            Some(unsafe { &*(&*self.msg as *const (dyn Display + Send + Sync) as *const D) })
+        } else {
+            None
+        }
+    }
+
+    /// Downcast this message by mutable reference.
+    pub fn downcast_mut<D>(&mut self) -> Option<&mut D>
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        if self.is::<D>() {
+            // Safety: the type id was recorded from the message's concrete
+            // type when this value was created.
+            Some(unsafe { &mut *(&mut *self.msg as *mut (dyn Display + Send + Sync) as *mut D) })
+        } else {
+            None
+        }
+    }
+}
+
+impl Debug for ContextMessageMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ContextMessageMut")
+            .field(&self.msg.to_string())
+            .finish()
+    }
+}
+
+impl Display for ContextMessageMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&*self.msg, f)
+    }
+}
+
+/// Mutable iterator over the elements of a [`Report`][crate::Report]'s chain.
+///
+/// This type is the iterator returned by [`Report::chain_mut`][crate::Report::chain_mut].
+#[allow(missing_debug_implementations)]
+pub struct ChainMut<'a> {
+    next: Option<FrameSourceMut<'a>>,
+}
+
+impl<'a> ChainMut<'a> {
+    pub(crate) fn new(report: MutPtr<'a, ErrorImpl<()>>) -> Self {
+        ChainMut {
+            next: Some(FrameSourceMut::Report(report)),
+        }
+    }
+}
+
+impl<'a> Iterator for ChainMut<'a> {
+    type Item = ChainFrameMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let report = match self.next.take()? {
+            FrameSourceMut::Report(report) => report,
+            FrameSourceMut::Error(error) => return Some(ChainFrameMut::Error(error)),
+        };
+
+        if let Some((msg, inner)) = ErrorImpl::context_mut(report) {
+            self.next = Some(inner);
+            return Some(ChainFrameMut::ContextMessage(msg));
+        }
+
+        Some(ChainFrameMut::Error(ErrorImpl::error_mut(report)))
+    }
+}
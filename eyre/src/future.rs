@@ -0,0 +1,212 @@
+//! Wrapping the errors of futures with additional context
+//!
+//! Wrapping the error of a future usually means awaiting it first, or
+//! mapping its output with a closure, in which case the location of the
+//! context layer is that of the closure. [`FutureExt`] instead wraps the
+//! error of the future's output when it completes, and attributes the new
+//! context layer to the place where the future was wrapped.
+//!
+//! # Example
+//!
+//! ```
+//! use eyre::future::FutureExt;
+//! use eyre::Result;
+//!
+//! async fn read_config(path: &str) -> Result<String> {
+//!     let config = async { std::fs::read_to_string(path) }
+//!         .wrap_err_with(|| format!("failed to read config from {}", path))
+//!         .await?;
+//!     Ok(config)
+//! }
+//! ```
+use crate::Report;
+use core::any::Any;
+use core::fmt::{self, Display};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+type Location = Option<&'static core::panic::Location<'static>>;
+
+/// Provides the `wrap_err` methods for futures whose output is a `Result`.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `eyre`.
+pub trait FutureExt<T, E>: Future<Output = Result<T, E>> + private::Sealed + Sized {
+    /// Wrap the error of the output with a new adhoc error.
+    ///
+    /// The context layer is attributed to the caller of this method.
+    #[cfg_attr(track_caller, track_caller)]
+    fn wrap_err<D>(self, msg: D) -> WrapErrFuture<Self, D>
+    where
+        D: Display + Send + Sync + 'static;
+
+    /// Wrap the error of the output with a new adhoc error that is evaluated
+    /// lazily, only once the future completed with an error.
+    ///
+    /// The context layer is attributed to the caller of this method.
+    #[cfg_attr(track_caller, track_caller)]
+    fn wrap_err_with<D, F>(self, msg: F) -> WrapErrWithFuture<Self, F>
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D;
+}
+
+impl<Fut, T, E> FutureExt<T, E> for Fut
+where
+    Fut: Future<Output = Result<T, E>>,
+    Report: From<E>,
+    E: 'static,
+{
+    fn wrap_err<D>(self, msg: D) -> WrapErrFuture<Self, D>
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        WrapErrFuture {
+            future: self,
+            msg: Some(msg),
+            location: caller(),
+        }
+    }
+
+    fn wrap_err_with<D, F>(self, msg: F) -> WrapErrWithFuture<Self, F>
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        WrapErrWithFuture {
+            future: self,
+            msg: Some(msg),
+            location: caller(),
+        }
+    }
+}
+
+/// Future returned by [`FutureExt::wrap_err`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WrapErrFuture<Fut, D> {
+    future: Fut,
+    msg: Option<D>,
+    location: Location,
+}
+
+impl<Fut, D> fmt::Debug for WrapErrFuture<Fut, D>
+where
+    Fut: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WrapErrFuture")
+            .field("future", &self.future)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Fut, T, E, D> Future for WrapErrFuture<Fut, D>
+where
+    Fut: Future<Output = Result<T, E>>,
+    Report: From<E>,
+    E: 'static,
+    D: Display + Send + Sync + 'static,
+{
+    type Output = Result<T, Report>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: `future` is structurally pinned, it is never moved out of
+        // `self` and `WrapErrFuture` has no `Drop` impl which could move it.
+        // `msg` is not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        future.poll(cx).map(|output| {
+            output.map_err(|error| {
+                let msg = this.msg.take().expect("polled after completion");
+                wrap_err_at(error, msg, this.location)
+            })
+        })
+    }
+}
+
+/// Future returned by [`FutureExt::wrap_err_with`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WrapErrWithFuture<Fut, F> {
+    future: Fut,
+    msg: Option<F>,
+    location: Location,
+}
+
+impl<Fut, F> fmt::Debug for WrapErrWithFuture<Fut, F>
+where
+    Fut: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WrapErrWithFuture")
+            .field("future", &self.future)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Fut, T, E, D, F> Future for WrapErrWithFuture<Fut, F>
+where
+    Fut: Future<Output = Result<T, E>>,
+    Report: From<E>,
+    E: 'static,
+    D: Display + Send + Sync + 'static,
+    F: FnOnce() -> D,
+{
+    type Output = Result<T, Report>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: see `WrapErrFuture::poll`
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        future.poll(cx).map(|output| {
+            output.map_err(|error| {
+                let msg = this.msg.take().expect("polled after completion");
+                wrap_err_at(error, msg(), this.location)
+            })
+        })
+    }
+}
+
+#[cfg_attr(track_caller, track_caller)]
+fn caller() -> Location {
+    #[cfg(track_caller)]
+    return Some(core::panic::Location::caller());
+    #[cfg(not(track_caller))]
+    return None;
+}
+
+/// Convert `error` into a report and wrap it with `msg`, attributing both to
+/// `location` instead of the code polling the future.
+fn wrap_err_at<E, D>(error: E, msg: D, location: Location) -> Report
+where
+    Report: From<E>,
+    E: 'static,
+    D: Display + Send + Sync + 'static,
+{
+    let mut error = Some(error);
+    let report = match (&mut error as &mut dyn Any).downcast_mut::<Option<Report>>() {
+        // Reports keep the location they were created at
+        Some(report) => report.take().unwrap(),
+        #[allow(unused_mut)]
+        None => {
+            let mut report = Report::from(error.take().unwrap());
+            #[cfg(track_caller)]
+            if let Some(location) = location {
+                report.set_location(location);
+            }
+            report
+        }
+    };
+
+    report.wrap_err_at(msg, location)
+}
+
+mod private {
+    use core::future::Future;
+
+    pub trait Sealed {}
+
+    impl<Fut, T, E> Sealed for Fut where Fut: Future<Output = Result<T, E>> {}
+}
@@ -0,0 +1,164 @@
+//! Turning reports into HTTP responses
+//!
+//! Web services tend to reimplement the same adapter from their error type to
+//! a response. [`ReportResponse`] wraps a [`Report`] and renders its chain as
+//! a JSON body, using the [`StatusCode`] attached to the report with
+//! [`Report::with_status`] or `500 Internal Server Error` if there is none.
+//!
+//! With the `axum` feature it implements `axum::response::IntoResponse`, and
+//! with the `warp` feature `warp::Reply`, so handlers can return
+//! `Result<T, ReportResponse>` and use `?` on anything that converts into a
+//! `Report`.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(not(feature = "auto-install"))]
+//! # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+//! use eyre::http::{ReportResponse, StatusCode};
+//! use eyre::eyre;
+//!
+//! fn find_user(id: u32) -> Result<String, ReportResponse> {
+//!     let report = eyre!("no user with id {}", id).with_status(StatusCode::NOT_FOUND);
+//!     Err(report.wrap_err("failed to load profile").into())
+//! }
+//!
+//! let response = find_user(7).unwrap_err();
+//! assert_eq!(response.status(), StatusCode::NOT_FOUND);
+//! assert_eq!(
+//!     response.body(),
+//!     r#"{"error":"failed to load profile","chain":["failed to load profile","no user with id 7"]}"#
+//! );
+//! ```
+use crate::Report;
+use core::fmt::{self, Debug, Display, Write};
+
+pub use ::http::StatusCode;
+
+/// A [`Report`] which can be returned as an HTTP response.
+///
+/// See the [module documentation](self) for details.
+pub struct ReportResponse(Report);
+
+impl ReportResponse {
+    /// The status code of the response, attached to the report via
+    /// [`Report::with_status`].
+    pub fn status(&self) -> StatusCode {
+        self.0
+            .get::<StatusCode>()
+            .copied()
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// The JSON body of the response, holding the outermost message as
+    /// `error` and the messages of the whole chain as `chain`.
+    pub fn body(&self) -> String {
+        let mut body = String::from("{\"error\":");
+        write_string(&mut body, &self.0);
+        body.push_str(",\"chain\":[");
+        for (n, error) in self.0.chain().enumerate() {
+            if n > 0 {
+                body.push(',');
+            }
+            write_string(&mut body, error);
+        }
+        body.push_str("]}");
+        body
+    }
+
+    /// The wrapped report.
+    pub fn report(&self) -> &Report {
+        &self.0
+    }
+
+    /// Unwrap the report.
+    pub fn into_report(self) -> Report {
+        self.0
+    }
+}
+
+impl<E> From<E> for ReportResponse
+where
+    Report: From<E>,
+{
+    #[cfg_attr(track_caller, track_caller)]
+    fn from(error: E) -> Self {
+        ReportResponse(Report::from(error))
+    }
+}
+
+impl Debug for ReportResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for ReportResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Report {
+    /// Attach the status code the report should be turned into by
+    /// [`ReportResponse`].
+    ///
+    /// The status code is stored as a regular report extension, so it
+    /// survives wrapping the report with additional context.
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.insert(status);
+        self
+    }
+}
+
+#[cfg(feature = "axum")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "axum")))]
+impl axum_core::response::IntoResponse for ReportResponse {
+    fn into_response(self) -> axum_core::response::Response {
+        (
+            self.status(),
+            [(::http::header::CONTENT_TYPE, "application/json")],
+            self.body(),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(feature = "warp")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "warp")))]
+impl warp::Reply for ReportResponse {
+    fn into_response(self) -> warp::reply::Response {
+        // warp uses an older version of `http`
+        let status = warp::http::StatusCode::from_u16(self.status().as_u16())
+            .unwrap_or(warp::http::StatusCode::INTERNAL_SERVER_ERROR);
+        let reply = warp::reply::with_header(self.body(), "content-type", "application/json");
+
+        warp::reply::with_status(reply, status).into_response()
+    }
+}
+
+/// Write `value` as a JSON string
+fn write_string(out: &mut String, value: impl Display) {
+    struct Escaper<'a>(&'a mut String);
+
+    impl Write for Escaper<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for c in s.chars() {
+                match c {
+                    '"' => self.0.push_str("\\\""),
+                    '\\' => self.0.push_str("\\\\"),
+                    '\n' => self.0.push_str("\\n"),
+                    '\r' => self.0.push_str("\\r"),
+                    '\t' => self.0.push_str("\\t"),
+                    c if (c as u32) < 0x20 => write!(self.0, "\\u{:04x}", c as u32)?,
+                    c => self.0.push(c),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    out.push('"');
+    let _ = write!(Escaper(out), "{}", value);
+    out.push('"');
+}
@@ -0,0 +1,74 @@
+use crate::{Aggregate, IteratorExt, Report};
+use core::iter::FromIterator;
+
+impl<I, T, E> IteratorExt<T, E> for I
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: Into<Report>,
+{
+    #[cfg_attr(track_caller, track_caller)]
+    fn try_collect_all<C>(self) -> Result<C, Report>
+    where
+        C: FromIterator<T>,
+    {
+        let mut failures = Vec::new();
+        let successes: C = self
+            .enumerate()
+            .filter_map(|(index, result)| match result {
+                Ok(ok) => Some(ok),
+                Err(error) => {
+                    failures.push((index, error.into()));
+                    None
+                }
+            })
+            .collect();
+
+        if failures.is_empty() {
+            Ok(successes)
+        } else {
+            Err(Report::new(Aggregate::new(failures)))
+        }
+    }
+}
+
+/// Collect all successful items, or report every failure.
+///
+/// This is a free-function form of
+/// [`IteratorExt::try_collect_all`](crate::IteratorExt::try_collect_all)
+/// collecting into a `Vec`, accepting anything that can be iterated over.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{eyre, join_all, Aggregate};
+///
+/// let results = vec![Ok(1), Err(eyre!("disk full")), Ok(3), Err(eyre!("network down"))];
+///
+/// let report = join_all(results).unwrap_err();
+/// let aggregate = report.downcast_ref::<Aggregate>().unwrap();
+/// assert_eq!(aggregate.len(), 2);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "iter")))]
+#[cfg_attr(track_caller, track_caller)]
+pub fn join_all<I, T, E>(results: I) -> Result<Vec<T>, Report>
+where
+    I: IntoIterator<Item = Result<T, E>>,
+    E: Into<Report>,
+{
+    results.into_iter().try_collect_all()
+}
+
+pub(crate) mod private {
+    use crate::Report;
+
+    pub trait Sealed {}
+
+    impl<I, T, E> Sealed for I
+    where
+        I: Iterator<Item = Result<T, E>>,
+        E: Into<Report>,
+    {
+    }
+}
@@ -0,0 +1,28 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::TypeId;
+
+/// A hashable key identifying the failure a [`Report`][crate::Report] describes.
+///
+/// This type is returned by [`Report::dedup_key`][crate::Report::dedup_key]. It
+/// consists of the messages of every error in the report's chain along with
+/// the type of the error the report was created from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReportKey {
+    messages: Vec<String>,
+    root_type: TypeId,
+}
+
+impl ReportKey {
+    pub(crate) fn new(messages: Vec<String>, root_type: TypeId) -> Self {
+        Self {
+            messages,
+            root_type,
+        }
+    }
+
+    /// The messages of every error in the report's chain, outermost first.
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+}
@@ -272,7 +272,7 @@
 //! to disable the `"anyhow"` feature:
 //!
 //! ```toml
-//! eyre = { version = "0.6", default-features = false, features = ["auto-install", "track-caller"] }
+//! eyre = { version = "0.6", default-features = false, features = ["auto-install", "future", "iter", "track-caller"] }
 //! ```
 //!
 //! ### `Context` and `Option`
@@ -284,8 +284,8 @@
 //! being somewhat meaningless.
 //!
 //! Instead `eyre` offers [`OptionExt::ok_or_eyre`] to yield _static_ errors from `None`,
-//! and intends for users to use the combinator functions provided by
-//! `std`, converting `Option`s to `Result`s, for _dynamic_ errors.
+//! and [`OptionExt::ok_or_eyre_with`] or the [`some_or_report!`] macro for
+//! _dynamic_ errors.
 //! So where you would write this with
 //! anyhow:
 //!
@@ -300,14 +300,14 @@
 //! With `eyre` we want users to write:
 //!
 //! ```rust
-//! use eyre::{eyre, OptionExt, Result};
+//! use eyre::{OptionExt, Result};
 //!
 //! # #[cfg(not(feature = "auto-install"))]
 //! # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
 //! #
 //! let opt: Option<()> = None;
 //! let result_static: Result<()> = opt.ok_or_eyre("static error message");
-//! let result_dynamic: Result<()> = opt.ok_or_else(|| eyre!("{} error message", "dynamic"));
+//! let result_dynamic: Result<()> = opt.ok_or_eyre_with(|| format!("{} error message", "dynamic"));
 //! ```
 //!
 //! **NOTE**: However, to help with porting we do provide a `ContextCompat` trait which
@@ -365,21 +365,78 @@
 
 extern crate alloc;
 
+mod aggregate;
 #[macro_use]
 mod backtrace;
 mod chain;
+pub mod code;
+#[cfg(any(feature = "miette-compat", feature = "anyhow-compat", feature = "pyo3"))]
+pub mod compat;
 mod context;
+mod diagnostic;
+mod ensure;
 mod error;
+mod exit;
+mod extensions;
+pub mod ffi;
+mod filter;
+mod fingerprint;
 mod fmt;
+mod frame;
+#[cfg(feature = "future")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "future")))]
+pub mod future;
+#[cfg(feature = "http")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "http")))]
+pub mod http;
+#[cfg(feature = "iter")]
+mod iter;
+mod key;
 mod kind;
 mod macros;
+mod observer;
 mod option;
+mod panic;
 mod ptr;
+mod scope;
+#[cfg(feature = "serde")]
+mod serialize;
+mod severity;
+mod shared;
+mod snapshot;
+mod tap;
+pub mod test;
+mod transient;
+pub mod typed;
+mod validator;
 mod wrapper;
 
+pub use crate::aggregate::Aggregate;
 use crate::backtrace::Backtrace;
+pub use crate::diagnostic::Diagnostic;
 use crate::error::ErrorImpl;
+pub use crate::exit::{run, ExitCode, MainResult};
+pub use crate::filter::{BacktraceFrame, DefaultHook, FrameFilter};
+pub use crate::fingerprint::FingerprintComponent;
+pub use crate::frame::{ChainFrame, ChainFrameMut, ChainMut, ContextMessageMut, Frames};
+#[cfg(feature = "iter")]
+pub use crate::iter::join_all;
+pub use crate::key::ReportKey;
+pub use crate::observer::{set_report_observer, set_wrap_observer, ReportObserver};
+pub use crate::panic::{capture_panics, PanicError};
+pub use crate::scope::{with_hook_scope, HookScope};
+#[cfg(feature = "serde")]
+pub use crate::serialize::DeserializedReport;
+pub use crate::severity::Severity;
+pub use crate::shared::SharedReport;
+pub use crate::snapshot::ReportSnapshot;
+pub use crate::transient::{set_transient_classifier, TransientClassifier};
+pub use crate::validator::{ValidationErrors, Validator};
+pub use crate::wrapper::RenderedError;
 use core::fmt::{Debug, Display};
+#[cfg(feature = "derive")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "derive")))]
+pub use eyre_derive::EyreDiagnostic;
 
 use std::error::Error as StdError;
 
@@ -389,6 +446,8 @@ pub use eyre as format_err;
 pub use eyre as anyhow;
 use once_cell::sync::OnceCell;
 use ptr::OwnedPtr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
 #[cfg(feature = "anyhow")]
 #[doc(hidden)]
 pub use DefaultHandler as DefaultContext;
@@ -485,8 +544,16 @@ pub struct Report {
 type ErrorHook =
     Box<dyn Fn(&(dyn StdError + 'static)) -> Box<dyn EyreHandler> + Sync + Send + 'static>;
 
+/// The hook installed with `set_hook`, which can be read without locking.
 static HOOK: OnceCell<ErrorHook> = OnceCell::new();
 
+/// Set by the first call to `replace_hook` or `take_hook`, after which the
+/// installed hook lives in `REPLACEABLE_HOOK` rather than in `HOOK`. This
+/// keeps programs that never replace their hook off the lock when creating
+/// reports.
+static REPLACEABLE: AtomicBool = AtomicBool::new(false);
+static REPLACEABLE_HOOK: RwLock<Option<Arc<ErrorHook>>> = RwLock::new(None);
+
 /// Error indicating that `set_hook` was unable to install the provided ErrorHook
 #[derive(Debug, Clone, Copy)]
 pub struct InstallError;
@@ -510,6 +577,10 @@ impl StdError for InstallError {}
 /// stored context. Once this type has been defined you must also define a global
 /// hook used to construct these handlers whenever `Report`s are constructed.
 ///
+/// Only one hook can be installed with `set_hook`, installing another one
+/// fails with an [`InstallError`]. Use [`replace_hook`] to override the
+/// installed hook.
+///
 /// # Examples
 ///
 /// ```rust,should_panic
@@ -596,24 +667,128 @@ impl StdError for InstallError {}
 /// }
 /// ```
 pub fn set_hook(hook: ErrorHook) -> Result<(), InstallError> {
-    HOOK.set(hook).map_err(|_| InstallError)
+    let mut installed = REPLACEABLE_HOOK
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if !REPLACEABLE.load(Ordering::Acquire) {
+        return HOOK.set(hook).map_err(|_| InstallError);
+    }
+
+    if installed.is_some() {
+        return Err(InstallError);
+    }
+
+    *installed = Some(Arc::new(hook));
+    Result::Ok(())
 }
 
-#[cfg_attr(track_caller, track_caller)]
-#[cfg_attr(not(track_caller), allow(unused_mut))]
-fn capture_handler(error: &(dyn StdError + 'static)) -> Box<dyn EyreHandler> {
+/// Install the provided error hook, replacing the currently installed one
+///
+/// Unlike [`set_hook`] this never fails, which makes it possible for long
+/// running test harnesses or REPLs to switch between handlers. Reports which
+/// were created before the hook was replaced keep the handler they were
+/// created with.
+///
+/// Returns the previously installed hook, if any.
+///
+/// # Example
+///
+/// ```rust
+/// use eyre::DefaultHandler;
+///
+/// let previous = eyre::replace_hook(Box::new(DefaultHandler::default_with));
+///
+/// // ... run a test case which relies on the default handler ...
+///
+/// if let Some(previous) = previous {
+///     eyre::replace_hook(previous);
+/// }
+/// ```
+pub fn replace_hook(hook: ErrorHook) -> Option<ErrorHook> {
+    replaceable_hook(|installed| installed.replace(Arc::new(hook)))
+}
+
+/// Uninstall the currently installed error hook and return it
+///
+/// Afterwards a new hook can be installed with [`set_hook`]. If no hook is
+/// installed by the time the next `Report` is created, the
+/// [`DefaultHandler`] is installed if the `auto-install` feature is enabled,
+/// and creating the report panics otherwise.
+pub fn take_hook() -> Option<ErrorHook> {
+    replaceable_hook(Option::take)
+}
+
+/// Switch over to `REPLACEABLE_HOOK`, moving the hook installed in `HOOK`
+/// into it on the first call, and update the installed hook with `f`.
+fn replaceable_hook(
+    f: impl FnOnce(&mut Option<Arc<ErrorHook>>) -> Option<Arc<ErrorHook>>,
+) -> Option<ErrorHook> {
+    let mut installed = REPLACEABLE_HOOK
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if !REPLACEABLE.load(Ordering::Acquire) {
+        *installed = HOOK.get().map(|hook| {
+            let hook: ErrorHook = Box::new(move |error| hook(error));
+            Arc::new(hook)
+        });
+        REPLACEABLE.store(true, Ordering::Release);
+    }
+
+    f(&mut installed).map(unwrap_hook)
+}
+
+/// Returns the hook that was handed out by [`global_hook`], which may still
+/// be in use for creating a handler on another thread.
+fn unwrap_hook(hook: Arc<ErrorHook>) -> ErrorHook {
+    match Arc::try_unwrap(hook) {
+        Result::Ok(hook) => hook,
+        Err(hook) => Box::new(move |error| hook(error)),
+    }
+}
+
+fn global_hook(error: &(dyn StdError + 'static)) -> Box<dyn EyreHandler> {
+    if !REPLACEABLE.load(Ordering::Acquire) {
+        #[cfg(not(feature = "auto-install"))]
+        let hook = HOOK
+            .get()
+            .expect("a handler must always be installed if the `auto-install` feature is disabled");
+
+        #[cfg(feature = "auto-install")]
+        let hook = HOOK.get_or_init(|| Box::new(DefaultHandler::default_with));
+
+        return (**hook)(error);
+    }
+
+    // Clone the hook out of the lock so it isn't held while the hook runs.
+    let installed = REPLACEABLE_HOOK
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    if let Some(hook) = installed {
+        return (**hook)(error);
+    }
+
     #[cfg(not(feature = "auto-install"))]
-    let hook = HOOK
-        .get()
-        .expect("a handler must always be installed if the `auto-install` feature is disabled")
-        .as_ref();
+    panic!("a handler must always be installed if the `auto-install` feature is disabled");
 
     #[cfg(feature = "auto-install")]
-    let hook = HOOK
-        .get_or_init(|| Box::new(DefaultHandler::default_with))
-        .as_ref();
+    {
+        let hook = REPLACEABLE_HOOK
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert_with(|| Arc::new(Box::new(DefaultHandler::default_with)))
+            .clone();
+        (**hook)(error)
+    }
+}
 
-    let mut handler = hook(error);
+#[cfg_attr(track_caller, track_caller)]
+#[cfg_attr(not(track_caller), allow(unused_mut))]
+fn capture_handler(error: &(dyn StdError + 'static)) -> Box<dyn EyreHandler> {
+    let mut handler = match scope::current() {
+        Some(hook) => hook(error),
+        None => global_hook(error),
+    };
 
     #[cfg(track_caller)]
     {
@@ -735,6 +910,94 @@ pub trait EyreHandler: core::any::Any + Send + Sync {
     /// Store the location of the caller who constructed this error report
     #[allow(unused_variables)]
     fn track_caller(&mut self, location: &'static std::panic::Location<'static>) {}
+
+    /// Store a structured key-value field attached to this error report via
+    /// [`Report::wrap_err_field`]
+    ///
+    /// Handlers that support fields should render them alongside the report,
+    /// e.g. as a table.
+    #[allow(unused_variables)]
+    fn field(&mut self, key: &'static str, value: &dyn Display) {}
+
+    /// Called when this error report is wrapped with an additional context
+    /// message, e.g. via [`Report::wrap_err`] or [`WrapErr::wrap_err`]
+    ///
+    /// The handler is moved to the new outermost report, so this is called
+    /// once per layer. `location` is where the context was added, if caller
+    /// tracking is enabled. Handlers can use this to record e.g. the location
+    /// and time of each layer.
+    #[allow(unused_variables)]
+    fn on_wrap(
+        &mut self,
+        msg: &dyn Display,
+        location: Option<&'static std::panic::Location<'static>>,
+    ) {
+    }
+
+    /// Store the severity of this error report set via
+    /// [`Report::set_severity`]
+    #[allow(unused_variables)]
+    fn set_severity(&mut self, severity: Severity) {}
+
+    /// Store the stable error code of this error report set via
+    /// [`Report::with_code`]
+    ///
+    /// Handlers that support codes should display them prominently, e.g. as
+    /// `error[E0425]: ...`.
+    #[allow(unused_variables)]
+    fn set_code(&mut self, code: &str) {}
+
+    /// Store the help text and suggestion of the [`Diagnostic`] this report
+    /// was created from via [`Report::from_diagnostic`]
+    ///
+    /// The error code of the diagnostic is passed to
+    /// [`set_code`](EyreHandler::set_code) separately.
+    #[allow(unused_variables)]
+    fn diagnostic(&mut self, diagnostic: &dyn Diagnostic) {}
+
+    /// Format the report of an error returned from `main` via [`MainResult`]
+    /// or [`run`]
+    ///
+    /// The default implementation prefixes the `Debug` output with `Error: `,
+    /// like returning an error from `main` does. Handlers whose reports
+    /// already start with a label should override this to avoid printing it
+    /// twice.
+    fn exit_report(
+        &self,
+        error: &(dyn StdError + 'static),
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        write!(f, "Error: ")?;
+        self.debug(error, f)
+    }
+
+    /// The code the process should exit with if the report of `error` is
+    /// returned from `main` via [`MainResult`] or [`run`]
+    ///
+    /// This is only consulted if no exit code was attached with
+    /// [`Report::with_exit_code`], and `None` falls back to `1`.
+    #[allow(unused_variables)]
+    fn exit_code(&self, error: &(dyn StdError + 'static)) -> Option<i32> {
+        None
+    }
+
+    /// The backtrace captured by this handler, rendered as plain text
+    ///
+    /// Used when serializing a report, handlers which don't capture
+    /// backtraces can leave this unimplemented.
+    #[allow(unused_variables)]
+    fn captured_backtrace(&self, error: &(dyn StdError + 'static)) -> Option<String> {
+        None
+    }
+
+    /// The sections attached to this error report, each rendered as plain
+    /// text
+    ///
+    /// Used when serializing a report, handlers which don't support sections
+    /// can leave this unimplemented.
+    fn sections(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 /// The default provided error report handler for `eyre::Report`.
@@ -744,6 +1007,9 @@ pub trait EyreHandler: core::any::Any + Send + Sync {
 #[allow(dead_code)]
 pub struct DefaultHandler {
     backtrace: Option<Backtrace>,
+    fields: Vec<(&'static str, String)>,
+    code: Option<String>,
+    filters: Option<std::sync::Arc<[Box<FrameFilter>]>>,
     #[cfg(track_caller)]
     location: Option<&'static std::panic::Location<'static>>,
 }
@@ -760,6 +1026,12 @@ impl DefaultHandler {
     /// `EyreHandlers` was not installed using `set_hook`, `DefaultHandler::default_with`
     /// is automatically installed as the hook.
     ///
+    /// The backtrace is captured with [`std::backtrace::Backtrace`], which
+    /// only records the frames of the stack when the report is created and
+    /// resolves them to symbol names and source locations the first time it
+    /// is formatted. Reports that are never printed never pay for symbol
+    /// resolution.
+    ///
     /// # Example
     ///
     /// ```rust,should_panic
@@ -778,15 +1050,34 @@ impl DefaultHandler {
     #[allow(unused_variables)]
     #[cfg_attr(not(feature = "auto-install"), allow(dead_code))]
     pub fn default_with(error: &(dyn StdError + 'static)) -> Box<dyn EyreHandler> {
+        Self::with_filters(error, None)
+    }
+
+    /// Construct a `DefaultHandler` which hides the backtrace frames rejected
+    /// by `filters`, used by [`DefaultHook`].
+    #[allow(unused_variables)]
+    pub(crate) fn with_filters(
+        error: &(dyn StdError + 'static),
+        filters: Option<std::sync::Arc<[Box<FrameFilter>]>>,
+    ) -> Box<dyn EyreHandler> {
         // Capture the backtrace if the source error did not already capture one
         let backtrace = backtrace_if_absent!(error);
 
         Box::new(Self {
             backtrace,
+            fields: Vec::new(),
+            code: None,
+            filters,
             #[cfg(track_caller)]
             location: None,
         })
     }
+
+    /// The backtrace captured by this handler, if it captured one
+    #[cfg(all(generic_member_access, feature = "anyhow-compat"))]
+    pub(crate) fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_ref()
+    }
 }
 
 impl core::fmt::Debug for DefaultHandler {
@@ -815,6 +1106,10 @@ impl EyreHandler for DefaultHandler {
             return core::fmt::Debug::fmt(error, f);
         }
 
+        if let Some(code) = &self.code {
+            write!(f, "error[{}]: ", code)?;
+        }
+
         write!(f, "{}", error)?;
 
         if let Some(cause) = error.source() {
@@ -830,6 +1125,26 @@ impl EyreHandler for DefaultHandler {
             }
         }
 
+        if !self.fields.is_empty() {
+            write!(f, "\n\nFields:")?;
+            let width = self
+                .fields
+                .iter()
+                .map(|(key, _)| key.len())
+                .max()
+                .unwrap_or(0);
+            for (key, value) in &self.fields {
+                writeln!(f)?;
+                write!(
+                    indenter::indented(f),
+                    "{:width$} = {}",
+                    key,
+                    value,
+                    width = width
+                )?;
+            }
+        }
+
         #[cfg(all(track_caller, feature = "track-caller"))]
         {
             if let Some(location) = self.location {
@@ -852,7 +1167,17 @@ impl EyreHandler for DefaultHandler {
                 .expect("backtrace capture failed");
 
             if let BacktraceStatus::Captured = backtrace.status() {
-                write!(f, "\n\nStack backtrace:\n{}", backtrace)?;
+                match &self.filters {
+                    Some(filters) => write!(
+                        f,
+                        "\n\nStack backtrace:\n{}",
+                        filter::FilteredBacktrace {
+                            trace: backtrace.to_string(),
+                            filters,
+                        }
+                    )?,
+                    None => write!(f, "\n\nStack backtrace:\n{}", backtrace)?,
+                }
             }
         }
 
@@ -863,6 +1188,41 @@ impl EyreHandler for DefaultHandler {
     fn track_caller(&mut self, location: &'static std::panic::Location<'static>) {
         self.location = Some(location);
     }
+
+    #[allow(unused_variables)]
+    fn captured_backtrace(&self, error: &(dyn StdError + 'static)) -> Option<String> {
+        #[cfg(generic_member_access)]
+        {
+            use std::backtrace::BacktraceStatus;
+
+            let backtrace = self
+                .backtrace
+                .as_ref()
+                .or_else(|| std::error::request_ref::<Backtrace>(error))?;
+
+            if let BacktraceStatus::Captured = backtrace.status() {
+                return Some(backtrace.to_string());
+            }
+        }
+
+        None
+    }
+
+    fn field(&mut self, key: &'static str, value: &dyn Display) {
+        let value = value.to_string();
+        match self
+            .fields
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.fields.push((key, value)),
+        }
+    }
+
+    fn set_code(&mut self, code: &str) {
+        self.code = Some(code.into());
+    }
 }
 
 /// Iterator of a chain of source errors.
@@ -1015,6 +1375,11 @@ pub type Result<T, E = Report> = core::result::Result<T, E>;
 /// }
 /// ```
 ///
+/// # Wrapping other report types
+///
+/// Results whose error type can't implement `Error` but converts into a `Report`, such as the
+/// report types of other crates, can be wrapped via [`WrapReportErr`].
+///
 /// # Effect on downcasting
 ///
 /// After attaching a message of type `D` onto an error of type `E`, the resulting
@@ -1130,6 +1495,165 @@ pub trait WrapErr<T, E>: context::private::Sealed {
     where
         D: Display + Send + Sync + 'static,
         F: FnOnce() -> D;
+
+    /// Wrap the error value with a new adhoc error, unless its outermost
+    /// message is already identical to `msg`.
+    ///
+    /// See [`Report::wrap_err_once`] for details.
+    #[cfg_attr(track_caller, track_caller)]
+    fn wrap_err_dedup<D>(self, msg: D) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static;
+
+    /// Replace the outermost context message of the error value with `msg`,
+    /// or wrap it with `msg` if it has none.
+    ///
+    /// See [`Report::change_context`] for details.
+    #[cfg_attr(track_caller, track_caller)]
+    fn change_context<D>(self, msg: D) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static;
+}
+
+/// Provides the [`wrap_boxed_err`][WrapBoxedErr::wrap_boxed_err] method for
+/// results holding a boxed error.
+///
+/// [`WrapErr`] can't be implemented for `Result<T, Box<dyn Error + Send +
+/// Sync>>`, because `Box<dyn Error + Send + Sync>` doesn't implement
+/// [`std::error::Error`] itself. This trait fills that gap. The boxed error
+/// becomes the root of the report, so its chain of sources is preserved.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `eyre`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::WrapBoxedErr;
+/// use std::error::Error;
+///
+/// fn legacy() -> Result<(), Box<dyn Error + Send + Sync>> {
+///     Err("disk full".into())
+/// }
+///
+/// let report = legacy().wrap_boxed_err("failed to save").unwrap_err();
+/// assert_eq!(format!("{:#}", report), "failed to save: disk full");
+/// ```
+pub trait WrapBoxedErr<T>: context::private::SealedBoxed {
+    /// Wrap the boxed error value with a new adhoc error
+    #[cfg_attr(track_caller, track_caller)]
+    fn wrap_boxed_err<D>(self, msg: D) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static;
+
+    /// Wrap the boxed error value with a new adhoc error that is evaluated
+    /// lazily only once an error does occur.
+    #[cfg_attr(track_caller, track_caller)]
+    fn wrap_boxed_err_with<D, F>(self, f: F) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D;
+}
+
+/// Provides the [`wrap_report_err`][WrapReportErr::wrap_report_err] method
+/// for results whose error converts into a [`Report`].
+///
+/// Crates that define their own report type usually can't implement
+/// [`std::error::Error`] for it, so [`WrapErr`] isn't available for their
+/// results. Providing a `From` conversion into `Report` makes them wrappable
+/// with this trait instead. The error is converted into a `Report` first, and
+/// the message is attached to it as with [`Report::wrap_err`].
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `eyre`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{Report, WrapReportErr};
+///
+/// #[derive(Debug)]
+/// struct OtherReport(String);
+///
+/// impl From<OtherReport> for Report {
+///     fn from(report: OtherReport) -> Self {
+///         Report::msg(report.0)
+///     }
+/// }
+///
+/// fn other_crate() -> Result<(), OtherReport> {
+///     Err(OtherReport("oh no!".into()))
+/// }
+///
+/// let report = other_crate().wrap_report_err("other crate failed").unwrap_err();
+/// assert_eq!(format!("{:#}", report), "other crate failed: oh no!");
+/// ```
+pub trait WrapReportErr<T>: context::private::SealedReport {
+    /// Convert the error value into a `Report` and wrap it with a new adhoc
+    /// error
+    #[cfg_attr(track_caller, track_caller)]
+    fn wrap_report_err<D>(self, msg: D) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static;
+
+    /// Convert the error value into a `Report` and wrap it with a new adhoc
+    /// error that is evaluated lazily only once an error does occur.
+    #[cfg_attr(track_caller, track_caller)]
+    fn wrap_report_err_with<D, F>(self, f: F) -> Result<T, Report>
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D;
+}
+
+/// Provides combinators for inspecting the [`Report`] of a `Result` without
+/// consuming it.
+///
+/// Long running services often handle an error by logging it and moving on.
+/// These combinators log or otherwise inspect the report in the `Err` case
+/// and return the result unchanged, so it can still be propagated or
+/// discarded with `.ok()`.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `eyre`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{eyre, ReportTap, Result};
+///
+/// fn refresh_cache() -> Result<()> {
+///     Err(eyre!("cache server unreachable"))
+/// }
+///
+/// let mut failures = Vec::new();
+/// let _ = refresh_cache()
+///     .tap_report(|report| failures.push(report.to_string()))
+///     .ok();
+/// assert_eq!(failures, ["cache server unreachable"]);
+/// ```
+pub trait ReportTap<T>: tap::private::Sealed {
+    /// Log the report with [`log::error!`] using its `Debug` format, which
+    /// includes the sections added by the installed handler.
+    #[cfg(feature = "log")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "log")))]
+    fn log_err(self) -> Self;
+
+    /// Emit the report as a [`tracing::error!`] event using its `Debug`
+    /// format, which includes the sections added by the installed handler.
+    #[cfg(feature = "tracing")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "tracing")))]
+    fn trace_err(self) -> Self;
+
+    /// Call `f` with a reference to the report if the result is an `Err`.
+    fn tap_report<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&Report);
 }
 
 /// Provides the [`ok_or_eyre`][OptionExt::ok_or_eyre] method for [`Option`].
@@ -1185,6 +1709,74 @@ pub trait OptionExt<T>: context::private::Sealed {
     fn ok_or_eyre<M>(self, message: M) -> crate::Result<T>
     where
         M: Debug + Display + Send + Sync + 'static;
+
+    /// Transform the [`Option<T>`] into a [`Result<T, E>`], lazily creating
+    /// the message of the [`Report`] with `f` in the [`None`] case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "auto-install"))]
+    /// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+    /// use eyre::OptionExt;
+    ///
+    /// let id = 7;
+    /// let user: Option<()> = None;
+    ///
+    /// let result = user.ok_or_eyre_with(|| format!("no user with id {}", id));
+    ///
+    /// assert_eq!(result.unwrap_err().to_string(), "no user with id 7");
+    /// ```
+    fn ok_or_eyre_with<M, F>(self, f: F) -> crate::Result<T>
+    where
+        M: Debug + Display + Send + Sync + 'static,
+        F: FnOnce() -> M;
+}
+
+/// Provides the `try_collect_all` method for iterators of `Result`s.
+///
+/// This trait is sealed and cannot be implemented for types outside of
+/// `eyre`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::IteratorExt;
+///
+/// let numbers: Vec<u32> = ["1", "2", "3"]
+///     .iter()
+///     .map(|s| s.parse::<u32>())
+///     .try_collect_all()
+///     .unwrap();
+/// assert_eq!(numbers, [1, 2, 3]);
+///
+/// let report = ["1", "x", "3", ""]
+///     .iter()
+///     .map(|s| s.parse::<u32>())
+///     .try_collect_all::<Vec<_>>()
+///     .unwrap_err();
+/// assert_eq!(
+///     report.to_string(),
+///     "2 errors occurred\n\
+///      [1] invalid digit found in string\n\
+///      [3] cannot parse integer from empty string",
+/// );
+/// ```
+#[cfg(feature = "iter")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "iter")))]
+pub trait IteratorExt<T, E>: iter::private::Sealed {
+    /// Collect all successful items, or report every failure.
+    ///
+    /// Unlike collecting into a `Result`, which stops at the first error,
+    /// this consumes the whole iterator. If any item failed, the returned
+    /// [`Report`] wraps an [`Aggregate`] holding each failure along with the
+    /// index of the item that produced it.
+    #[cfg_attr(track_caller, track_caller)]
+    fn try_collect_all<C>(self) -> Result<C, Report>
+    where
+        C: core::iter::FromIterator<T>;
 }
 
 /// Provides the `context` and `with_context` methods for `Result` and `Option` to enhance
@@ -1249,6 +1841,56 @@ pub trait ContextCompat<T>: context::private::Sealed {
         F: FnOnce() -> D;
 }
 
+/// Convert an error that is not threadsafe into a `Report`.
+///
+/// Errors produced by single-threaded libraries often hold `Rc`s or other
+/// non-`Send` state, which means they cannot be stored in a `Report` and
+/// cannot cross into multithreaded code. This adapter walks the error's
+/// source chain and captures the `Display` and `Debug` output of every error
+/// in it, producing a threadsafe `Report` whose [`chain()`][Report::chain]
+/// prints the same messages as the original error.
+///
+/// The original error is consumed and dropped; it cannot be recovered by
+/// downcasting the returned report. See [`Report::msg_from_error`] for the
+/// borrowing equivalent.
+///
+/// ```
+/// use std::fmt;
+/// use std::rc::Rc;
+///
+/// #[derive(Debug)]
+/// struct ParseError {
+///     input: Rc<str>,
+/// }
+///
+/// impl fmt::Display for ParseError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "unexpected input: {}", self.input)
+///     }
+/// }
+///
+/// impl std::error::Error for ParseError {}
+///
+/// fn parse(input: &str) -> Result<(), ParseError> {
+///     Err(ParseError { input: input.into() })
+/// }
+///
+/// fn handle(input: &str) -> eyre::Result<()> {
+///     parse(input).map_err(eyre::from_local)?;
+///     Ok(())
+/// }
+///
+/// let report = handle("}").unwrap_err();
+/// assert_eq!(report.to_string(), "unexpected input: }");
+/// ```
+#[cfg_attr(track_caller, track_caller)]
+pub fn from_local<E>(error: E) -> Report
+where
+    E: StdError,
+{
+    Report::msg_from_error(&error)
+}
+
 /// Equivalent to `Ok::<_, eyre::Error>(value)`.
 ///
 /// This simplifies creation of an eyre::Result in places where type inference
@@ -1281,7 +1923,14 @@ pub mod private {
 
     pub use alloc::format;
     pub use core::format_args;
+    pub use core::option::Option::{None, Some};
     pub use core::result::Result::Err;
+    pub use core::stringify;
+
+    #[doc(hidden)]
+    pub mod ensure {
+        pub use crate::ensure::{comparison_failed, DebugOperand, OpaqueOperand, Operand};
+    }
 
     #[doc(hidden)]
     pub mod kind {
@@ -1309,7 +1958,7 @@ pub mod private {
 
         if let Some(message) = fmt_arguments_as_str {
             // eyre!("literal"), can downcast to &'static str
-            Report::msg(message)
+            Report::msg_static(message)
         } else {
             // eyre!("interpolate {var}"), can downcast to String
             Report::msg(fmt::format(args))
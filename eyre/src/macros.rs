@@ -69,6 +69,13 @@ macro_rules! bail {
 /// if the condition fails. Unlike `assert!`, `ensure!` returns an `eyre::Result`
 /// rather than panicking.
 ///
+/// Without a message, the report says which condition failed. If the
+/// condition is an `==` or `!=` comparison of simple operands, such as
+/// variables, literals, parenthesized expressions, calls or method calls, and
+/// both operands implement `Debug`, their values are included too, similar to
+/// `assert_eq!`. Other conditions, e.g. `a + b == c`, are only stringified;
+/// wrap such operands in parentheses to include their values.
+///
 /// # Example
 ///
 /// ```
@@ -105,11 +112,23 @@ macro_rules! bail {
 /// #     Ok(())
 /// # }
 /// ```
+///
+/// ```
+/// # use eyre::{ensure, Result};
+/// #
+/// fn check_checksum(expected: u32, actual: u32) -> Result<()> {
+///     ensure!(expected == actual);
+///     Ok(())
+/// }
+///
+/// let error = check_checksum(0xcafe, 0xbeef).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Condition failed: `expected == actual` (left: 51966, right: 48879)",
+/// );
+/// ```
 #[macro_export]
 macro_rules! ensure {
-    ($cond:expr $(,)?) => {
-        $crate::ensure!($cond, concat!("Condition failed: `", stringify!($cond), "`"))
-    };
     ($cond:expr, $msg:literal $(,)?) => {
         if !$cond {
             return $crate::private::Err($crate::eyre!($msg));
@@ -125,6 +144,242 @@ macro_rules! ensure {
             return $crate::private::Err($crate::eyre!($fmt, $($arg)*));
         }
     };
+    (
+        $lhs:tt $(($($lhs_args:tt)*))? $(. $lhs_field:tt $(($($lhs_field_args:tt)*))?)*
+        == $rhs:tt $(($($rhs_args:tt)*))? $(. $rhs_field:tt $(($($rhs_field_args:tt)*))?)* $(,)?
+    ) => {
+        $crate::__ensure_operands!(
+            (==)
+            [$lhs $(($($lhs_args)*))? $(. $lhs_field $(($($lhs_field_args)*))?)*]
+            [$rhs $(($($rhs_args)*))? $(. $rhs_field $(($($rhs_field_args)*))?)*]
+        )
+    };
+    (
+        $lhs:tt $(($($lhs_args:tt)*))? $(. $lhs_field:tt $(($($lhs_field_args:tt)*))?)*
+        != $rhs:tt $(($($rhs_args:tt)*))? $(. $rhs_field:tt $(($($rhs_field_args:tt)*))?)* $(,)?
+    ) => {
+        $crate::__ensure_operands!(
+            (!=)
+            [$lhs $(($($lhs_args)*))? $(. $lhs_field $(($($lhs_field_args)*))?)*]
+            [$rhs $(($($rhs_args)*))? $(. $rhs_field $(($($rhs_field_args)*))?)*]
+        )
+    };
+    ($cond:expr $(,)?) => {
+        $crate::ensure!($cond, concat!("Condition failed: `", stringify!($cond), "`"))
+    };
+}
+
+// Evaluates both operands of an `ensure!` comparison once and includes them
+// in the report if the comparison fails. `ensure!` only forwards comparisons
+// whose operands can't contain operators binding looser than `==` or `!=`:
+// a single token tree, optionally followed by a call and by field accesses or
+// method calls, e.g. `len`, `(a + b)`, `Some(x)` or `name.len()`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ensure_operands {
+    (($op:tt) [$($left:tt)+] [$($right:tt)+]) => {
+        match (&($($left)+), &($($right)+)) {
+            (left, right) => {
+                if !(*left $op *right) {
+                    #[allow(unused_imports)]
+                    use $crate::private::ensure::{DebugOperand, OpaqueOperand, Operand};
+                    return $crate::private::Err($crate::private::ensure::comparison_failed(
+                        concat!(
+                            "Condition failed: `",
+                            stringify!($($left)+),
+                            " ",
+                            stringify!($op),
+                            " ",
+                            stringify!($($right)+),
+                            "`",
+                        ),
+                        (&Operand(left)).eyre_render(),
+                        (&Operand(right)).eyre_render(),
+                    ));
+                }
+            }
+        }
+    };
+}
+
+/// Return early with an error if two expressions are not equal.
+///
+/// Analogously to `assert_eq!`, the report includes the `Debug`
+/// representation of both operands. Without a message, it also says which
+/// comparison failed.
+///
+/// # Example
+///
+/// ```
+/// # use eyre::{ensure_eq, Result};
+/// #
+/// fn check_version(version: u32) -> Result<()> {
+///     ensure_eq!(version, 2, "unsupported format version");
+///     Ok(())
+/// }
+///
+/// assert_eq!(
+///     check_version(3).unwrap_err().to_string(),
+///     "unsupported format version (left: 3, right: 2)",
+/// );
+/// ```
+#[macro_export]
+macro_rules! ensure_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__ensure_cmp!(
+            (==),
+            $left,
+            $right,
+            concat!("Condition failed: `", stringify!($left), " == ", stringify!($right), "`")
+        )
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::__ensure_cmp!((==), $left, $right, $crate::private::format!($($arg)+))
+    };
+}
+
+/// Return early with an error if two expressions are equal.
+///
+/// Analogously to `assert_ne!`, the report includes the `Debug`
+/// representation of both operands. Without a message, it also says which
+/// comparison failed.
+///
+/// # Example
+///
+/// ```
+/// # use eyre::{ensure_ne, Result};
+/// #
+/// fn transfer(from: &str, to: &str) -> Result<()> {
+///     ensure_ne!(from, to, "cannot transfer to the same account");
+///     Ok(())
+/// }
+///
+/// assert_eq!(
+///     transfer("alice", "alice").unwrap_err().to_string(),
+///     r#"cannot transfer to the same account (left: "alice", right: "alice")"#,
+/// );
+/// ```
+#[macro_export]
+macro_rules! ensure_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__ensure_cmp!(
+            (!=),
+            $left,
+            $right,
+            concat!("Condition failed: `", stringify!($left), " != ", stringify!($right), "`")
+        )
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::__ensure_cmp!((!=), $left, $right, $crate::private::format!($($arg)+))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ensure_cmp {
+    (($op:tt), $left:expr, $right:expr, $message:expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left $op *right) {
+                    return $crate::private::Err($crate::private::ensure::comparison_failed(
+                        $message,
+                        $crate::private::Some($crate::private::format!("{:?}", left)),
+                        $crate::private::Some($crate::private::format!("{:?}", right)),
+                    ));
+                }
+            }
+        }
+    };
+}
+
+/// Return early with an error if a condition is satisfied.
+///
+/// This macro is equivalent to `if $cond { bail!(<other args>); }`, the
+/// inverse of [`ensure!`].
+///
+/// # Example
+///
+/// ```
+/// # use eyre::{bail_if, Result};
+/// #
+/// fn read_chunk(len: usize) -> Result<()> {
+///     bail_if!(len > 4096, "chunk of {} bytes is too large", len);
+///     Ok(())
+/// }
+///
+/// assert_eq!(
+///     read_chunk(8192).unwrap_err().to_string(),
+///     "chunk of 8192 bytes is too large",
+/// );
+/// ```
+#[macro_export]
+macro_rules! bail_if {
+    ($cond:expr, $($arg:tt)+) => {
+        if $cond {
+            $crate::bail!($($arg)+);
+        }
+    };
+}
+
+/// Unwrap an `Option`, returning early with an error if it is `None`.
+///
+/// This macro is equivalent to
+/// `match $option { Some(value) => value, None => return Err(eyre!(<other args>)) }`.
+///
+/// Without a message, the report says which expression was `None`.
+///
+/// # Example
+///
+/// ```
+/// # use eyre::{some_or_report, Result};
+/// # use std::collections::HashMap;
+/// #
+/// fn port(config: &HashMap<&str, u16>, service: &str) -> Result<u16> {
+///     let port = some_or_report!(config.get(service), "no port configured for {}", service);
+///     Ok(*port)
+/// }
+///
+/// let config = HashMap::from([("http", 80)]);
+/// assert_eq!(port(&config, "http").unwrap(), 80);
+/// assert_eq!(
+///     port(&config, "ftp").unwrap_err().to_string(),
+///     "no port configured for ftp",
+/// );
+/// ```
+#[macro_export]
+macro_rules! some_or_report {
+    ($option:expr $(,)?) => {
+        match $option {
+            $crate::private::Some(value) => value,
+            $crate::private::None => {
+                return $crate::private::Err($crate::eyre!(concat!(
+                    "`",
+                    stringify!($option),
+                    "` was None"
+                )));
+            }
+        }
+    };
+    ($option:expr, $msg:literal $(,)?) => {
+        match $option {
+            $crate::private::Some(value) => value,
+            $crate::private::None => return $crate::private::Err($crate::eyre!($msg)),
+        }
+    };
+    ($option:expr, $err:expr $(,)?) => {
+        match $option {
+            $crate::private::Some(value) => value,
+            $crate::private::None => return $crate::private::Err($crate::eyre!($err)),
+        }
+    };
+    ($option:expr, $fmt:expr, $($arg:tt)*) => {
+        match $option {
+            $crate::private::Some(value) => value,
+            $crate::private::None => {
+                return $crate::private::Err($crate::eyre!($fmt, $($arg)*));
+            }
+        }
+    };
 }
 
 /// Construct an ad-hoc error from a string.
@@ -149,8 +404,49 @@ macro_rules! ensure {
 ///     # Ok(())
 /// }
 /// ```
+///
+/// An error which caused the new one can be passed as `source`, the new
+/// error then wraps it as if by
+/// [`wrap_err`](crate::Report::wrap_err), producing a chain of length two:
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{eyre, Result};
+/// use std::fs::File;
+///
+/// fn open(path: &str) -> Result<File> {
+///     File::open(path).map_err(|e| eyre!(source = e, "failed to open {}", path))
+/// }
+///
+/// let report = open("/does/not/exist").unwrap_err();
+/// assert_eq!(report.chain().count(), 2);
+/// assert_eq!(report.to_string(), "failed to open /does/not/exist");
+/// ```
+///
+/// A stable error code can be attached by passing it as `code`, see
+/// [`Report::with_code`](crate::Report::with_code):
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::eyre;
+///
+/// let report = eyre!(code = "E0425", "cannot find value `{}` in this scope", "x");
+/// assert_eq!(report.code(), Some("E0425"));
+/// ```
 #[macro_export]
 macro_rules! eyre {
+    (code = $code:expr, $($msg:tt)+) => ({
+        $crate::eyre!($($msg)+).with_code($code)
+    });
+    (source = $source:expr, $($msg:tt)+) => ({
+        use $crate::private::kind::*;
+        let error = match $source {
+            error => (&error).eyre_kind().new(error),
+        };
+        error.wrap_err($crate::private::format!($($msg)+))
+    });
     ($msg:literal $(,)?) => ({
         let error = $crate::private::format_err($crate::private::format_args!($msg));
         error
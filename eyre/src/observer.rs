@@ -0,0 +1,87 @@
+use crate::{InstallError, Report};
+use core::cell::Cell;
+use once_cell::sync::OnceCell;
+
+/// A callback observing [`Report`]s, see [`set_report_observer`] and
+/// [`set_wrap_observer`].
+pub type ReportObserver = dyn Fn(&Report) + Send + Sync + 'static;
+
+static ON_CREATE: OnceCell<Box<ReportObserver>> = OnceCell::new();
+static ON_WRAP: OnceCell<Box<ReportObserver>> = OnceCell::new();
+
+std::thread_local! {
+    static OBSERVING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Install a callback which is invoked with every newly constructed
+/// [`Report`].
+///
+/// The observer runs right after the report and its handler have been
+/// created, so it can inspect the chain, the handler and the extensions of
+/// the report, e.g. to count errors for metrics or to sample reports for
+/// further analysis. Reports created by the observer itself are not observed.
+///
+/// Like [`set_hook`](crate::set_hook), an observer can only be installed
+/// once.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static ERRORS_CREATED: AtomicUsize = AtomicUsize::new(0);
+///
+/// eyre::set_report_observer(Box::new(|_report| {
+///     ERRORS_CREATED.fetch_add(1, Ordering::Relaxed);
+/// }))
+/// .unwrap();
+///
+/// let _ = eyre::eyre!("oh no!");
+/// assert_eq!(ERRORS_CREATED.load(Ordering::Relaxed), 1);
+/// ```
+pub fn set_report_observer(observer: Box<ReportObserver>) -> Result<(), InstallError> {
+    ON_CREATE.set(observer).map_err(|_| InstallError)
+}
+
+/// Install a callback which is invoked every time a [`Report`] is wrapped
+/// with additional context, e.g. via [`Report::wrap_err`].
+///
+/// The observer is passed the new outermost report. Like
+/// [`set_report_observer`], reports created by the observer itself are not
+/// observed, and an observer can only be installed once.
+pub fn set_wrap_observer(observer: Box<ReportObserver>) -> Result<(), InstallError> {
+    ON_WRAP.set(observer).map_err(|_| InstallError)
+}
+
+pub(crate) fn created(report: &Report) {
+    observe(&ON_CREATE, report);
+}
+
+pub(crate) fn wrapped(report: &Report) {
+    observe(&ON_WRAP, report);
+}
+
+fn observe(observer: &OnceCell<Box<ReportObserver>>, report: &Report) {
+    let observer = match observer.get() {
+        Some(observer) => observer,
+        None => return,
+    };
+
+    // Don't recurse into the observer for reports it creates itself
+    if OBSERVING.with(|observing| observing.replace(true)) {
+        return;
+    }
+
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            OBSERVING.with(|observing| observing.set(false));
+        }
+    }
+
+    let _reset = Reset;
+    observer(report);
+}
@@ -12,4 +12,16 @@ impl<T> OptionExt<T> for Option<T> {
             None => Err(crate::Report::msg(message)),
         }
     }
+
+    #[track_caller]
+    fn ok_or_eyre_with<M, F>(self, f: F) -> crate::Result<T>
+    where
+        M: Debug + Display + Send + Sync + 'static,
+        F: FnOnce() -> M,
+    {
+        match self {
+            Some(ok) => Ok(ok),
+            None => Err(crate::Report::msg(f())),
+        }
+    }
 }
@@ -0,0 +1,196 @@
+use crate::backtrace::Backtrace;
+use crate::Report;
+use core::fmt;
+use std::cell::{Cell, RefCell};
+use std::error::Error as StdError;
+use std::panic::{self as std_panic, UnwindSafe};
+use std::sync::{Arc, Mutex};
+
+#[allow(deprecated)]
+type PanicHook = Box<dyn Fn(&std_panic::PanicInfo<'_>) + Sync + Send + 'static>;
+
+/// The panic hook that was installed before the first active call to
+/// [`capture_panics`], along with the number of calls currently active.
+struct Installed {
+    active: usize,
+    previous: Arc<PanicHook>,
+}
+
+static INSTALLED: Mutex<Option<Installed>> = Mutex::new(None);
+
+std::thread_local! {
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    static CAPTURED: RefCell<Option<PanicError>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, converting a panic inside of it into a [`Report`].
+///
+/// While `f` runs, a panic hook is installed which records the panic message,
+/// location and a backtrace of the panicking thread instead of printing them.
+/// The recorded information is turned into a [`PanicError`] and returned as
+/// a `Report`, so that panics in untrusted code such as plugins go through the
+/// same reporting pipeline as errors. Once no more calls to `capture_panics`
+/// are active the previously installed panic hook is restored.
+///
+/// Panics on other threads are passed on to the previous panic hook as usual,
+/// so this can be called from multiple threads at once. Replacing the panic
+/// hook while `f` runs is not supported, the replacement will be overwritten
+/// when the previous hook is restored.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{capture_panics, PanicError};
+///
+/// let report = capture_panics(|| -> u32 { panic!("plugin crashed") }).unwrap_err();
+///
+/// let panic = report.downcast_ref::<PanicError>().unwrap();
+/// assert_eq!(panic.message(), "plugin crashed");
+///
+/// assert_eq!(capture_panics(|| 42).unwrap(), 42);
+/// ```
+#[cfg_attr(track_caller, track_caller)]
+pub fn capture_panics<F, T>(f: F) -> Result<T, Report>
+where
+    F: FnOnce() -> T + UnwindSafe,
+{
+    install();
+    // Discard a panic recorded for a closure which caught it itself
+    CAPTURED.with(|captured| captured.borrow_mut().take());
+    let was_capturing = CAPTURING.with(|capturing| capturing.replace(true));
+    let result = std_panic::catch_unwind(f);
+    CAPTURING.with(|capturing| capturing.set(was_capturing));
+    uninstall();
+
+    result.map_err(|payload| {
+        let error = CAPTURED
+            .with(|captured| captured.borrow_mut().take())
+            .unwrap_or_else(|| PanicError {
+                message: payload_message(&*payload),
+                location: None,
+                backtrace: None,
+            });
+
+        Report::from_std(error)
+    })
+}
+
+fn install() {
+    let mut installed = INSTALLED.lock().unwrap_or_else(|e| e.into_inner());
+    match &mut *installed {
+        Some(installed) => installed.active += 1,
+        None => {
+            let previous = Arc::new(std_panic::take_hook());
+            let fallback = previous.clone();
+            std_panic::set_hook(Box::new(move |info| {
+                if CAPTURING.with(Cell::get) {
+                    let error = PanicError {
+                        message: payload_message(info.payload()),
+                        location: info.location().map(ToString::to_string),
+                        backtrace: capture_backtrace!(),
+                    };
+                    CAPTURED.with(|captured| *captured.borrow_mut() = Some(error));
+                } else {
+                    fallback(info);
+                }
+            }));
+
+            *installed = Some(Installed {
+                active: 1,
+                previous,
+            });
+        }
+    }
+}
+
+fn uninstall() {
+    let mut installed = INSTALLED.lock().unwrap_or_else(|e| e.into_inner());
+    let done = match &mut *installed {
+        Some(installed) => {
+            installed.active -= 1;
+            installed.active == 0
+        }
+        None => false,
+    };
+
+    if done {
+        let previous = installed.take().unwrap().previous;
+        // Dropping our hook releases its handle on the previous one
+        drop(std_panic::take_hook());
+        match Arc::try_unwrap(previous) {
+            Ok(previous) => std_panic::set_hook(previous),
+            Err(previous) => std_panic::set_hook(Box::new(move |info| previous(info))),
+        }
+    }
+}
+
+fn payload_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+        .unwrap_or_else(|| "<non string panic payload>".to_owned())
+}
+
+/// A panic captured by [`capture_panics`].
+///
+/// The backtrace of the panicking thread is provided via generic member
+/// access, so handlers print it instead of one captured where the report was
+/// created.
+pub struct PanicError {
+    message: String,
+    location: Option<String>,
+    #[cfg_attr(not(backtrace), allow(dead_code))]
+    backtrace: Option<Backtrace>,
+}
+
+impl PanicError {
+    /// The panic message, or `<non string panic payload>` if the panic was
+    /// not raised with a string.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The location of the panic, formatted as `file:line:column`.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    /// The backtrace of the panicking thread, if one was captured.
+    ///
+    /// Whether a backtrace is captured follows the `RUST_BACKTRACE` and
+    /// `RUST_LIB_BACKTRACE` environment variables.
+    #[cfg(backtrace)]
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.backtrace.as_ref()
+    }
+}
+
+impl fmt::Debug for PanicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PanicError")
+            .field("message", &self.message)
+            .field("location", &self.location)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for PanicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {}: {}", location, self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+impl StdError for PanicError {
+    #[cfg(generic_member_access)]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        if let Some(backtrace) = &self.backtrace {
+            request.provide_ref::<Backtrace>(backtrace);
+        }
+    }
+}
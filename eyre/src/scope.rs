@@ -0,0 +1,106 @@
+use crate::EyreHandler;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::cell::RefCell;
+use std::error::Error as StdError;
+use std::sync::Arc;
+
+type ScopedHook =
+    Arc<dyn Fn(&(dyn StdError + 'static)) -> Box<dyn EyreHandler> + Sync + Send + 'static>;
+
+std::thread_local! {
+    static SCOPED_HOOK: RefCell<Option<ScopedHook>> = RefCell::new(None);
+}
+
+/// Returns the hook of the innermost [`HookScope`] currently being polled on
+/// this thread.
+pub(crate) fn current() -> Option<ScopedHook> {
+    SCOPED_HOOK.with(|hook| hook.borrow().clone())
+}
+
+/// Restores the previously active scoped hook when dropped, even if polling
+/// the inner future panicked.
+struct Restore(Option<ScopedHook>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        SCOPED_HOOK.with(|hook| *hook.borrow_mut() = previous);
+    }
+}
+
+/// Future returned by [`with_hook_scope`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct HookScope<F> {
+    hook: ScopedHook,
+    future: F,
+}
+
+impl<F> fmt::Debug for HookScope<F>
+where
+    F: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HookScope")
+            .field("future", &self.future)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> Future for HookScope<F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: `future` is structurally pinned, it is never moved out of
+        // `self` and `HookScope` has no `Drop` impl which could move it.
+        let this = unsafe { self.get_unchecked_mut() };
+        let scoped = this.hook.clone();
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        let previous = SCOPED_HOOK.with(|hook| hook.replace(Some(scoped)));
+        let _restore = Restore(previous);
+
+        future.poll(cx)
+    }
+}
+
+/// Use a specific handler for reports created inside `future`.
+///
+/// While the returned future is being polled, every `Report` constructed on
+/// the polling thread gets its handler from `hook` instead of the globally
+/// installed one. This includes reports created by futures awaited within
+/// `future`, but not by tasks it spawns onto an executor, as those are polled
+/// independently. Scopes can be nested, with the innermost one taking
+/// precedence.
+///
+/// This only relies on the `Future` trait and works with any async runtime.
+///
+/// # Example
+///
+/// ```
+/// use eyre::{eyre, DefaultHandler, Report};
+///
+/// async fn handle_request() -> Result<(), Report> {
+///     Err(eyre!("resource not found"))
+/// }
+///
+/// async fn serve() {
+///     let result = eyre::with_hook_scope(DefaultHandler::default_with, handle_request()).await;
+///     assert!(result.unwrap_err().handler().is::<DefaultHandler>());
+/// }
+/// ```
+pub fn with_hook_scope<H, F>(hook: H, future: F) -> HookScope<F>
+where
+    H: Fn(&(dyn StdError + 'static)) -> Box<dyn EyreHandler> + Sync + Send + 'static,
+    F: Future,
+{
+    HookScope {
+        hook: Arc::new(hook),
+        future,
+    }
+}
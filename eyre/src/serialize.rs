@@ -0,0 +1,117 @@
+//! Structured serialization of reports
+use crate::error::ErrorImpl;
+use crate::{RenderedError, Report};
+use core::fmt;
+use serde::{Deserialize, Serialize, Serializer};
+use std::error::Error as StdError;
+
+/// A report that was serialized in another process and deserialized here.
+///
+/// `Report` serializes into a structured form holding the message of each
+/// error in its chain, the backtrace captured by its handler and the sections
+/// attached to it, all as plain text. `DeserializedReport` is the matching
+/// deserialized form, which implements [`std::error::Error`] with one source
+/// per error in the original chain, so it can be wrapped in a `Report` again
+/// on the receiving side.
+///
+/// Serializing a `Report` holding a `DeserializedReport` keeps the backtrace
+/// and sections it was received with, so reports can be passed through any
+/// number of processes.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{eyre, DeserializedReport, Report, WrapErr};
+///
+/// let report = Err::<(), _>(eyre!("connection reset"))
+///     .wrap_err("failed to fetch shard 3")
+///     .unwrap_err();
+///
+/// let json = serde_json::to_string(&report).unwrap();
+/// let received: DeserializedReport = serde_json::from_str(&json).unwrap();
+///
+/// let report = Report::new(received).wrap_err("worker 7 failed");
+/// assert_eq!(
+///     format!("{:#}", report),
+///     "worker 7 failed: failed to fetch shard 3: connection reset"
+/// );
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DeserializedReport {
+    chain: RenderedError,
+    #[serde(default)]
+    backtrace: Option<String>,
+    #[serde(default)]
+    sections: Vec<String>,
+}
+
+impl DeserializedReport {
+    /// The messages of the errors in the original chain, starting with the
+    /// outermost error.
+    pub fn chain(&self) -> impl Iterator<Item = &str> {
+        self.chain.chain()
+    }
+
+    /// The backtrace captured by the handler of the original report, if it
+    /// captured one.
+    pub fn backtrace(&self) -> Option<&str> {
+        self.backtrace.as_deref()
+    }
+
+    /// The sections attached to the original report.
+    pub fn sections(&self) -> &[String] {
+        &self.sections
+    }
+
+    /// Build the serialized form of `report`.
+    pub fn from_report(report: &Report) -> Self {
+        let received = report
+            .chain()
+            .find_map(|error| error.downcast_ref::<DeserializedReport>());
+
+        let mut sections = report.handler().sections();
+        if let Some(received) = received {
+            sections.extend(received.sections.iter().cloned());
+        }
+
+        let error = ErrorImpl::error(report.inner.as_ref());
+        DeserializedReport {
+            chain: RenderedError::new(error),
+            backtrace: report
+                .handler()
+                .captured_backtrace(error)
+                .or_else(|| received.and_then(|received| received.backtrace.clone())),
+            sections,
+        }
+    }
+}
+
+impl fmt::Debug for DeserializedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeserializedReport")
+            .field("chain", &self.chain().collect::<Vec<_>>())
+            .field("backtrace", &self.backtrace)
+            .field("sections", &self.sections)
+            .finish()
+    }
+}
+
+impl fmt::Display for DeserializedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.chain, f)
+    }
+}
+
+impl StdError for DeserializedReport {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.chain.source()
+    }
+}
+
+impl Serialize for Report {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DeserializedReport::from_report(self).serialize(serializer)
+    }
+}
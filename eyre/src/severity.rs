@@ -0,0 +1,62 @@
+use crate::Report;
+use core::fmt;
+
+/// How severe the problem described by a [`Report`] is.
+///
+/// Reports are [`Error`](Severity::Error)s unless a different severity is set
+/// via [`Report::set_severity`]. Handlers are informed about the severity via
+/// [`EyreHandler::set_severity`](crate::EyreHandler::set_severity), so they
+/// can render e.g. warnings differently from errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[non_exhaustive]
+pub enum Severity {
+    /// Informational, nothing went wrong
+    Info,
+    /// Something went wrong, but the operation could continue
+    Warning,
+    /// The operation failed
+    #[default]
+    Error,
+    /// The operation failed and the program can't continue
+    Fatal,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Fatal => "fatal",
+        })
+    }
+}
+
+impl Report {
+    /// Set how severe the problem described by this report is.
+    ///
+    /// The severity is kept when the report is wrapped with additional
+    /// context.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eyre::{eyre, Severity};
+    ///
+    /// let mut report = eyre!("config file not found, using defaults");
+    /// report.set_severity(Severity::Warning);
+    ///
+    /// let report = report.wrap_err("failed to load config");
+    /// assert_eq!(report.severity(), Severity::Warning);
+    /// ```
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.insert(severity);
+        self.handler_mut().set_severity(severity);
+    }
+
+    /// How severe the problem described by this report is, see
+    /// [`set_severity`](Report::set_severity).
+    pub fn severity(&self) -> Severity {
+        self.get::<Severity>().copied().unwrap_or_default()
+    }
+}
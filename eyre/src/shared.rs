@@ -0,0 +1,93 @@
+use crate::{Report, StdError};
+use core::fmt::{self, Debug, Display};
+use core::ops::Deref;
+use std::sync::Arc;
+
+/// A cheaply cloneable [`Report`], created with [`Report::into_shared`].
+///
+/// `SharedReport` keeps the report, including its handler and whatever the
+/// handler captured, behind an `Arc`. It formats exactly like the report it
+/// was created from, and the read-only methods of [`Report`] such as
+/// [`chain`](Report::chain), [`handler`](Report::handler) and
+/// [`downcast_ref`](Report::downcast_ref) are available through `Deref`.
+///
+/// Since `SharedReport` implements `std::error::Error`, it can be converted
+/// back into a new `Report` with `?` or [`Report::new`], whose chain starts
+/// with the messages of the shared report.
+#[derive(Clone)]
+pub struct SharedReport {
+    inner: Arc<Report>,
+}
+
+impl Report {
+    /// Convert this report into a [`SharedReport`] which can be cloned.
+    ///
+    /// This is useful for handing the same failure to several consumers,
+    /// e.g. every waiter of a retried operation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::{eyre, SharedReport};
+    ///
+    /// let report = eyre!("connection refused").wrap_err("failed to fetch index");
+    /// let shared: SharedReport = report.into_shared();
+    ///
+    /// let waiters = vec![shared.clone(), shared.clone(), shared];
+    /// for waiter in &waiters {
+    ///     assert_eq!(waiter.to_string(), "failed to fetch index");
+    ///     assert_eq!(waiter.root_cause().to_string(), "connection refused");
+    /// }
+    /// ```
+    pub fn into_shared(self) -> SharedReport {
+        SharedReport {
+            inner: Arc::new(self),
+        }
+    }
+}
+
+impl SharedReport {
+    /// Get back the original report if this is the only clone left.
+    ///
+    /// Otherwise the shared report is returned unchanged in the `Err` case.
+    pub fn try_unwrap(self) -> Result<Report, Self> {
+        Arc::try_unwrap(self.inner).map_err(|inner| SharedReport { inner })
+    }
+
+    /// Returns true if both shared reports were cloned from the same report.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.inner, &other.inner)
+    }
+}
+
+impl Deref for SharedReport {
+    type Target = Report;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl From<Report> for SharedReport {
+    fn from(report: Report) -> Self {
+        report.into_shared()
+    }
+}
+
+impl Debug for SharedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.inner, f)
+    }
+}
+
+impl Display for SharedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&*self.inner, f)
+    }
+}
+
+impl StdError for SharedReport {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        (**self.inner).source()
+    }
+}
@@ -0,0 +1,99 @@
+use crate::error::ErrorImpl;
+use crate::{RenderedError, Report};
+use core::fmt;
+
+/// An owned summary of a [`Report`], created with [`Report::snapshot`].
+///
+/// `Report` is neither `Clone` nor, with most handlers, cheap to move around
+/// once it has captured a backtrace. A snapshot holds only the rendered
+/// messages of the chain as a [`RenderedError`], the location the report was
+/// created at and the sections attached by its handler, so it can be cloned freely and sent to
+/// e.g. a UI thread. With the `serde` feature enabled it is also
+/// `Serialize` and `Deserialize`.
+///
+/// Like `Report`, the snapshot displays the outermost message, or the whole
+/// chain separated by `": "` with the alternate flag `{:#}`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{eyre, ReportSnapshot};
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel::<ReportSnapshot>();
+///
+/// let report = eyre!("connection reset").wrap_err("failed to sync");
+/// tx.send(report.snapshot()).unwrap();
+///
+/// let snapshot = rx.recv().unwrap();
+/// assert_eq!(snapshot.to_string(), "failed to sync");
+/// assert_eq!(snapshot.chain().collect::<Vec<_>>(), ["failed to sync", "connection reset"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportSnapshot {
+    chain: RenderedError,
+    location: Option<String>,
+    sections: Vec<String>,
+}
+
+impl ReportSnapshot {
+    /// The messages of the errors in the chain of the report, starting with
+    /// the outermost error.
+    pub fn chain(&self) -> impl Iterator<Item = &str> {
+        self.chain.chain()
+    }
+
+    /// The chain of the report as an error, see [`RenderedError`].
+    pub fn error(&self) -> &RenderedError {
+        &self.chain
+    }
+
+    /// Where the original report was created, formatted as
+    /// `file:line:column`, if it was captured.
+    ///
+    /// This is the location of the innermost error, not of any
+    /// [`wrap_err`](Report::wrap_err) calls made around it, see
+    /// [`Report::locations`] for those.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    /// The sections attached to the report by its handler, see
+    /// [`EyreHandler::sections`](crate::EyreHandler::sections).
+    pub fn sections(&self) -> &[String] {
+        &self.sections
+    }
+}
+
+impl fmt::Display for ReportSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut chain = self.chain();
+        if let Some(message) = chain.next() {
+            f.write_str(message)?;
+        }
+
+        if f.alternate() {
+            for message in chain {
+                write!(f, ": {}", message)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Report {
+    /// Create an owned, `Clone`able summary of this report.
+    ///
+    /// See [`ReportSnapshot`] for details.
+    pub fn snapshot(&self) -> ReportSnapshot {
+        ReportSnapshot {
+            chain: RenderedError::new(ErrorImpl::error(self.inner.as_ref())),
+            location: self.locations().last().map(ToString::to_string),
+            sections: self.handler().sections(),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{Report, ReportTap};
+
+impl<T> ReportTap<T> for Result<T, Report> {
+    #[cfg(feature = "log")]
+    fn log_err(self) -> Self {
+        self.tap_report(|report| log::error!("{:?}", report))
+    }
+
+    #[cfg(feature = "tracing")]
+    fn trace_err(self) -> Self {
+        self.tap_report(|report| tracing::error!("{:?}", report))
+    }
+
+    fn tap_report<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&Report),
+    {
+        if let Err(report) = &self {
+            f(report);
+        }
+        self
+    }
+}
+
+pub(crate) mod private {
+    use crate::Report;
+
+    pub trait Sealed {}
+
+    impl<T> Sealed for Result<T, Report> {}
+}
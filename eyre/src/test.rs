@@ -0,0 +1,120 @@
+//! Assertions for testing the reports returned by your code
+//!
+//! Checking the content of a report usually means formatting it and
+//! searching the output, or walking its chain by hand. The macros in this
+//! module do that for you, and print the whole chain or report when the
+//! assertion fails.
+//!
+//! - [`assert_err_chain!`](crate::assert_err_chain) asserts that a result is an
+//!   error with exactly the given chain of messages.
+//! - [`assert_report_contains!`](crate::assert_report_contains) asserts that
+//!   the `Debug` output of a report contains a string.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(not(feature = "auto-install"))]
+//! # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+//! use eyre::{assert_err_chain, assert_report_contains, eyre, Result, WrapErr};
+//!
+//! fn load_config() -> Result<()> {
+//!     Err(eyre!("file not found")).wrap_err("failed to load config")
+//! }
+//!
+//! assert_err_chain!(load_config(), ["failed to load config", "file not found"]);
+//! assert_report_contains!(load_config(), "file not found");
+//! ```
+use crate::Report;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+impl Report {
+    /// The messages of every error in the chain of this Report, outermost
+    /// first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eyre::eyre;
+    ///
+    /// let report = eyre!("file not found").wrap_err("failed to load config");
+    /// assert_eq!(report.chain_strings(), ["failed to load config", "file not found"]);
+    /// ```
+    pub fn chain_strings(&self) -> Vec<String> {
+        self.chain().map(|error| error.to_string()).collect()
+    }
+}
+
+#[doc(hidden)]
+#[cfg_attr(track_caller, track_caller)]
+pub fn assert_chain(expression: &str, actual: Option<Vec<String>>, expected: &[&str]) {
+    let actual = match actual {
+        Some(actual) => actual,
+        None => panic!(
+            "assertion failed: `{}` is an error\n expected chain: {:?}",
+            expression, expected
+        ),
+    };
+
+    if !actual
+        .iter()
+        .map(String::as_str)
+        .eq(expected.iter().copied())
+    {
+        panic!(
+            "assertion failed: error chain of `{}` matches\n   actual chain: {:?}\n expected chain: {:?}",
+            expression, actual, expected
+        );
+    }
+}
+
+#[doc(hidden)]
+#[cfg_attr(track_caller, track_caller)]
+pub fn assert_contains(expression: &str, report: &str, needle: &str) {
+    if !report.contains(needle) {
+        panic!(
+            "assertion failed: report of `{}` contains {:?}\nreport:\n{}",
+            expression, needle, report
+        );
+    }
+}
+
+/// Assert that a `Result<T, Report>` is an error whose chain consists of
+/// exactly the given messages, outermost first.
+///
+/// See the [`test`](crate::test) module for an example.
+#[macro_export]
+macro_rules! assert_err_chain {
+    ($result:expr, [$($msg:expr),* $(,)?] $(,)?) => {
+        match &$result {
+            $crate::private::Err(report) => $crate::test::assert_chain(
+                $crate::private::stringify!($result),
+                $crate::private::Some($crate::Report::chain_strings(report)),
+                &[$($msg),*],
+            ),
+            _ => $crate::test::assert_chain(
+                $crate::private::stringify!($result),
+                $crate::private::None,
+                &[$($msg),*],
+            ),
+        }
+    };
+}
+
+/// Assert that the `Debug` output of a `Report`, or of a `Result` holding
+/// one, contains the given string.
+///
+/// The `Debug` output is rendered by the installed handler, so this also
+/// matches sections, backtraces and other content it adds to the report.
+///
+/// See the [`test`](crate::test) module for an example.
+#[macro_export]
+macro_rules! assert_report_contains {
+    ($report:expr, $needle:expr $(,)?) => {
+        $crate::test::assert_contains(
+            $crate::private::stringify!($report),
+            &$crate::private::format!("{:?}", $report),
+            $needle,
+        )
+    };
+}
@@ -0,0 +1,87 @@
+use crate::{InstallError, Report};
+use once_cell::sync::OnceCell;
+
+/// A callback deciding whether a [`Report`] describes a transient failure,
+/// see [`set_transient_classifier`].
+pub type TransientClassifier = dyn Fn(&Report) -> bool + Send + Sync + 'static;
+
+static CLASSIFIER: OnceCell<Box<TransientClassifier>> = OnceCell::new();
+
+/// Whether a report was explicitly marked as transient or permanent via
+/// [`Report::set_transient`]
+#[derive(Debug, Clone, Copy)]
+struct Transient(bool);
+
+/// Install a callback which classifies the [`Report`]s that weren't
+/// explicitly marked via [`Report::set_transient`], see
+/// [`Report::is_transient`].
+///
+/// This allows teaching retry logic about the errors of the libraries in use
+/// in one place, instead of downcasting the chain wherever a retry decision
+/// is made. Like [`set_hook`](crate::set_hook), a classifier can only be
+/// installed once.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{Report, WrapErr};
+/// use std::io;
+///
+/// eyre::set_transient_classifier(Box::new(|report| {
+///     report.chain().any(|error| {
+///         matches!(
+///             error.downcast_ref::<io::Error>().map(io::Error::kind),
+///             Some(io::ErrorKind::TimedOut | io::ErrorKind::Interrupted)
+///         )
+///     })
+/// }))
+/// .unwrap();
+///
+/// let report = Report::new(io::Error::from(io::ErrorKind::TimedOut)).wrap_err("failed to fetch");
+/// assert!(report.is_transient());
+/// ```
+pub fn set_transient_classifier(classifier: Box<TransientClassifier>) -> Result<(), InstallError> {
+    CLASSIFIER.set(classifier).map_err(|_| InstallError)
+}
+
+impl Report {
+    /// Mark this report as describing a transient failure which may succeed
+    /// when retried, or as a permanent one.
+    ///
+    /// The mark is kept when the report is wrapped with additional context,
+    /// and takes precedence over the classifier installed via
+    /// [`set_transient_classifier`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eyre::eyre;
+    ///
+    /// let mut report = eyre!("service unavailable");
+    /// report.set_transient(true);
+    ///
+    /// let report = report.wrap_err("failed to fetch index");
+    /// assert!(report.is_transient());
+    /// ```
+    pub fn set_transient(&mut self, transient: bool) {
+        self.insert(Transient(transient));
+    }
+
+    /// Whether this report describes a transient failure which may succeed
+    /// when retried.
+    ///
+    /// Returns the mark set via [`set_transient`](Report::set_transient) if
+    /// any, and otherwise asks the classifier installed via
+    /// [`set_transient_classifier`]. Reports are permanent if neither
+    /// applies.
+    pub fn is_transient(&self) -> bool {
+        match self.get::<Transient>() {
+            Some(Transient(transient)) => *transient,
+            None => CLASSIFIER
+                .get()
+                .map_or(false, |classifier| classifier(self)),
+        }
+    }
+}
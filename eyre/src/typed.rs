@@ -0,0 +1,261 @@
+//! Reports whose handler type is known at compile time
+//!
+//! A [`crate::Report`] gets its handler from the globally installed hook, so
+//! reading data a custom handler captured, such as an HTTP status code,
+//! requires downcasting the `dyn EyreHandler` it holds. [`Report<H>`] is a
+//! thin wrapper around it which always captures its handler with
+//! [`CaptureHandler::capture`] instead, and hands out `&H` directly.
+//!
+//! # Example
+//!
+//! ```
+//! use eyre::typed::{self, CaptureHandler};
+//! use eyre::EyreHandler;
+//! use std::error::Error;
+//! use std::fmt;
+//!
+//! struct HttpHandler {
+//!     status: u16,
+//! }
+//!
+//! impl EyreHandler for HttpHandler {
+//!     fn debug(&self, error: &(dyn Error + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+//!         write!(f, "{} {}", self.status, error)
+//!     }
+//! }
+//!
+//! impl CaptureHandler for HttpHandler {
+//!     fn capture(_: &(dyn Error + 'static)) -> Self {
+//!         HttpHandler { status: 500 }
+//!     }
+//! }
+//!
+//! fn find_user(id: u32) -> typed::Result<(), HttpHandler> {
+//!     let mut report = typed::Report::<HttpHandler>::msg(format!("no user with id {}", id));
+//!     report.handler_mut().status = 404;
+//!     Err(report.wrap_err("failed to handle request"))
+//! }
+//!
+//! let report = find_user(7).unwrap_err();
+//! assert_eq!(report.handler().status, 404);
+//! assert_eq!(format!("{:?}", report), "404 failed to handle request");
+//! ```
+use crate::wrapper::MessageError;
+use crate::{EyreHandler, StdError};
+use core::fmt::{self, Debug, Display};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+/// A handler that can be captured by a [`Report<H>`] without going through
+/// the installed hook
+pub trait CaptureHandler: EyreHandler + Sized {
+    /// Capture a new handler for a report created from `error`
+    fn capture(error: &(dyn StdError + 'static)) -> Self;
+}
+
+/// `Result<T, typed::Report<H>>`
+pub type Result<T, H> = core::result::Result<T, Report<H>>;
+
+/// A [`crate::Report`] whose handler is always of type `H`
+///
+/// All read-only methods of [`crate::Report`] are available through `Deref`,
+/// while methods which consume or mutate the report are forwarded so that
+/// they keep the handler type.
+pub struct Report<H> {
+    inner: crate::Report,
+    _handler: PhantomData<fn() -> H>,
+}
+
+#[cfg_attr(track_caller, track_caller)]
+#[cfg_attr(not(track_caller), allow(unused_mut))]
+fn capture<H>(error: &(dyn StdError + 'static)) -> Box<dyn EyreHandler>
+where
+    H: CaptureHandler,
+{
+    let mut handler = H::capture(error);
+
+    #[cfg(track_caller)]
+    handler.track_caller(std::panic::Location::caller());
+
+    Box::new(handler)
+}
+
+impl<H> Report<H>
+where
+    H: CaptureHandler,
+{
+    /// Create a new report from any error type, see [`crate::Report::new`].
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn new<E>(error: E) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        let handler = capture::<H>(&error);
+        Report::wrap(crate::Report::from_std_with_handler(error, handler))
+    }
+
+    /// Create a new report from a printable error message, see
+    /// [`crate::Report::msg`].
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn msg<M>(message: M) -> Self
+    where
+        M: Display + Debug + Send + Sync + 'static,
+    {
+        let error = MessageError(message);
+        let handler = capture::<H>(&error);
+        Report::wrap(crate::Report::from_adhoc_with_handler(error, handler))
+    }
+
+    /// Convert an untyped report, keeping its handler if it is an `H` and
+    /// capturing a new one otherwise.
+    ///
+    /// Whatever the previous handler captured, such as its backtrace, is lost
+    /// when it is replaced.
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn from_report(mut report: crate::Report) -> Self {
+        if !report.handler().is::<H>() {
+            let handler = capture::<H>(&*report);
+            report.set_handler(handler);
+        }
+
+        Report::wrap(report)
+    }
+}
+
+impl<H> Report<H>
+where
+    H: EyreHandler,
+{
+    fn wrap(inner: crate::Report) -> Self {
+        debug_assert!(inner.handler().is::<H>());
+
+        Report {
+            inner,
+            _handler: PhantomData,
+        }
+    }
+
+    /// Get a reference to the handler of this report.
+    pub fn handler(&self) -> &H {
+        self.inner
+            .handler()
+            .downcast_ref()
+            .expect("typed reports always hold a handler of their handler type")
+    }
+
+    /// Get a mutable reference to the handler of this report.
+    pub fn handler_mut(&mut self) -> &mut H {
+        self.inner
+            .handler_mut()
+            .downcast_mut()
+            .expect("typed reports always hold a handler of their handler type")
+    }
+
+    /// Wrap the error value with additional context, see
+    /// [`crate::Report::wrap_err`].
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn wrap_err<D>(self, msg: D) -> Self
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        Report::wrap(self.inner.wrap_err(msg))
+    }
+
+    /// Wrap the error value with additional context that is evaluated lazily,
+    /// see [`crate::Report::wrap_err_with`].
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn wrap_err_with<D, F>(self, msg: F) -> Self
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        Report::wrap(self.inner.wrap_err_with(msg))
+    }
+
+    /// Downcast this report to the error type `E`, see
+    /// [`crate::Report::downcast`].
+    pub fn downcast<E>(self) -> core::result::Result<E, Self>
+    where
+        E: Display + Debug + Send + Sync + 'static,
+    {
+        self.inner.downcast().map_err(Report::wrap)
+    }
+
+    /// Get a mutable reference to the error of type `E` in this report's
+    /// chain, see [`crate::Report::downcast_mut`].
+    pub fn downcast_mut<E>(&mut self) -> Option<&mut E>
+    where
+        E: Display + Debug + Send + Sync + 'static,
+    {
+        self.inner.downcast_mut()
+    }
+
+    /// Attach a typed value to this report, see [`crate::Report::insert`].
+    pub fn insert<T>(&mut self, value: T) -> Option<T>
+    where
+        T: core::any::Any + Send + Sync,
+    {
+        self.inner.insert(value)
+    }
+
+    /// Get a mutable reference to the value of type `T` attached to this
+    /// report, see [`crate::Report::get_mut`].
+    pub fn get_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: core::any::Any + Send + Sync,
+    {
+        self.inner.get_mut()
+    }
+
+    /// Remove the value of type `T` attached to this report, see
+    /// [`crate::Report::remove`].
+    pub fn remove<T>(&mut self) -> Option<T>
+    where
+        T: core::any::Any + Send + Sync,
+    {
+        self.inner.remove()
+    }
+
+    /// Convert this report into an untyped [`crate::Report`], keeping its
+    /// handler.
+    pub fn into_report(self) -> crate::Report {
+        self.inner
+    }
+}
+
+impl<H> Deref for Report<H> {
+    type Target = crate::Report;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<E, H> From<E> for Report<H>
+where
+    E: StdError + Send + Sync + 'static,
+    H: CaptureHandler,
+{
+    #[cfg_attr(track_caller, track_caller)]
+    fn from(error: E) -> Self {
+        Report::new(error)
+    }
+}
+
+impl<H> From<Report<H>> for crate::Report {
+    fn from(report: Report<H>) -> Self {
+        report.inner
+    }
+}
+
+impl<H> Debug for Report<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+impl<H> Display for Report<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
@@ -0,0 +1,162 @@
+use crate::Report;
+use alloc::borrow::Cow;
+use core::fmt::{self, Debug, Display};
+use std::error::Error as StdError;
+
+/// Accumulates validation failures into a single [`Report`]
+///
+/// Validating configuration files or user input usually means checking many
+/// independent conditions, and reporting only the first failed check forces
+/// users to fix their input one error at a time. A `Validator` records every
+/// failed check instead, and [`finish`][Validator::finish] returns a report
+/// listing all of them.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(not(feature = "auto-install"))]
+/// # eyre::set_hook(Box::new(eyre::DefaultHandler::default_with)).unwrap();
+/// use eyre::{Result, Validator};
+///
+/// struct Config<'a> {
+///     name: &'a str,
+///     port: &'a str,
+/// }
+///
+/// fn validate(config: &Config<'_>) -> Result<u16> {
+///     let mut validator = Validator::new();
+///
+///     validator.check(!config.name.is_empty(), "name must not be empty");
+///     let port = validator.check_field("port", config.port.parse::<u16>());
+///
+///     validator.finish()?;
+///     Ok(port.unwrap())
+/// }
+///
+/// let report = validate(&Config { name: "", port: "http" }).unwrap_err();
+/// assert_eq!(
+///     report.to_string(),
+///     "2 validation errors\n\
+///      - name must not be empty\n\
+///      - port: invalid digit found in string",
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct Validator {
+    failures: Vec<(Option<Cow<'static, str>>, Report)>,
+}
+
+impl Validator {
+    /// Create a `Validator` without any failures
+    pub fn new() -> Self {
+        Validator::default()
+    }
+
+    /// Record a failure with the given message if `cond` is `false`
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn check<M>(&mut self, cond: bool, msg: M) -> &mut Self
+    where
+        M: Display + Debug + Send + Sync + 'static,
+    {
+        if !cond {
+            self.failures.push((None, Report::msg(msg)));
+        }
+
+        self
+    }
+
+    /// Record the error of `result` as a failure of the field `name`
+    ///
+    /// Returns the value of `result` if it was successful.
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn check_field<T, E>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        result: Result<T, E>,
+    ) -> Option<T>
+    where
+        E: Into<Report>,
+    {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.failures.push((Some(name.into()), error.into()));
+                None
+            }
+        }
+    }
+
+    /// Returns `true` if no check has failed so far
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Finish validating, returning a [`Report`] wrapping [`ValidationErrors`]
+    /// if any check failed
+    #[cfg_attr(track_caller, track_caller)]
+    pub fn finish(self) -> Result<(), Report> {
+        if self.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Report::new(ValidationErrors {
+                failures: self.failures,
+            }))
+        }
+    }
+}
+
+/// The failures recorded by a [`Validator`]
+///
+/// Reports returned by [`Validator::finish`] can be downcast to this type to
+/// inspect the individual failures.
+pub struct ValidationErrors {
+    failures: Vec<(Option<Cow<'static, str>>, Report)>,
+}
+
+impl ValidationErrors {
+    /// The number of failed checks
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Returns `true` if no check failed
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Iterate over the failures along with the name of the field that
+    /// failed, if they were recorded with [`Validator::check_field`]
+    pub fn iter(&self) -> impl Iterator<Item = (Option<&str>, &Report)> {
+        self.failures
+            .iter()
+            .map(|(field, report)| (field.as_deref(), report))
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} validation error{}",
+            self.failures.len(),
+            if self.failures.len() == 1 { "" } else { "s" }
+        )?;
+
+        for (field, report) in &self.failures {
+            match field {
+                Some(field) => write!(f, "\n- {}: {:#}", field, report)?,
+                None => write!(f, "\n- {:#}", report)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.failures.iter()).finish()
+    }
+}
+
+impl StdError for ValidationErrors {}
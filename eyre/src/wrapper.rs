@@ -1,5 +1,7 @@
-use crate::StdError;
+use crate::error::ErrorImpl;
+use crate::{Report, StdError};
 use core::fmt::{self, Debug, Display};
+use once_cell::sync::OnceCell;
 
 #[repr(transparent)]
 pub(crate) struct DisplayError<M>(pub(crate) M);
@@ -91,3 +93,243 @@ impl StdError for BoxedError {
         self.0.source()
     }
 }
+
+/// A lightweight error holding only the rendered messages of a chain of errors
+///
+/// Created by [`Report::into_display_error`][crate::Report::into_display_error].
+/// Unlike a `Report` it has no handler or backtrace and is `Clone`, which
+/// makes it suitable for embedding in public error enums or in results that
+/// are shared between tasks. Its `Display` output and `source` chain match
+/// those of the report it was created from.
+///
+/// This is also the owned chain held by [`ReportSnapshot`][crate::ReportSnapshot]
+/// and, with the `serde` feature, [`DeserializedReport`][crate::DeserializedReport].
+/// With the `serde` feature it serializes as the list of messages in its
+/// chain, outermost first.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RenderedError {
+    message: String,
+    // The `Debug` output of the original error, kept by `Report::msg_from_error`.
+    debug: Option<String>,
+    source: Option<Box<RenderedError>>,
+}
+
+impl RenderedError {
+    pub(crate) fn new<E>(error: &E) -> Self
+    where
+        E: StdError + ?Sized,
+    {
+        RenderedError {
+            message: error.to_string(),
+            debug: None,
+            source: error
+                .source()
+                .map(|source| Box::new(RenderedError::new(source))),
+        }
+    }
+
+    /// Like `new`, but also keeps the `Debug` output of each error so the
+    /// chain prints the same as the original with `{:?}`.
+    pub(crate) fn with_debug<E>(error: &E) -> Self
+    where
+        E: StdError + ?Sized,
+    {
+        RenderedError {
+            message: error.to_string(),
+            debug: Some(format!("{:?}", error)),
+            source: error
+                .source()
+                .map(|source| Box::new(RenderedError::with_debug(source))),
+        }
+    }
+
+    /// Build a chain from its messages, outermost first.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_messages<I>(messages: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        let mut messages = messages.into_iter().rev();
+        let root = RenderedError {
+            message: messages.next().unwrap_or_default(),
+            debug: None,
+            source: None,
+        };
+
+        messages.fold(root, |source, message| RenderedError {
+            message,
+            debug: None,
+            source: Some(Box::new(source)),
+        })
+    }
+
+    /// The messages of the errors in the chain, starting with this one.
+    pub fn chain(&self) -> impl Iterator<Item = &str> {
+        core::iter::successors(Some(self), |error| error.source.as_deref())
+            .map(|error| error.message.as_str())
+    }
+}
+
+impl Debug for RenderedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.debug {
+            Some(debug) => f.write_str(debug),
+            None => f
+                .debug_struct("RenderedError")
+                .field("message", &self.message)
+                .field("source", &self.source)
+                .finish(),
+        }
+    }
+}
+
+impl Display for RenderedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl StdError for RenderedError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source.as_ref().map(|source| &**source as _)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RenderedError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.chain())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RenderedError {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<String>::deserialize(deserializer).map(RenderedError::from_messages)
+    }
+}
+
+/// The error a report was created from, followed by a source that was
+/// attached afterwards via [`Report::with_source`].
+///
+/// Its `Debug` and `Display` impls are the same as the wrapped error. Its
+/// `source` chain visits the sources of the wrapped error first and ends with
+/// the attached source.
+pub(crate) struct SourceError {
+    pub(crate) error: Report,
+    pub(crate) source: Report,
+    /// The sources of `error`, built the first time the chain is walked.
+    ///
+    /// The links point into `error`, `source` and the links themselves, all of
+    /// which live in separate heap allocations, so they stay valid as long as
+    /// neither report is modified. Code handing out mutable access to either
+    /// report must call `invalidate_links` first.
+    links: OnceCell<Box<[SourceLink]>>,
+}
+
+impl SourceError {
+    pub(crate) fn new(error: Report, source: Report) -> Self {
+        SourceError {
+            error,
+            source,
+            links: OnceCell::new(),
+        }
+    }
+
+    pub(crate) fn invalidate_links(&mut self) {
+        self.links.take();
+    }
+
+    fn build_links(&self) -> Box<[SourceLink]> {
+        let attached: *const (dyn StdError + 'static) =
+            ErrorImpl::error(self.source.inner.as_ref());
+        let mut links: Box<[SourceLink]> = core::iter::successors(
+            ErrorImpl::error(self.error.inner.as_ref()).source(),
+            |error| (*error).source(),
+        )
+        .map(|error| SourceLink {
+            error,
+            next: attached,
+        })
+        .collect();
+
+        // The boxed slice doesn't move anymore, so each link can now point to
+        // the one after it.
+        for index in 1..links.len() {
+            let next: *const SourceLink = &links[index];
+            links[index - 1].next = next;
+        }
+
+        links
+    }
+}
+
+/// One of the sources of the error wrapped by a [`SourceError`], followed in
+/// the chain by the next one or by the attached source.
+struct SourceLink {
+    error: *const (dyn StdError + 'static),
+    next: *const (dyn StdError + 'static),
+}
+
+// Safety: the links only point to errors owned by the `SourceError` holding
+// them, whose reports are `Send` and `Sync`.
+unsafe impl Send for SourceLink {}
+unsafe impl Sync for SourceLink {}
+
+impl SourceLink {
+    fn error(&self) -> &(dyn StdError + 'static) {
+        // Safety: see `SourceError::links`
+        unsafe { &*self.error }
+    }
+}
+
+impl Debug for SourceLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.error(), f)
+    }
+}
+
+impl Display for SourceLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.error(), f)
+    }
+}
+
+impl StdError for SourceLink {
+    #[cfg(generic_member_access)]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        self.error().provide(request);
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        // Safety: see `SourceError::links`
+        Some(unsafe { &*self.next })
+    }
+}
+
+impl Debug for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(ErrorImpl::error(self.error.inner.as_ref()), f)
+    }
+}
+
+impl Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(ErrorImpl::error(self.error.inner.as_ref()), f)
+    }
+}
+
+impl StdError for SourceError {
+    #[cfg(generic_member_access)]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        ErrorImpl::error(self.error.inner.as_ref()).provide(request);
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self.links.get_or_init(|| self.build_links()).first() {
+            Some(link) => Some(link),
+            None => Some(ErrorImpl::error(self.source.inner.as_ref())),
+        }
+    }
+}
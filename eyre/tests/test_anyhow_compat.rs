@@ -0,0 +1,104 @@
+#![cfg(feature = "anyhow-compat")]
+
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::compat::{AnyhowError, IntoEyre};
+use eyre::WrapErr;
+use std::fmt::{self, Display};
+
+#[derive(Debug, PartialEq)]
+struct RootError(u32);
+
+impl Display for RootError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "root error {}", self.0)
+    }
+}
+
+impl std::error::Error for RootError {}
+
+fn anyhow_result() -> anyhow::Result<()> {
+    Err(anyhow::Error::new(RootError(1)).context(String::from("anyhow context")))
+}
+
+#[test]
+fn test_downcast_ref() {
+    maybe_install_handler().unwrap();
+
+    let report = anyhow_result()
+        .into_eyre()
+        .wrap_err("eyre context")
+        .unwrap_err();
+
+    assert_eq!(report.downcast_ref::<RootError>(), Some(&RootError(1)));
+    assert_eq!(report.downcast_ref::<String>().unwrap(), "anyhow context");
+    assert!(report.is::<AnyhowError>());
+    assert!(!report.is::<std::io::Error>());
+
+    let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["eyre context", "anyhow context", "root error 1"]);
+}
+
+#[test]
+fn test_downcast() {
+    maybe_install_handler().unwrap();
+
+    let report = anyhow_result()
+        .into_eyre()
+        .wrap_err("eyre context")
+        .unwrap_err();
+    let report = report.downcast::<std::io::Error>().unwrap_err();
+    assert_eq!(report.downcast::<RootError>().unwrap(), RootError(1));
+
+    let report = anyhow_result().into_eyre().unwrap_err();
+    assert_eq!(report.downcast::<String>().unwrap(), "anyhow context");
+}
+
+#[test]
+fn test_downcast_mut() {
+    maybe_install_handler().unwrap();
+
+    let mut report = anyhow_result().into_eyre().unwrap_err();
+    report.downcast_mut::<RootError>().unwrap().0 = 2;
+    assert_eq!(report.root_cause().to_string(), "root error 2");
+}
+
+#[test]
+fn test_into_anyhow() {
+    use eyre::compat::{EyreError, IntoAnyhow};
+
+    maybe_install_handler().unwrap();
+
+    let report = eyre::Report::new(RootError(3)).wrap_err("eyre context");
+    let error = Err::<(), _>(report)
+        .into_anyhow()
+        .map_err(|error| error.context("anyhow context"))
+        .unwrap_err();
+
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["anyhow context", "eyre context", "root error 3"]);
+    assert_eq!(
+        format!("{:#}", error),
+        "anyhow context: eyre context: root error 3"
+    );
+
+    let report = error.downcast_ref::<EyreError>().unwrap().report();
+    assert_eq!(report.downcast_ref::<RootError>(), Some(&RootError(3)));
+}
+
+#[test]
+fn test_round_trip() {
+    use eyre::compat::IntoAnyhow;
+
+    maybe_install_handler().unwrap();
+
+    let report = eyre::Report::new(RootError(4)).wrap_err("eyre context");
+    let report = report.into_anyhow().into_eyre();
+    assert!(!report.is::<AnyhowError>());
+    assert_eq!(*report.downcast_ref::<&str>().unwrap(), "eyre context");
+
+    let error = anyhow::Error::new(RootError(5));
+    let error = error.into_eyre().into_anyhow();
+    assert_eq!(error.downcast::<RootError>().unwrap(), RootError(5));
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{assert_err_chain, assert_report_contains, eyre, Result, WrapErr};
+
+fn load_config() -> Result<()> {
+    Err(eyre!("file not found")).wrap_err("failed to load config")
+}
+
+#[test]
+fn test_chain_strings() {
+    maybe_install_handler().unwrap();
+
+    let report = load_config().unwrap_err().wrap_err("failed to start");
+    assert_eq!(
+        report.chain_strings(),
+        ["failed to start", "failed to load config", "file not found"]
+    );
+}
+
+#[test]
+fn test_assert_err_chain() {
+    maybe_install_handler().unwrap();
+
+    assert_err_chain!(load_config(), ["failed to load config", "file not found"]);
+    assert_err_chain!(
+        load_config().wrap_err("failed to start"),
+        ["failed to start", "failed to load config", "file not found",],
+    );
+}
+
+#[test]
+#[should_panic(expected = "error chain of `load_config()` matches")]
+fn test_assert_err_chain_mismatch() {
+    maybe_install_handler().unwrap();
+
+    assert_err_chain!(load_config(), ["failed to load config"]);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `Ok::<(), eyre::Report>(())` is an error")]
+fn test_assert_err_chain_ok() {
+    maybe_install_handler().unwrap();
+
+    assert_err_chain!(Ok::<(), eyre::Report>(()), ["file not found"]);
+}
+
+#[test]
+fn test_assert_report_contains() {
+    maybe_install_handler().unwrap();
+
+    assert_report_contains!(load_config(), "file not found");
+    assert_report_contains!(load_config().unwrap_err(), "failed to load config");
+}
+
+#[test]
+#[should_panic(expected = "report of `load_config()` contains \"permission denied\"")]
+fn test_assert_report_contains_mismatch() {
+    maybe_install_handler().unwrap();
+
+    assert_report_contains!(load_config(), "permission denied");
+}
@@ -11,3 +11,15 @@ fn test_sync() {
     fn assert_sync<T: Sync>() {}
     assert_sync::<Report>();
 }
+
+#[test]
+fn test_unwind_safe() {
+    fn assert_unwind_safe<T: std::panic::UnwindSafe>() {}
+    assert_unwind_safe::<Report>();
+}
+
+#[test]
+fn test_ref_unwind_safe() {
+    fn assert_ref_unwind_safe<T: std::panic::RefUnwindSafe>() {}
+    assert_ref_unwind_safe::<Report>();
+}
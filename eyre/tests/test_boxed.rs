@@ -1,7 +1,7 @@
 mod common;
 
 use self::common::maybe_install_handler;
-use eyre::{eyre, Report};
+use eyre::{eyre, Report, WrapBoxedErr};
 use std::error::Error as StdError;
 use std::io;
 use thiserror::Error;
@@ -64,3 +64,22 @@ fn test_boxed_sources() {
         error.source().unwrap().source().unwrap().to_string()
     );
 }
+
+#[test]
+fn test_wrap_boxed_err() {
+    maybe_install_handler().unwrap();
+
+    let result: Result<(), Box<dyn StdError + Send + Sync>> = Err(Box::new(MyError {
+        source: io::Error::new(io::ErrorKind::Other, "oh no!"),
+    }));
+    let error = result.wrap_boxed_err("it failed").unwrap_err();
+
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["it failed", "outer", "oh no!"]);
+    assert!(error.is::<Box<dyn StdError + Send + Sync>>());
+
+    let result: Result<(), Box<dyn StdError + Send + Sync>> = Ok(());
+    result
+        .wrap_boxed_err_with(|| -> &str { unreachable!() })
+        .unwrap();
+}
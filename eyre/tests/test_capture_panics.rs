@@ -0,0 +1,40 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{capture_panics, PanicError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PREVIOUS_HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+fn test_capture_panics() {
+    maybe_install_handler().unwrap();
+
+    std::panic::set_hook(Box::new(|_| {
+        PREVIOUS_HOOK_CALLS.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    assert_eq!(capture_panics(|| 42).unwrap(), 42);
+
+    let report = capture_panics(|| -> () { panic!("plugin {} crashed", 7) }).unwrap_err();
+    let panic = report.downcast_ref::<PanicError>().unwrap();
+    assert_eq!(panic.message(), "plugin 7 crashed");
+    assert!(panic.location().unwrap().contains("test_capture_panics.rs"));
+    assert!(report.to_string().starts_with("panicked at "));
+    assert!(report.to_string().ends_with(": plugin 7 crashed"));
+
+    // Captured panics don't reach the previous hook
+    assert_eq!(PREVIOUS_HOOK_CALLS.load(Ordering::SeqCst), 0);
+
+    // Nested captures report the innermost panic
+    let outer = capture_panics(|| capture_panics(|| -> () { panic!("inner") }).unwrap_err());
+    let inner = outer.unwrap();
+    assert_eq!(
+        inner.downcast_ref::<PanicError>().unwrap().message(),
+        "inner"
+    );
+
+    // The previous hook is restored afterwards
+    let _ = std::panic::catch_unwind(|| panic!("uncaptured"));
+    assert_eq!(PREVIOUS_HOOK_CALLS.load(Ordering::SeqCst), 1);
+}
@@ -52,3 +52,57 @@ fn test_len() {
     assert_eq!(0, chain.len());
     assert!(chain.next().is_none());
 }
+
+#[derive(Debug)]
+struct Outer(Inner);
+
+impl std::fmt::Display for Outer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("outer")
+    }
+}
+
+impl std::error::Error for Outer {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Debug)]
+struct Inner(u8);
+
+impl std::fmt::Display for Inner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "inner {}", self.0)
+    }
+}
+
+impl std::error::Error for Inner {}
+
+#[test]
+fn test_find() {
+    maybe_install_handler().unwrap();
+
+    let mut e = Report::new(Outer(Inner(7))).wrap_err("context");
+
+    assert!(e.downcast_ref::<Inner>().is_none());
+    assert_eq!(7, e.find::<Inner>().unwrap().0);
+    assert_eq!(7, e.root_cause_downcast::<Inner>().unwrap().0);
+    assert!(e.root_cause_downcast::<Outer>().is_none());
+    assert!(e.find::<std::io::Error>().is_none());
+
+    // Sources can't be accessed mutably
+    assert!(e.find_mut::<Inner>().is_none());
+    e.find_mut::<Outer>().unwrap().0 .0 = 8;
+    assert_eq!("inner 8", e.root_cause().to_string());
+}
+
+#[test]
+fn test_find_context() {
+    maybe_install_handler().unwrap();
+
+    let e = eyre!("root").wrap_err(Inner(1)).wrap_err(Inner(2));
+
+    assert_eq!(2, e.find::<Inner>().unwrap().0);
+    assert!(e.root_cause_downcast::<Inner>().is_none());
+}
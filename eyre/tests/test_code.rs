@@ -0,0 +1,49 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::code::ErrorCode;
+use eyre::{eyre, Report};
+use std::io;
+
+#[test]
+fn test_with_code() {
+    maybe_install_handler().unwrap();
+
+    let report = eyre!("connection refused");
+    assert_eq!(report.code(), None);
+
+    let report = report.with_code("NET001").wrap_err("failed to fetch index");
+    assert_eq!(report.code(), Some("NET001"));
+    assert_eq!(report.get::<ErrorCode>(), Some(&ErrorCode::new("NET001")));
+
+    let report = report.with_code(format!("NET{:03}", 2));
+    assert_eq!(report.code(), Some("NET002"));
+}
+
+#[test]
+fn test_macro() {
+    maybe_install_handler().unwrap();
+
+    let name = "x";
+    let report = eyre!(code = "E0425", "cannot find value `{}` in this scope", name);
+    assert_eq!(report.code(), Some("E0425"));
+    assert_eq!(report.to_string(), "cannot find value `x` in this scope");
+
+    let error = io::Error::new(io::ErrorKind::NotFound, "oh no!");
+    let report = eyre!(code = "E0001", source = error, "failed to open");
+    assert_eq!(report.code(), Some("E0001"));
+    assert_eq!(report.chain().count(), 2);
+}
+
+#[test]
+fn test_default_handler() {
+    maybe_install_handler().unwrap();
+
+    let report = Report::msg("cannot find value `x` in this scope").with_code("E0425");
+    let output = format!("{:?}", report);
+    assert!(
+        output.starts_with("error[E0425]: cannot find value `x` in this scope"),
+        "{}",
+        output
+    );
+}
@@ -3,7 +3,7 @@ mod drop;
 
 use crate::common::maybe_install_handler;
 use crate::drop::{DetectDrop, Flag};
-use eyre::{Report, Result, WrapErr};
+use eyre::{Report, Result, WrapErr, WrapReportErr};
 use std::fmt::{self, Display};
 use thiserror::Error;
 
@@ -171,3 +171,116 @@ fn test_unsuccessful_downcast() {
     drop(err);
     assert!(dropped.all());
 }
+
+#[derive(Debug)]
+struct OtherReport(&'static str);
+
+impl From<OtherReport> for Report {
+    fn from(report: OtherReport) -> Self {
+        Report::msg(report.0.to_owned())
+    }
+}
+
+#[test]
+fn test_wrap_into_report() {
+    maybe_install_handler().unwrap();
+
+    let result: Result<(), OtherReport> = Err(OtherReport("oh no!"));
+    let error = result.wrap_report_err("context").unwrap_err();
+
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["context", "oh no!"]);
+    assert_eq!(error.downcast_ref::<String>().unwrap(), "oh no!");
+}
+
+#[test]
+fn test_wrap_err_dedup() {
+    maybe_install_handler().unwrap();
+
+    fn attempt(previous: Option<Report>) -> Result<(), Report> {
+        Err(previous.unwrap_or_else(|| eyre::eyre!("connection refused")))
+    }
+
+    let mut result = attempt(None).wrap_err_dedup("failed to reach the server");
+    for _ in 0..3 {
+        result = attempt(result.err()).wrap_err_dedup("failed to reach the server");
+    }
+
+    let error = result.unwrap_err();
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["failed to reach the server", "connection refused"]);
+
+    let error = error.wrap_err_once("while syncing");
+    assert_eq!(error.chain().count(), 3);
+}
+
+#[test]
+fn test_change_context() {
+    maybe_install_handler().unwrap();
+
+    let mut result: Result<(), Report> = Err(eyre::eyre!("connection refused"));
+    for attempt in 1..=3 {
+        result = result.change_context(format!("attempt {} failed", attempt));
+    }
+
+    let error = result.unwrap_err();
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["attempt 3 failed", "connection refused"]);
+
+    // Dropping the old message must not drop the error it was attached to
+    let dropped = Flag::new();
+    let error = error
+        .wrap_err(DetectDrop::new("context", &dropped))
+        .change_context("replaced");
+    assert!(dropped.get());
+    assert_eq!(
+        format!("{:#}", error),
+        "replaced: attempt 3 failed: connection refused"
+    );
+    assert_eq!(error.root_cause().to_string(), "connection refused");
+}
+
+#[test]
+fn test_wrapped_error_layer() {
+    maybe_install_handler().unwrap();
+
+    let error = || Err::<(), _>(std::io::Error::new(std::io::ErrorKind::Other, "oh no!"));
+
+    let report = error().change_context("replaced").unwrap_err();
+    assert_eq!(format!("{:#}", report), "replaced: oh no!");
+
+    let report = error()
+        .wrap_err("failed to read")
+        .unwrap_err()
+        .change_context("failed to load");
+    assert_eq!(format!("{:#}", report), "failed to load: oh no!");
+    assert!(report.downcast_ref::<std::io::Error>().is_some());
+
+    let report = error()
+        .wrap_err("failed to read")
+        .unwrap_err()
+        .with_source(std::io::Error::new(std::io::ErrorKind::Other, "disk full"));
+    let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["failed to read", "oh no!", "disk full"]);
+    assert_eq!(report.downcast::<&str>().unwrap(), "failed to read");
+}
+
+#[test]
+fn test_locations() {
+    maybe_install_handler().unwrap();
+
+    let line = line!();
+    let report = Err::<(), _>(eyre::eyre!("connection refused"))
+        .wrap_err("failed to connect")
+        .wrap_err("attempt 3 failed")
+        .unwrap_err();
+
+    let locations: Vec<_> = report.locations().collect();
+    if cfg!(feature = "track-caller") {
+        let lines: Vec<_> = locations.iter().map(|location| location.line()).collect();
+        assert_eq!(lines, [line + 3, line + 2, line + 1]);
+        assert!(locations.iter().all(|location| location.file() == file!()));
+    } else {
+        assert!(locations.is_empty());
+    }
+}
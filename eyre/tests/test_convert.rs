@@ -26,3 +26,34 @@ fn test_question_mark() -> Result<(), Box<dyn StdError>> {
     f()?;
     Ok(())
 }
+
+#[test]
+fn test_into_parts() {
+    maybe_install_handler().unwrap();
+
+    let has_dropped = Flag::new();
+    let error: Report = Report::new(DetectDrop::new("TestIntoParts", &has_dropped));
+    let (error, handler) = error.into_parts();
+    assert_eq!("oh no!", error.to_string());
+    assert!(format!("{:?}", error).contains("TestIntoParts"));
+    drop(handler);
+    assert!(!has_dropped.get());
+    drop(error);
+    assert!(has_dropped.get());
+}
+
+#[test]
+fn test_into_display_error() {
+    maybe_install_handler().unwrap();
+
+    let error: Report = eyre::eyre!("disk full").wrap_err("failed to save");
+    let rendered = error.into_display_error();
+    let cloned = rendered.clone();
+    assert_eq!(rendered, cloned);
+
+    let chain: Vec<String> = eyre::Chain::new(&cloned).map(ToString::to_string).collect();
+    assert_eq!(chain, ["failed to save", "disk full"]);
+
+    let report = Report::new(rendered);
+    assert_eq!(report.to_string(), "failed to save");
+}
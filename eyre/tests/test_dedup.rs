@@ -0,0 +1,51 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, Report, WrapErr};
+use std::collections::HashSet;
+use std::io;
+
+fn io_error(msg: &str) -> Report {
+    Err::<(), _>(io::Error::new(io::ErrorKind::Other, msg.to_owned()))
+        .wrap_err("failed to sync")
+        .unwrap_err()
+}
+
+#[test]
+fn test_same_failure() {
+    maybe_install_handler().unwrap();
+
+    assert_eq!(
+        io_error("oh no!").dedup_key(),
+        io_error("oh no!").dedup_key()
+    );
+    assert_eq!(
+        io_error("oh no!").dedup_key().messages(),
+        ["failed to sync", "oh no!"],
+    );
+}
+
+#[test]
+fn test_different_messages() {
+    maybe_install_handler().unwrap();
+
+    assert_ne!(
+        io_error("oh no!").dedup_key(),
+        io_error("oh dear").dedup_key()
+    );
+}
+
+#[test]
+fn test_different_root_type() {
+    maybe_install_handler().unwrap();
+
+    let adhoc = eyre!("oh no!").wrap_err("failed to sync");
+    assert_eq!(adhoc.to_string(), io_error("oh no!").to_string());
+    assert_ne!(adhoc.dedup_key(), io_error("oh no!").dedup_key());
+
+    let keys: HashSet<_> = [adhoc, io_error("oh no!"), io_error("oh no!")]
+        .iter()
+        .map(Report::dedup_key)
+        .collect();
+    assert_eq!(keys.len(), 2);
+}
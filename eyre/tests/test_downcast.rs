@@ -146,3 +146,18 @@ fn test_unsuccessful_downcast() {
     assert!(error.downcast_mut::<&str>().is_none());
     assert!(error.downcast::<&str>().is_err());
 }
+
+#[test]
+fn test_msg_static() {
+    maybe_install_handler().unwrap();
+
+    static MESSAGE: &str = "oh no!";
+
+    let error = Report::msg_static(MESSAGE);
+    let message = *error.downcast_ref::<&str>().unwrap();
+    assert!(std::ptr::eq(message, MESSAGE));
+    assert_eq!(error.to_string(), "oh no!");
+
+    let error = Report::msg_static("oh no!");
+    assert_eq!("oh no!", error.downcast::<&str>().unwrap());
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, ExitCode, Report};
+use std::env;
+use std::process::Command;
+
+#[test]
+fn test_exit_code() {
+    maybe_install_handler().unwrap();
+
+    let report = eyre!("oh no!");
+    assert_eq!(report.exit_code(), None);
+
+    let report = report.with_exit_code(2).wrap_err("wrapped");
+    assert_eq!(report.exit_code(), Some(2));
+    assert_eq!(report.get::<ExitCode>(), Some(&ExitCode(2)));
+
+    let report = report.with_exit_code(3);
+    assert_eq!(report.exit_code(), Some(3));
+}
+
+#[test]
+fn test_run() {
+    maybe_install_handler().unwrap();
+
+    // Re-run this test in a child process, which exits via `eyre::run`
+    if let Some(code) = env::var_os("EYRE_TEST_RUN_EXIT_CODE") {
+        let code = code.to_str().unwrap().parse().unwrap();
+        eyre::run(move || match code {
+            0 => Ok(()),
+            1 => Err(eyre!("oh no!")),
+            code => Err(Report::msg("oh no!").with_exit_code(code)),
+        });
+    }
+
+    for code in [0, 1, 42] {
+        let output = Command::new(env::current_exe().unwrap())
+            .args(["test_run", "--exact", "--nocapture"])
+            .env("EYRE_TEST_RUN_EXIT_CODE", code.to_string())
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(code));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert_eq!(stderr.contains("Error: oh no!"), code != 0, "{}", stderr);
+    }
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, Report, WrapErr};
+use std::io;
+
+#[derive(Debug, PartialEq)]
+struct StatusCode(u16);
+
+#[derive(Debug, PartialEq)]
+struct RetryAfter(u64);
+
+#[test]
+fn test_insert_get() {
+    maybe_install_handler().unwrap();
+
+    let mut report = eyre!("user not found");
+    assert_eq!(report.get::<StatusCode>(), None);
+
+    assert_eq!(report.insert(StatusCode(404)), None);
+    assert_eq!(report.insert(StatusCode(410)), Some(StatusCode(404)));
+    report.insert(RetryAfter(30));
+
+    assert_eq!(report.get::<StatusCode>(), Some(&StatusCode(410)));
+    report.get_mut::<RetryAfter>().unwrap().0 = 60;
+    assert_eq!(report.remove::<RetryAfter>(), Some(RetryAfter(60)));
+    assert_eq!(report.get::<RetryAfter>(), None);
+}
+
+#[test]
+fn test_survives_wrap_err() {
+    maybe_install_handler().unwrap();
+
+    let mut report = Report::new(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+    report.insert(StatusCode(404));
+
+    let report = Err::<(), _>(report)
+        .wrap_err("failed to read config")
+        .unwrap_err()
+        .wrap_err("failed to start");
+
+    assert_eq!(report.get::<StatusCode>(), Some(&StatusCode(404)));
+    assert!(report.downcast_ref::<io::Error>().is_some());
+}
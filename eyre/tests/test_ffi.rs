@@ -0,0 +1,129 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::ffi::{EyreReportRef, EyreReportVTable, EyreWriteFn, EYRE_REPORT_VTABLE_VERSION};
+use eyre::{eyre, Report};
+use std::ffi::c_void;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+fn chain(report: &Report) -> Vec<String> {
+    report.chain().map(|error| error.to_string()).collect()
+}
+
+#[test]
+fn test_handle_chain() {
+    maybe_install_handler().unwrap();
+
+    let report = eyre!("missing symbol").wrap_err("failed to load plugin");
+    let debug = format!("{:?}", report);
+    let handle = EyreReportRef::new(report);
+
+    assert_eq!(handle.chain_len(), 2);
+    assert_eq!(handle.chain(), ["failed to load plugin", "missing symbol"]);
+    assert_eq!(handle.message(1).as_deref(), Some("missing symbol"));
+    assert_eq!(handle.message(2), None);
+    assert_eq!(handle.debug(), debug);
+    assert_eq!(format!("{:?}", handle), debug);
+}
+
+#[derive(Debug)]
+struct PanickingSource;
+
+impl std::fmt::Display for PanickingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("failed to load plugin")
+    }
+}
+
+impl std::error::Error for PanickingSource {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        panic!("oh no!")
+    }
+}
+
+#[test]
+fn test_handle_chain_panicking_source() {
+    maybe_install_handler().unwrap();
+
+    let handle = EyreReportRef::new(Report::new(PanickingSource));
+    assert_eq!(handle.chain_len(), 1);
+    assert_eq!(handle.message(1), None);
+}
+
+#[test]
+fn test_roundtrip() {
+    maybe_install_handler().unwrap();
+
+    let report = Report::new(io::Error::new(io::ErrorKind::NotFound, "missing"))
+        .wrap_err("failed to load plugin");
+    let report = unsafe { EyreReportRef::from(report).into_report() };
+
+    assert_eq!(chain(&report), ["failed to load plugin", "missing"]);
+    assert!(report.downcast_ref::<io::Error>().is_some());
+}
+
+static FOREIGN_DROPPED: AtomicBool = AtomicBool::new(false);
+
+unsafe extern "C" fn foreign_chain_len(data: *const c_void) -> usize {
+    (&*data.cast::<Vec<&str>>()).len()
+}
+
+unsafe extern "C" fn foreign_write_message(
+    data: *const c_void,
+    index: usize,
+    write: EyreWriteFn,
+    context: *mut c_void,
+) -> bool {
+    match (&*data.cast::<Vec<&str>>()).get(index) {
+        // Write in two chunks to check that they are joined
+        Some(message) => {
+            let (head, tail) = message.split_at(message.len() / 2);
+            write(context, head.as_ptr(), head.len()) && write(context, tail.as_ptr(), tail.len())
+        }
+        None => false,
+    }
+}
+
+unsafe extern "C" fn foreign_write_debug(
+    data: *const c_void,
+    write: EyreWriteFn,
+    context: *mut c_void,
+) -> bool {
+    let debug = format!("foreign report: {:?}", &*data.cast::<Vec<&str>>());
+    write(context, debug.as_ptr(), debug.len())
+}
+
+unsafe extern "C" fn foreign_drop(data: *mut c_void) {
+    drop(Box::from_raw(data.cast::<Vec<&str>>()));
+    FOREIGN_DROPPED.store(true, Ordering::SeqCst);
+}
+
+static FOREIGN_VTABLE: EyreReportVTable = EyreReportVTable {
+    version: EYRE_REPORT_VTABLE_VERSION,
+    chain_len: foreign_chain_len,
+    write_message: foreign_write_message,
+    write_debug: foreign_write_debug,
+    drop: foreign_drop,
+};
+
+#[test]
+fn test_foreign_handle() {
+    maybe_install_handler().unwrap();
+
+    let data = Box::new(vec!["failed to load plugin", "missing symbol"]);
+    let handle =
+        unsafe { EyreReportRef::from_raw_parts(Box::into_raw(data).cast(), &FOREIGN_VTABLE) };
+    assert_eq!(handle.chain(), ["failed to load plugin", "missing symbol"]);
+
+    let report = unsafe { handle.into_report() }.wrap_err("failed to start");
+    assert_eq!(
+        chain(&report),
+        ["failed to start", "failed to load plugin", "missing symbol"]
+    );
+    assert_eq!(format!("{:?}", report.root_cause()), "missing symbol");
+
+    assert!(!FOREIGN_DROPPED.load(Ordering::SeqCst));
+    drop(report);
+    assert!(FOREIGN_DROPPED.load(Ordering::SeqCst));
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::eyre;
+
+#[test]
+fn test_wrap_err_with() {
+    maybe_install_handler().unwrap();
+
+    let mut called = false;
+    let report = eyre!("disk full").wrap_err_with(|| {
+        called = true;
+        "failed to save"
+    });
+
+    assert!(called);
+    assert_eq!(report.to_string(), "failed to save");
+    assert_eq!(report.chain().count(), 2);
+}
+
+#[test]
+fn test_fields_table() {
+    maybe_install_handler().unwrap();
+
+    let report = eyre!("failed to load user")
+        .wrap_err_field("user_id", 42)
+        .wrap_err_field("path", "/etc/users")
+        .wrap_err_field("user_id", 43);
+
+    assert_eq!(report.to_string(), "failed to load user");
+
+    let output = format!("{:?}", report);
+    assert!(output
+        .starts_with("failed to load user\n\nFields:\n    user_id = 43\n    path    = /etc/users"));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, FingerprintComponent, Report, WrapErr};
+use std::io;
+
+fn load(id: u32) -> Report {
+    Err::<(), _>(eyre!("user {} not found", id))
+        .wrap_err(format!("failed to load profile {}", id))
+        .unwrap_err()
+}
+
+fn read(kind: io::ErrorKind) -> Report {
+    Err::<(), _>(io::Error::new(kind, "read failed"))
+        .wrap_err("failed to read config")
+        .unwrap_err()
+}
+
+#[test]
+fn test_interpolated_values_ignored() {
+    maybe_install_handler().unwrap();
+
+    assert_eq!(load(1).fingerprint(), load(2).fingerprint());
+    assert_ne!(load(1).dedup_key(), load(2).dedup_key());
+}
+
+#[test]
+fn test_call_sites_distinguished() {
+    maybe_install_handler().unwrap();
+
+    let other = Err::<(), _>(eyre!("user {} not found", 1))
+        .wrap_err(format!("failed to load profile {}", 1))
+        .unwrap_err();
+
+    assert_eq!(load(1).to_string(), other.to_string());
+    assert_ne!(load(1).fingerprint(), other.fingerprint());
+}
+
+#[test]
+fn test_components() {
+    maybe_install_handler().unwrap();
+
+    let report = read(io::ErrorKind::NotFound);
+    let components = report.fingerprint_components();
+
+    assert_eq!(components.len(), 3);
+    assert!(matches!(components[0], FingerprintComponent::Location(_)));
+    assert_eq!(
+        components[1],
+        FingerprintComponent::Type(std::any::TypeId::of::<io::Error>())
+    );
+    assert_eq!(
+        components[2],
+        FingerprintComponent::Message("read failed".into())
+    );
+
+    let line = line!();
+    let report = Report::msg("retry 3 of 5 failed");
+    match report.fingerprint_components()[1] {
+        FingerprintComponent::Location(location) => {
+            assert_eq!(location.file(), file!());
+            assert_eq!(location.line(), line + 1);
+        }
+        ref other => panic!("unexpected component {:?}", other),
+    }
+}
@@ -0,0 +1,27 @@
+use eyre::{eyre, DefaultHook};
+
+#[test]
+fn test_frame_filter() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    DefaultHook::new()
+        .add_default_filters()
+        .add_frame_filter(Box::new(|frames| {
+            frames.retain(
+                |frame| !matches!(&frame.name, Some(name) if name.contains("test_frame_filter")),
+            )
+        }))
+        .install()
+        .unwrap();
+
+    let report = eyre!("oh no!");
+    let output = format!("{:?}", report);
+    assert!(output.starts_with("oh no!"));
+
+    #[cfg(all(generic_member_access, not(miri)))]
+    {
+        assert!(output.contains("Stack backtrace:"));
+        assert!(output.contains("frames hidden"));
+        assert!(!output.contains("test_frame_filter::"));
+    }
+}
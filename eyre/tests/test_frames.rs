@@ -0,0 +1,137 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, ChainFrame, ChainFrameMut, Report, WrapErr};
+use std::io;
+
+fn describe(report: &Report) -> Vec<String> {
+    report
+        .frames()
+        .map(|frame| match frame {
+            ChainFrame::ContextMessage(msg) => format!("context: {}", msg),
+            ChainFrame::Error(error) => format!("error: {}", error),
+        })
+        .collect()
+}
+
+#[test]
+fn test_std_error_context() {
+    maybe_install_handler().unwrap();
+
+    let error = io::Error::new(io::ErrorKind::NotFound, "oh no!");
+    let report = Err::<(), _>(error)
+        .wrap_err("f failed")
+        .wrap_err("g failed")
+        .unwrap_err();
+
+    assert_eq!(
+        describe(&report),
+        ["context: g failed", "context: f failed", "error: oh no!"],
+    );
+    assert_eq!(report.frames().count(), report.chain().count());
+}
+
+#[test]
+fn test_report_context() {
+    maybe_install_handler().unwrap();
+
+    let report = eyre!("oh no!").wrap_err("f failed");
+    assert_eq!(describe(&report), ["context: f failed", "error: oh no!"]);
+}
+
+#[test]
+fn test_no_context() {
+    maybe_install_handler().unwrap();
+
+    let report = Report::new(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+    assert_eq!(describe(&report), ["error: oh no!"]);
+}
+
+#[derive(Debug)]
+struct Credentials {
+    user: String,
+    password: String,
+}
+
+impl std::fmt::Display for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "login failed for {}:{}", self.user, self.password)
+    }
+}
+
+impl std::error::Error for Credentials {}
+
+#[test]
+fn test_chain_mut() {
+    maybe_install_handler().unwrap();
+
+    let mut report = Report::new(Credentials {
+        user: "admin".into(),
+        password: "hunter2".into(),
+    })
+    .wrap_err(String::from("token=abc"))
+    .wrap_err("request failed");
+
+    let mut visited = Vec::new();
+    for frame in report.chain_mut() {
+        visited.push(frame.to_string());
+        match frame {
+            ChainFrameMut::ContextMessage(mut msg) => {
+                if let Some(msg) = msg.downcast_mut::<String>() {
+                    *msg = "token=<redacted>".into();
+                }
+            }
+            ChainFrameMut::Error(error) => {
+                error.downcast_mut::<Credentials>().unwrap().password = "<redacted>".into();
+            }
+        }
+    }
+
+    assert_eq!(
+        visited,
+        [
+            "request failed",
+            "token=abc",
+            "login failed for admin:hunter2"
+        ]
+    );
+
+    let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(
+        chain,
+        [
+            "request failed",
+            "token=<redacted>",
+            "login failed for admin:<redacted>"
+        ]
+    );
+}
+
+#[test]
+fn test_chain_mut_wrapped_error() {
+    maybe_install_handler().unwrap();
+
+    let mut report = Err::<(), _>(Credentials {
+        user: "admin".into(),
+        password: "hunter2".into(),
+    })
+    .wrap_err(String::from("token=abc"))
+    .unwrap_err();
+
+    for frame in report.chain_mut() {
+        match frame {
+            ChainFrameMut::ContextMessage(mut msg) => {
+                *msg.downcast_mut::<String>().unwrap() = "token=<redacted>".into();
+            }
+            ChainFrameMut::Error(error) => {
+                error.downcast_mut::<Credentials>().unwrap().password = "<redacted>".into();
+            }
+        }
+    }
+
+    let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(
+        chain,
+        ["token=<redacted>", "login failed for admin:<redacted>"]
+    );
+}
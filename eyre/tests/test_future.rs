@@ -0,0 +1,77 @@
+#![cfg(feature = "future")]
+
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::future::FutureExt;
+use eyre::{eyre, Report};
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn lines(report: &Report) -> Vec<u32> {
+    assert!(report
+        .locations()
+        .all(|location| location.file() == file!()));
+    report.locations().map(|location| location.line()).collect()
+}
+
+#[test]
+fn test_wrap_err() {
+    maybe_install_handler().unwrap();
+
+    let line = line!();
+    let future = async { Err::<(), _>(io::Error::new(io::ErrorKind::Other, "oh no!")) }
+        .wrap_err("failed to read");
+    let report = block_on(future).unwrap_err();
+
+    let chain: Vec<_> = report.chain().map(|e| e.to_string()).collect();
+    assert_eq!(chain, ["failed to read", "oh no!"]);
+    assert!(report.downcast_ref::<io::Error>().is_some());
+
+    if cfg!(feature = "track-caller") {
+        assert_eq!(lines(&report), [line + 2, line + 2]);
+    }
+
+    let output = block_on(async { Ok::<_, io::Error>(5) }.wrap_err("unused"));
+    assert_eq!(output.unwrap(), 5);
+}
+
+#[test]
+fn test_wrap_err_with() {
+    maybe_install_handler().unwrap();
+
+    let line = line!();
+    let future =
+        async { Err::<(), _>(eyre!("oh no!")) }.wrap_err_with(|| format!("attempt {} failed", 3));
+    let report = block_on(future).unwrap_err();
+
+    let chain: Vec<_> = report.chain().map(|e| e.to_string()).collect();
+    assert_eq!(chain, ["attempt 3 failed", "oh no!"]);
+
+    if cfg!(feature = "track-caller") {
+        assert_eq!(lines(&report), [line + 2, line + 2]);
+    }
+
+    let future = async { Ok::<(), Report>(()) }.wrap_err_with(|| -> &str { unreachable!() });
+    block_on(future).unwrap();
+}
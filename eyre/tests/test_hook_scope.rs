@@ -0,0 +1,74 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, EyreHandler, Report};
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+struct ScopedHandler;
+
+impl EyreHandler for ScopedHandler {
+    fn debug(&self, error: &(dyn StdError + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "scoped: {}", error)
+    }
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Returns `Pending` once before completing, to check that the scope is
+/// re-entered on every poll.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn test_hook_scope() {
+    maybe_install_handler().unwrap();
+
+    let (inside, after_yield) =
+        block_on(eyre::with_hook_scope(|_| Box::new(ScopedHandler), async {
+            let inside: Report = eyre!("inside");
+            YieldOnce(false).await;
+            let after_yield: Report = eyre!("after yield");
+            (inside, after_yield)
+        }));
+
+    assert!(inside.handler().is::<ScopedHandler>());
+    assert!(after_yield.handler().is::<ScopedHandler>());
+    assert_eq!(format!("{:?}", inside), "scoped: inside");
+
+    let outside: Report = eyre!("outside");
+    assert!(!outside.handler().is::<ScopedHandler>());
+}
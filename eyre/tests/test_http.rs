@@ -0,0 +1,64 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::http::{ReportResponse, StatusCode};
+use eyre::{eyre, WrapErr};
+use std::io;
+
+fn read_config() -> Result<String, ReportResponse> {
+    let error = io::Error::new(io::ErrorKind::NotFound, "no \"config.toml\"");
+    Err(error)?
+}
+
+#[test]
+fn test_status_and_body() {
+    maybe_install_handler().unwrap();
+
+    let response = read_config().unwrap_err();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        response.body(),
+        r#"{"error":"no \"config.toml\"","chain":["no \"config.toml\""]}"#
+    );
+
+    let report = Err::<(), _>(eyre!("rate limited").with_status(StatusCode::TOO_MANY_REQUESTS))
+        .wrap_err("failed to sync")
+        .unwrap_err();
+    let response = ReportResponse::from(report);
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        response.body(),
+        r#"{"error":"failed to sync","chain":["failed to sync","rate limited"]}"#
+    );
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn test_axum_response() {
+    use axum_core::response::IntoResponse;
+
+    maybe_install_handler().unwrap();
+
+    let response =
+        ReportResponse::from(eyre!("oh no!").with_status(StatusCode::BAD_REQUEST)).into_response();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers()[http::header::CONTENT_TYPE],
+        "application/json"
+    );
+}
+
+#[cfg(feature = "warp")]
+#[test]
+fn test_warp_reply() {
+    use warp::Reply;
+
+    maybe_install_handler().unwrap();
+
+    let response =
+        ReportResponse::from(eyre!("oh no!").with_status(StatusCode::BAD_REQUEST)).into_response();
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(response.headers()["content-type"], "application/json");
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, join_all, Aggregate, IteratorExt, Report};
+
+#[test]
+fn test_all_ok() {
+    maybe_install_handler().unwrap();
+
+    let results: Vec<Result<u32, Report>> = vec![Ok(1), Ok(2)];
+    let collected: Vec<u32> = results.into_iter().try_collect_all().unwrap();
+    assert_eq!(collected, [1, 2]);
+}
+
+#[test]
+fn test_collects_every_failure() {
+    maybe_install_handler().unwrap();
+
+    let results = vec![
+        Ok(1),
+        Err(eyre!("first failure")),
+        Ok(3),
+        Err(eyre!("second failure").wrap_err("while processing item")),
+    ];
+    let report = results
+        .into_iter()
+        .try_collect_all::<Vec<u32>>()
+        .unwrap_err();
+
+    assert_eq!(
+        report.to_string(),
+        "2 errors occurred\n[1] first failure\n[3] while processing item: second failure",
+    );
+
+    let aggregate = report.downcast::<Aggregate>().unwrap();
+    assert_eq!(aggregate.len(), 2);
+
+    let failures = aggregate.into_failures();
+    assert_eq!(failures[0].0, 1);
+    assert_eq!(failures[0].1.to_string(), "first failure");
+    assert_eq!(failures[1].0, 3);
+    assert_eq!(failures[1].1.chain().count(), 2);
+}
+
+#[test]
+fn test_join_all() {
+    maybe_install_handler().unwrap();
+
+    let ok: Vec<Result<u32, std::num::ParseIntError>> = vec!["1".parse(), "2".parse()];
+    assert_eq!(join_all(ok).unwrap(), [1, 2]);
+
+    let results = vec![Ok(1), Err(eyre!("disk full")), Err(eyre!("network down"))];
+    let report = join_all(results).unwrap_err();
+    let aggregate = report.downcast_ref::<Aggregate>().unwrap();
+    let indices: Vec<_> = aggregate.iter().map(|(index, _)| index).collect();
+    assert_eq!(indices, [1, 2]);
+}
+
+#[test]
+fn test_report_aggregate() {
+    maybe_install_handler().unwrap();
+
+    let report = Report::aggregate(vec![
+        eyre!("disk full").wrap_err("failed to save"),
+        eyre!("network down"),
+    ]);
+
+    assert!(format!("{:?}", report)
+        .starts_with("2 errors occurred\n[0] failed to save: disk full\n[1] network down"));
+
+    let failures = report.downcast::<Aggregate>().unwrap().into_failures();
+    assert_eq!(failures.len(), 2);
+    assert_eq!(failures[1].1.to_string(), "network down");
+}
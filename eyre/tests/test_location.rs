@@ -160,3 +160,21 @@ fn test_option_compat_with_context() {
     // should panic if the location isn't in our crate
     println!("{:?}", err);
 }
+
+#[test]
+fn test_validator_check_field() {
+    let _ = eyre::set_hook(Box::new(|_e| {
+        let expected_location = file!();
+        Box::new(LocationHandler::new(expected_location))
+    }));
+
+    let mut validator = eyre::Validator::new();
+    validator.check_field("path", read_path("totally_fake_path"));
+    let err = validator.finish().unwrap_err();
+    let errors = err.downcast_ref::<eyre::ValidationErrors>().unwrap();
+
+    // should panic if the location isn't in our crate
+    for (_, error) in errors.iter() {
+        println!("{:?}", error);
+    }
+}
@@ -2,7 +2,7 @@
 mod common;
 
 use self::common::*;
-use eyre::{ensure, eyre, Result};
+use eyre::{bail_if, ensure, ensure_eq, ensure_ne, eyre, Result};
 use std::cell::Cell;
 use std::future::Future;
 use std::pin::Pin;
@@ -48,6 +48,150 @@ fn test_ensure() {
         f().unwrap_err().to_string(),
         "Condition failed: `v + v == 1`",
     );
+
+    let f = || {
+        ensure!((v + v) == 1);
+        Ok(())
+    };
+    assert_eq!(
+        f().unwrap_err().to_string(),
+        "Condition failed: `(v + v) == 1` (left: 2, right: 1)",
+    );
+
+    let f = || {
+        ensure!(v != 1,);
+        Ok(())
+    };
+    assert_eq!(
+        f().unwrap_err().to_string(),
+        "Condition failed: `v != 1` (left: 1, right: 1)",
+    );
+}
+
+#[test]
+fn test_ensure_comparison() {
+    maybe_install_handler().unwrap();
+
+    let name = "eyre";
+    let f = || {
+        ensure!(name.len() != 4);
+        Ok(())
+    };
+    assert_eq!(
+        f().unwrap_err().to_string(),
+        "Condition failed: `name.len() != 4` (left: 4, right: 4)",
+    );
+
+    let f = || {
+        ensure!(Some(name) == None);
+        Ok(())
+    };
+    assert_eq!(
+        f().unwrap_err().to_string(),
+        "Condition failed: `Some(name) == None` (left: Some(\"eyre\"), right: None)",
+    );
+
+    // Operands are only evaluated once
+    let calls = Cell::new(0);
+    let next = || {
+        calls.set(calls.get() + 1);
+        calls.get()
+    };
+    let f = || {
+        ensure!(next() == 2);
+        Ok(())
+    };
+    assert_eq!(
+        f().unwrap_err().to_string(),
+        "Condition failed: `next() == 2` (left: 1, right: 2)",
+    );
+    assert_eq!(calls.get(), 1);
+
+    // Lower precedence operators are reported without operands
+    let f = |name: &str| {
+        ensure!(name.is_empty() || name.len() == 3);
+        Ok(())
+    };
+    assert_eq!(
+        f(name).unwrap_err().to_string(),
+        "Condition failed: `name.is_empty() || name.len() == 3`",
+    );
+
+    // Long conditions don't run into the macro recursion limit
+    let values = [1, 2, 3];
+    let f = || {
+        ensure!(
+            values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                + values[0]
+                + values[1]
+                + values[2]
+                == 0
+        );
+        Ok(())
+    };
+    assert!(f().is_err());
+
+    // Operands without a Debug impl are not rendered
+    struct Opaque;
+
+    impl PartialEq for Opaque {
+        fn eq(&self, _: &Self) -> bool {
+            false
+        }
+    }
+
+    let f = || {
+        ensure!(Opaque == Opaque);
+        Ok(())
+    };
+    assert_eq!(
+        f().unwrap_err().to_string(),
+        "Condition failed: `Opaque == Opaque`",
+    );
 }
 
 #[test]
@@ -100,3 +244,80 @@ fn test_brace_escape() {
     let err = eyre!("unterminated ${{..}} expression");
     assert_eq!("unterminated ${..} expression", err.to_string());
 }
+
+#[test]
+fn test_source() {
+    maybe_install_handler().unwrap();
+
+    let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "oh no!");
+    let err = eyre!(source = io_error, "failed to open {}", "config.toml");
+    assert_eq!("failed to open config.toml", err.to_string());
+    assert_eq!(2, err.chain().count());
+    assert_eq!(
+        std::io::ErrorKind::NotFound,
+        err.root_cause()
+            .downcast_ref::<std::io::Error>()
+            .unwrap()
+            .kind()
+    );
+
+    let err = eyre!(source = eyre!("inner"), "outer");
+    assert_eq!("outer: inner", format!("{:#}", err));
+}
+
+#[test]
+#[cfg(not(eyre_no_fmt_args_capture))]
+fn test_source_capture_format_args() {
+    maybe_install_handler().unwrap();
+
+    let path = "config.toml";
+    let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "oh no!");
+    let err = eyre!(source = io_error, "failed to open {path}");
+    assert_eq!("failed to open config.toml: oh no!", format!("{:#}", err));
+}
+
+#[test]
+fn test_ensure_eq_ne() {
+    maybe_install_handler().unwrap();
+
+    let f = |a: u8, b: u8| -> Result<()> {
+        ensure_eq!(a, b);
+        Ok(())
+    };
+    assert!(f(1, 1).is_ok());
+    assert_eq!(
+        f(1, 2).unwrap_err().to_string(),
+        "Condition failed: `a == b` (left: 1, right: 2)",
+    );
+
+    let f = |a: &str, b: &str| -> Result<()> {
+        ensure_ne!(a, b, "{} must differ from {}", "source", "target");
+        Ok(())
+    };
+    assert!(f("x", "y").is_ok());
+    assert_eq!(
+        f("x", "x").unwrap_err().to_string(),
+        r#"source must differ from target (left: "x", right: "x")"#,
+    );
+
+    let f = |a: Vec<u8>| -> Result<()> {
+        ensure_ne!(a, Vec::<u8>::new());
+        Ok(())
+    };
+    assert_eq!(
+        f(vec![]).unwrap_err().to_string(),
+        "Condition failed: `a != Vec::<u8>::new()` (left: [], right: [])",
+    );
+}
+
+#[test]
+fn test_bail_if() {
+    maybe_install_handler().unwrap();
+
+    let f = |len: usize| -> Result<()> {
+        bail_if!(len > 3, "too long: {}", len);
+        Ok(())
+    };
+    assert!(f(3).is_ok());
+    assert_eq!(f(4).unwrap_err().to_string(), "too long: 4");
+}
@@ -0,0 +1,69 @@
+use eyre::{eyre, EyreHandler, MainResult, Report};
+use std::env;
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+use std::process::{Command, ExitCode, Termination};
+
+struct ExitHandler;
+
+impl EyreHandler for ExitHandler {
+    fn debug(&self, error: &(dyn StdError + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", error)
+    }
+
+    fn exit_report(
+        &self,
+        error: &(dyn StdError + 'static),
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        self.debug(error, f)
+    }
+
+    fn exit_code(&self, error: &(dyn StdError + 'static)) -> Option<i32> {
+        match error.downcast_ref::<io::Error>()?.kind() {
+            io::ErrorKind::NotFound => Some(66),
+            _ => None,
+        }
+    }
+}
+
+fn exit_code(result: impl Into<MainResult>) -> String {
+    format!("{:?}", result.into().report())
+}
+
+fn expected(code: u8) -> String {
+    format!("{:?}", ExitCode::from(code))
+}
+
+#[test]
+fn test_main_result() {
+    eyre::set_hook(Box::new(|_| Box::new(ExitHandler))).unwrap();
+
+    // Re-run this test in a child process, which only reports a failed main
+    if env::var_os("EYRE_TEST_MAIN_RESULT").is_some() {
+        let _ = MainResult::from(eyre!("oh no!")).report();
+        return;
+    }
+
+    assert_eq!(exit_code(Ok::<(), Report>(())), expected(0));
+    assert_eq!(exit_code(eyre!("oh no!")), expected(1));
+    assert_eq!(exit_code(eyre!("oh no!").with_exit_code(42)), expected(42));
+
+    let not_found = || io::Error::new(io::ErrorKind::NotFound, "oh no!");
+    assert_eq!(exit_code(Err::<(), _>(not_found())), expected(66));
+    assert_eq!(
+        exit_code(Report::new(not_found()).with_exit_code(3)),
+        expected(3)
+    );
+
+    let output = Command::new(env::current_exe().unwrap())
+        .args(["test_main_result", "--exact", "--nocapture"])
+        .env("EYRE_TEST_MAIN_RESULT", "1")
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("error: oh no!\n"), "{}", stderr);
+    assert!(!stderr.contains("Error: "), "{}", stderr);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "miette-compat")]
+
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::compat::{self, DiagnosticError, IntoEyre};
+use eyre::WrapErr;
+use miette::{Diagnostic, LabeledSpan, SourceSpan};
+
+#[derive(Debug)]
+struct ParseError {
+    src: String,
+    span: SourceSpan,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("unexpected token")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new("try quoting the value"))
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.src)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(std::iter::once(LabeledSpan::new_with_span(
+            Some("here".into()),
+            self.span,
+        ))))
+    }
+}
+
+fn parse_error() -> ParseError {
+    ParseError {
+        src: "key = value value".into(),
+        span: (12, 5).into(),
+    }
+}
+
+#[test]
+fn test_report_keeps_diagnostic() {
+    maybe_install_handler().unwrap();
+
+    let result: miette::Result<()> = Err(miette::Report::new(parse_error()));
+    let report = result
+        .into_eyre()
+        .wrap_err("failed to load config")
+        .unwrap_err();
+
+    assert_eq!(report.to_string(), "failed to load config");
+
+    let diagnostic = compat::diagnostic(&report).unwrap();
+    assert_eq!(diagnostic.to_string(), "unexpected token");
+    assert_eq!(
+        diagnostic.help().unwrap().to_string(),
+        "try quoting the value"
+    );
+
+    let labels: Vec<_> = diagnostic.labels().unwrap().collect();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].label(), Some("here"));
+    assert_eq!(labels[0].offset(), 12);
+
+    let snippet = diagnostic
+        .source_code()
+        .unwrap()
+        .read_span(labels[0].inner(), 0, 0)
+        .unwrap();
+    assert_eq!(snippet.data(), b"value");
+}
+
+#[test]
+fn test_from_diagnostic() {
+    maybe_install_handler().unwrap();
+
+    let report = compat::from_diagnostic(parse_error());
+    let error = report.downcast::<DiagnosticError>().unwrap();
+    let diagnostic = error.into_diagnostic();
+    assert_eq!(diagnostic.labels().unwrap().count(), 1);
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, WrapErr};
+use std::sync::Mutex;
+
+static CREATED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static WRAPPED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+#[test]
+fn test_observers() {
+    maybe_install_handler().unwrap();
+
+    eyre::set_report_observer(Box::new(|report| {
+        // Reports created by the observer aren't observed again
+        let _ = eyre!("created by the observer");
+        CREATED.lock().unwrap().push(report.to_string());
+    }))
+    .unwrap();
+    eyre::set_wrap_observer(Box::new(|report| {
+        WRAPPED.lock().unwrap().push(format!("{:#}", report));
+    }))
+    .unwrap();
+
+    let report = Err::<(), _>(std::io::Error::new(std::io::ErrorKind::Other, "oh no!"))
+        .wrap_err("failed to read")
+        .wrap_err("failed to load config")
+        .unwrap_err();
+    let _ = eyre!("{} errors", 2);
+
+    // Wrapping a plain error creates a single report with the message
+    assert_eq!(*CREATED.lock().unwrap(), ["failed to read", "2 errors"]);
+    assert_eq!(
+        *WRAPPED.lock().unwrap(),
+        ["failed to load config: failed to read: oh no!"]
+    );
+    drop(report);
+
+    assert!(eyre::set_report_observer(Box::new(|_| {})).is_err());
+}
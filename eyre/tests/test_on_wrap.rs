@@ -0,0 +1,39 @@
+use eyre::{eyre, EyreHandler, WrapErr};
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::panic::Location;
+
+#[derive(Default)]
+struct WrapRecorder {
+    layers: Vec<(String, Option<&'static Location<'static>>)>,
+}
+
+impl EyreHandler for WrapRecorder {
+    fn debug(&self, error: &(dyn StdError + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", error)
+    }
+
+    fn on_wrap(&mut self, msg: &dyn Display, location: Option<&'static Location<'static>>) {
+        self.layers.push((msg.to_string(), location));
+    }
+}
+
+#[test]
+fn test_on_wrap() {
+    eyre::set_hook(Box::new(|_| Box::<WrapRecorder>::default())).unwrap();
+
+    let report = Err::<(), _>(eyre!("connection refused"))
+        .wrap_err("failed to connect")
+        .wrap_err_with(|| format!("attempt {} failed", 3))
+        .unwrap_err();
+
+    let handler = report.handler().downcast_ref::<WrapRecorder>().unwrap();
+    let messages: Vec<_> = handler.layers.iter().map(|(msg, _)| msg.as_str()).collect();
+    assert_eq!(messages, ["failed to connect", "attempt 3 failed"]);
+
+    for (_, location) in &handler.layers {
+        if let Some(location) = location {
+            assert_eq!(location.file(), file!());
+        }
+    }
+}
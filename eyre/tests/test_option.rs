@@ -1,7 +1,7 @@
 mod common;
 
 use self::common::maybe_install_handler;
-use eyre::OptionExt;
+use eyre::{some_or_report, OptionExt, Result};
 
 #[test]
 fn test_option_ok_or_eyre() {
@@ -13,3 +13,46 @@ fn test_option_ok_or_eyre() {
 
     assert_eq!(result.unwrap_err().to_string(), "static str error");
 }
+
+#[test]
+fn test_option_ok_or_eyre_with() {
+    maybe_install_handler().unwrap();
+
+    let id = 7;
+    let option: Option<()> = None;
+
+    let result = option.ok_or_eyre_with(|| format!("no user with id {}", id));
+
+    assert_eq!(result.unwrap_err().to_string(), "no user with id 7");
+    assert_eq!(
+        Some(1)
+            .ok_or_eyre_with(|| -> String { unreachable!() })
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn test_some_or_report() {
+    maybe_install_handler().unwrap();
+
+    fn lookup(values: &[u32], index: usize) -> Result<u32> {
+        let value = some_or_report!(values.get(index), "no value at index {}", index);
+        Ok(*value)
+    }
+
+    assert_eq!(lookup(&[1, 2], 1).unwrap(), 2);
+    assert_eq!(
+        lookup(&[1, 2], 5).unwrap_err().to_string(),
+        "no value at index 5"
+    );
+
+    fn first(values: &[u32]) -> Result<u32> {
+        Ok(*some_or_report!(values.first()))
+    }
+
+    assert_eq!(
+        first(&[]).unwrap_err().to_string(),
+        "`values.first()` was None"
+    );
+}
@@ -2,6 +2,7 @@
 
 use pyo3::prelude::*;
 
+use eyre::compat::{IntoEyre, PythonTraceback};
 use eyre::{bail, Result, WrapErr};
 
 fn f() -> Result<()> {
@@ -22,7 +23,7 @@ fn test_pyo3_exception_contents() {
     use pyo3::types::IntoPyDict;
 
     let err = h().unwrap_err();
-    let expected_contents = format!("{:?}", err);
+    let expected_contents = err.to_string();
     let pyerr = PyErr::from(err);
 
     Python::with_gil(|py| {
@@ -31,3 +32,41 @@ fn test_pyo3_exception_contents() {
         assert_eq!(pyerr.value(py).to_string(), expected_contents);
     })
 }
+
+#[test]
+fn test_pyo3_exception_cause_chain() {
+    let pyerr = PyErr::from(h().unwrap_err());
+
+    Python::with_gil(|py| {
+        let mut causes = Vec::new();
+        let mut next = Some(pyerr);
+        while let Some(exception) = next {
+            assert!(exception.is_instance_of::<pyo3::exceptions::PyRuntimeError>(py));
+            causes.push(exception.value(py).to_string());
+            next = exception.cause(py);
+        }
+
+        assert_eq!(causes, ["g failed", "f failed", "oh no!"]);
+    })
+}
+
+#[test]
+fn test_pyo3_round_trip() {
+    Python::with_gil(|py| {
+        let report = py
+            .run("def fail():\n    1 / 0\nfail()", None, None)
+            .into_eyre()
+            .wrap_err("failed to run script")
+            .unwrap_err();
+
+        let traceback = report.get::<PythonTraceback>().unwrap();
+        assert!(traceback.as_str().contains("in fail"), "{}", traceback);
+        assert!(format!("{:?}", report).contains("in fail"));
+
+        let pyerr = PyErr::from(report);
+        assert_eq!(pyerr.value(py).to_string(), "failed to run script");
+
+        let cause = pyerr.cause(py).unwrap();
+        assert!(cause.is_instance_of::<pyo3::exceptions::PyZeroDivisionError>(py));
+    })
+}
@@ -0,0 +1,43 @@
+use eyre::{eyre, EyreHandler};
+use std::error::Error as StdError;
+use std::fmt;
+
+struct LabelHandler(&'static str);
+
+impl EyreHandler for LabelHandler {
+    fn debug(&self, error: &(dyn StdError + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.0, error)
+    }
+}
+
+type ErrorHook = Box<dyn Fn(&(dyn StdError + 'static)) -> Box<dyn EyreHandler> + Send + Sync>;
+
+fn label_hook(label: &'static str) -> ErrorHook {
+    Box::new(move |_| Box::new(LabelHandler(label)))
+}
+
+fn label_of_new_report() -> &'static str {
+    let report = eyre!("oh no!");
+    report.handler().downcast_ref::<LabelHandler>().unwrap().0
+}
+
+#[test]
+fn test_replace_and_take_hook() {
+    assert!(eyre::take_hook().is_none());
+    eyre::set_hook(label_hook("first")).unwrap();
+    assert!(eyre::set_hook(label_hook("rejected")).is_err());
+    let first_report = eyre!("oh no!");
+    assert_eq!(label_of_new_report(), "first");
+
+    let previous = eyre::replace_hook(label_hook("second")).unwrap();
+    assert_eq!(label_of_new_report(), "second");
+    assert_eq!(format!("{:?}", first_report), "first: oh no!");
+
+    let second = eyre::take_hook().unwrap();
+    assert!(eyre::take_hook().is_none());
+    let handler = second(&fmt::Error);
+    assert_eq!(handler.downcast_ref::<LabelHandler>().unwrap().0, "second");
+
+    eyre::set_hook(previous).unwrap();
+    assert_eq!(label_of_new_report(), "first");
+}
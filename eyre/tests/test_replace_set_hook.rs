@@ -0,0 +1,37 @@
+use eyre::{eyre, EyreHandler};
+use std::error::Error as StdError;
+use std::fmt;
+
+struct LabelHandler(&'static str);
+
+impl EyreHandler for LabelHandler {
+    fn debug(&self, error: &(dyn StdError + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.0, error)
+    }
+}
+
+fn label_of_new_report() -> &'static str {
+    let report = eyre!("oh no!");
+    report.handler().downcast_ref::<LabelHandler>().unwrap().0
+}
+
+#[test]
+fn test_replace_hook_installed_with_set_hook() {
+    eyre::set_hook(Box::new(|_| Box::new(LabelHandler("first")))).unwrap();
+    assert_eq!(label_of_new_report(), "first");
+
+    let previous = eyre::replace_hook(Box::new(|_| Box::new(LabelHandler("second")))).unwrap();
+    assert_eq!(label_of_new_report(), "second");
+    let handler = previous(&fmt::Error);
+    assert_eq!(handler.downcast_ref::<LabelHandler>().unwrap().0, "first");
+
+    assert!(eyre::set_hook(previous).is_err());
+    let second = eyre::take_hook().unwrap();
+    assert_eq!(
+        second(&fmt::Error)
+            .downcast_ref::<LabelHandler>()
+            .unwrap()
+            .0,
+        "second"
+    );
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, DeserializedReport, Report, WrapErr};
+
+fn failing_report() -> Report {
+    Err::<(), _>(eyre!("connection reset"))
+        .wrap_err("failed to fetch shard 3")
+        .unwrap_err()
+}
+
+#[test]
+fn test_serialize_chain() {
+    maybe_install_handler().unwrap();
+
+    let value = serde_json::to_value(failing_report()).unwrap();
+    assert_eq!(
+        value["chain"],
+        serde_json::json!(["failed to fetch shard 3", "connection reset"])
+    );
+    assert_eq!(value["sections"], serde_json::json!([]));
+}
+
+#[test]
+fn test_round_trip() {
+    maybe_install_handler().unwrap();
+
+    let json = serde_json::to_string(&failing_report()).unwrap();
+    let received: DeserializedReport = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        received.chain().collect::<Vec<_>>(),
+        ["failed to fetch shard 3", "connection reset"]
+    );
+    assert_eq!(serde_json::to_string(&received).unwrap(), json);
+
+    let report = Report::new(received).wrap_err("worker 7 failed");
+    let messages: Vec<_> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        [
+            "worker 7 failed",
+            "failed to fetch shard 3",
+            "connection reset"
+        ]
+    );
+}
+
+#[test]
+fn test_forwarding_keeps_backtrace_and_sections() {
+    maybe_install_handler().unwrap();
+
+    let json = r#"{
+        "chain": ["disk full"],
+        "backtrace": "   0: worker::main",
+        "sections": ["Note: retried 3 times"]
+    }"#;
+    let received: DeserializedReport = serde_json::from_str(json).unwrap();
+    let report = Report::new(received).wrap_err("worker failed");
+
+    let forwarded: DeserializedReport =
+        serde_json::from_value(serde_json::to_value(&report).unwrap()).unwrap();
+    assert_eq!(
+        forwarded.chain().collect::<Vec<_>>(),
+        ["worker failed", "disk full"]
+    );
+    assert_eq!(forwarded.backtrace(), Some("   0: worker::main"));
+    assert_eq!(forwarded.sections(), ["Note: retried 3 times"]);
+}
+
+#[test]
+fn test_snapshot_roundtrip() {
+    maybe_install_handler().unwrap();
+
+    let snapshot = failing_report().snapshot();
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let received: eyre::ReportSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(received, snapshot);
+}
@@ -0,0 +1,27 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, Severity};
+
+#[test]
+fn test_severity() {
+    maybe_install_handler().unwrap();
+
+    let mut report = eyre!("oh no!");
+    assert_eq!(report.severity(), Severity::Error);
+
+    report.set_severity(Severity::Warning);
+    assert_eq!(report.severity(), Severity::Warning);
+
+    let report = report.wrap_err("wrapped");
+    assert_eq!(report.severity(), Severity::Warning);
+    assert_eq!(report.get::<Severity>(), Some(&Severity::Warning));
+}
+
+#[test]
+fn test_severity_order() {
+    assert!(Severity::Info < Severity::Warning);
+    assert!(Severity::Warning < Severity::Error);
+    assert!(Severity::Error < Severity::Fatal);
+    assert_eq!(Severity::Fatal.to_string(), "fatal");
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, Report, SharedReport};
+use std::error::Error as StdError;
+use std::io;
+
+fn assert_send_sync_clone<T: Send + Sync + Clone + StdError + 'static>() {}
+
+#[test]
+fn test_shared_formatting() {
+    maybe_install_handler().unwrap();
+
+    let report = eyre!("connection refused").wrap_err("failed to fetch index");
+    let debug = format!("{:?}", report);
+    let alternate = format!("{:#}", report);
+
+    let shared = report.into_shared();
+    let clone = shared.clone();
+
+    assert!(SharedReport::ptr_eq(&shared, &clone));
+    assert_eq!(clone.to_string(), "failed to fetch index");
+    assert_eq!(format!("{:#}", clone), alternate);
+    assert_eq!(format!("{:?}", clone), debug);
+}
+
+#[test]
+fn test_shared_chain() {
+    maybe_install_handler().unwrap();
+    assert_send_sync_clone::<SharedReport>();
+
+    let error = io::Error::new(io::ErrorKind::Other, "oh no!");
+    let shared = Report::new(error).wrap_err("outer").into_shared();
+
+    let chain: Vec<_> = shared.chain().map(|e| e.to_string()).collect();
+    assert_eq!(chain, ["outer", "oh no!"]);
+    assert!(shared.downcast_ref::<io::Error>().is_some());
+    assert_eq!(shared.source().unwrap().to_string(), "oh no!");
+
+    let report = Report::new(shared.clone()).wrap_err("retry failed");
+    let chain: Vec<_> = report.chain().map(|e| e.to_string()).collect();
+    assert_eq!(chain, ["retry failed", "outer", "oh no!"]);
+    assert!(report.downcast_ref::<SharedReport>().is_some());
+}
+
+#[test]
+fn test_shared_try_unwrap() {
+    maybe_install_handler().unwrap();
+
+    let shared = eyre!("oh no!").into_shared();
+    let clone = shared.clone();
+
+    let shared = shared.try_unwrap().unwrap_err();
+    drop(clone);
+
+    let report = shared.try_unwrap().unwrap();
+    assert_eq!(report.to_string(), "oh no!");
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, Report, ReportSnapshot};
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::io;
+use std::rc::Rc;
+
+#[derive(Debug)]
+struct LocalError {
+    msg: Rc<str>,
+    source: io::Error,
+}
+
+impl Display for LocalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl StdError for LocalError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.source)
+    }
+}
+
+fn local_error() -> LocalError {
+    LocalError {
+        msg: Rc::from("failed to read config"),
+        source: io::Error::new(io::ErrorKind::NotFound, "oh no!"),
+    }
+}
+
+#[test]
+fn test_msg_from_error_chain() {
+    maybe_install_handler().unwrap();
+
+    let error = local_error();
+    let report = Report::msg_from_error(&error);
+    let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["failed to read config", "oh no!"]);
+}
+
+#[test]
+fn test_msg_from_error_debug() {
+    maybe_install_handler().unwrap();
+
+    let error = local_error();
+    let report = Report::msg_from_error(&error);
+    assert_eq!(
+        format!("{:?}", report.chain().next().unwrap()),
+        format!("{:?}", error)
+    );
+    assert_eq!(
+        format!("{:?}", report.root_cause()),
+        format!("{:?}", error.source),
+    );
+}
+
+#[test]
+fn test_msg_from_error_dyn() {
+    maybe_install_handler().unwrap();
+
+    let error: Box<dyn StdError> = Box::new(local_error());
+    let report = Report::msg_from_error(&*error).wrap_err("context");
+    assert_eq!(report.chain().count(), 3);
+    assert!(report.downcast_ref::<io::Error>().is_none());
+}
+
+#[test]
+fn test_from_local() {
+    maybe_install_handler().unwrap();
+
+    let result: Result<(), LocalError> = Err(local_error());
+    let report = result.map_err(eyre::from_local).unwrap_err();
+    let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, ["failed to read config", "oh no!"]);
+
+    // The resulting report is threadsafe.
+    std::thread::spawn(move || drop(report)).join().unwrap();
+}
+
+#[test]
+fn test_report_snapshot() {
+    maybe_install_handler().unwrap();
+
+    let report = Report::new(local_error().source).wrap_err("failed to read config");
+    let snapshot = report.snapshot();
+    drop(report);
+
+    let snapshot = std::thread::spawn(move || snapshot.clone()).join().unwrap();
+    assert_eq!(
+        snapshot.chain().collect::<Vec<_>>(),
+        ["failed to read config", "oh no!"]
+    );
+    assert_eq!(snapshot.to_string(), "failed to read config");
+    assert_eq!(format!("{:#}", snapshot), "failed to read config: oh no!");
+    if let Some(location) = snapshot.location() {
+        assert!(location.starts_with(file!()));
+    }
+
+    let snapshot: ReportSnapshot = eyre!("oh no!").snapshot();
+    assert_eq!(snapshot.chain().count(), 1);
+}
+
+#[test]
+fn test_report_snapshot_location() {
+    maybe_install_handler().unwrap();
+
+    let line = line!() + 1;
+    let report = eyre!("oh no!");
+    let report = report.wrap_err("failed to read config");
+
+    if let Some(location) = report.snapshot().location() {
+        assert!(location.starts_with(&format!("{}:{}:", file!(), line)));
+    }
+}
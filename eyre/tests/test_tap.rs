@@ -0,0 +1,58 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, ReportTap, Result};
+
+fn fails() -> Result<u32> {
+    Err(eyre!("oh no!"))
+}
+
+#[test]
+fn test_tap_report() {
+    maybe_install_handler().unwrap();
+
+    let mut tapped = Vec::new();
+    let result = fails().tap_report(|report| tapped.push(report.to_string()));
+    assert_eq!(result.unwrap_err().to_string(), "oh no!");
+    assert_eq!(tapped, ["oh no!"]);
+
+    let result = Ok::<_, eyre::Report>(1).tap_report(|_| panic!("called on Ok"));
+    assert_eq!(result.unwrap(), 1);
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn test_log_err() {
+    use std::sync::Mutex;
+
+    struct Capture(Mutex<Vec<(log::Level, String)>>);
+
+    impl log::Log for Capture {
+        fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: Capture = Capture(Mutex::new(Vec::new()));
+
+    maybe_install_handler().unwrap();
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let result = fails().log_err();
+    assert!(result.is_err());
+
+    let records = LOGGER.0.lock().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].0, log::Level::Error);
+    assert!(records[0].1.starts_with("oh no!"));
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, Report, WrapErr};
+use std::io;
+
+fn io_error(kind: io::ErrorKind) -> Report {
+    Report::new(io::Error::from(kind))
+}
+
+#[test]
+fn test_transient() {
+    maybe_install_handler().unwrap();
+
+    // Without a classifier, reports are permanent unless marked
+    assert!(!io_error(io::ErrorKind::TimedOut).is_transient());
+
+    let mut report = eyre!("service unavailable");
+    report.set_transient(true);
+    let report = report.wrap_err("failed to fetch index");
+    assert!(report.is_transient());
+
+    eyre::set_transient_classifier(Box::new(|report| {
+        report.chain().any(|error| {
+            error
+                .downcast_ref::<io::Error>()
+                .map_or(false, |error| error.kind() == io::ErrorKind::TimedOut)
+        })
+    }))
+    .unwrap();
+    assert!(eyre::set_transient_classifier(Box::new(|_| true)).is_err());
+
+    let report = io_error(io::ErrorKind::TimedOut).wrap_err("failed to fetch index");
+    assert!(report.is_transient());
+    assert!(!io_error(io::ErrorKind::NotFound).is_transient());
+
+    // Explicit marks take precedence over the classifier
+    let mut report = io_error(io::ErrorKind::TimedOut);
+    report.set_transient(false);
+    assert!(!report.wrap_err("failed to fetch index").is_transient());
+
+    let result: Result<(), io::Error> = Err(io::ErrorKind::TimedOut.into());
+    assert!(result
+        .wrap_err("failed to fetch")
+        .unwrap_err()
+        .is_transient());
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::typed::{self, CaptureHandler};
+use eyre::{eyre, EyreHandler};
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+
+struct StatusHandler {
+    status: u16,
+    message: String,
+}
+
+impl EyreHandler for StatusHandler {
+    fn debug(&self, error: &(dyn StdError + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.status, error)
+    }
+}
+
+impl CaptureHandler for StatusHandler {
+    fn capture(error: &(dyn StdError + 'static)) -> Self {
+        StatusHandler {
+            status: 500,
+            message: error.to_string(),
+        }
+    }
+}
+
+fn read_config() -> typed::Result<(), StatusHandler> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "oh no!"))?;
+    Ok(())
+}
+
+#[test]
+fn test_capture() {
+    maybe_install_handler().unwrap();
+
+    let mut report = typed::Report::<StatusHandler>::msg("not found");
+    assert_eq!(report.handler().status, 500);
+    assert_eq!(report.handler().message, "not found");
+
+    report.handler_mut().status = 404;
+    let report = report.wrap_err("failed to handle request");
+    assert_eq!(report.handler().status, 404);
+    assert_eq!(format!("{:?}", report), "[404] failed to handle request");
+    assert_eq!(report.chain().count(), 2);
+}
+
+#[test]
+fn test_question_mark() {
+    maybe_install_handler().unwrap();
+
+    let report = read_config().unwrap_err();
+    assert_eq!(report.handler().message, "oh no!");
+    assert!(report.downcast_ref::<io::Error>().is_some());
+
+    let error = report.downcast::<io::Error>().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_from_report() {
+    maybe_install_handler().unwrap();
+
+    let report = typed::Report::<StatusHandler>::from_report(eyre!("oh no!"));
+    assert_eq!(report.handler().status, 500);
+    assert_eq!(report.handler().message, "oh no!");
+
+    let mut untyped = report.into_report();
+    untyped
+        .handler_mut()
+        .downcast_mut::<StatusHandler>()
+        .unwrap()
+        .status = 404;
+
+    let report = typed::Report::<StatusHandler>::from_report(untyped);
+    assert_eq!(report.handler().status, 404);
+}
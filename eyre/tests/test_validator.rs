@@ -0,0 +1,42 @@
+mod common;
+
+use self::common::maybe_install_handler;
+use eyre::{eyre, ValidationErrors, Validator};
+
+#[test]
+fn test_valid() {
+    maybe_install_handler().unwrap();
+
+    let mut validator = Validator::new();
+    validator.check(true, "unreachable");
+    let value = validator.check_field("port", Ok::<_, eyre::Report>(8080));
+
+    assert_eq!(value, Some(8080));
+    assert!(validator.is_valid());
+    assert!(validator.finish().is_ok());
+}
+
+#[test]
+fn test_accumulates_failures() {
+    maybe_install_handler().unwrap();
+
+    let mut validator = Validator::new();
+    validator
+        .check(false, "name must not be empty")
+        .check(true, "unreachable");
+    let port = validator.check_field("port", Err::<u16, _>(eyre!("out of range")));
+
+    assert_eq!(port, None);
+    assert!(!validator.is_valid());
+
+    let report = validator.finish().unwrap_err();
+    assert_eq!(
+        report.to_string(),
+        "2 validation errors\n- name must not be empty\n- port: out of range",
+    );
+
+    let errors = report.downcast_ref::<ValidationErrors>().unwrap();
+    assert_eq!(errors.len(), 2);
+    let fields: Vec<_> = errors.iter().map(|(field, _)| field).collect();
+    assert_eq!(fields, [None, Some("port")]);
+}
@@ -0,0 +1,169 @@
+mod common;
+mod drop;
+
+use self::common::maybe_install_handler;
+use self::drop::{DetectDrop, Flag};
+use eyre::{eyre, Report};
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::io;
+
+#[derive(Debug)]
+struct ConfigError {
+    source: io::Error,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to read config")
+    }
+}
+
+impl StdError for ConfigError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.source)
+    }
+}
+
+fn chain(report: &Report) -> Vec<String> {
+    report.chain().map(|error| error.to_string()).collect()
+}
+
+#[test]
+fn test_with_source_chain() {
+    maybe_install_handler().unwrap();
+
+    let report = eyre!("exit status: 2")
+        .wrap_err("failed to run make")
+        .with_source(io::Error::new(io::ErrorKind::Other, "no targets"));
+
+    assert_eq!(report.to_string(), "failed to run make");
+    assert_eq!(
+        chain(&report),
+        ["failed to run make", "exit status: 2", "no targets"]
+    );
+    assert_eq!(report.root_cause().to_string(), "no targets");
+
+    let report = report.with_source(io::Error::new(io::ErrorKind::Other, "stderr closed"));
+    assert_eq!(
+        chain(&report),
+        [
+            "failed to run make",
+            "exit status: 2",
+            "no targets",
+            "stderr closed"
+        ]
+    );
+}
+
+#[test]
+fn test_with_source_unwrapped() {
+    maybe_install_handler().unwrap();
+
+    let report =
+        eyre!("exit status: 2").with_source(io::Error::new(io::ErrorKind::Other, "no targets"));
+
+    assert_eq!(report.to_string(), "exit status: 2");
+    assert_eq!(format!("{:#}", report), "exit status: 2: no targets");
+    assert_eq!(chain(&report), ["exit status: 2", "no targets"]);
+}
+
+#[test]
+fn test_with_source_after_existing_sources() {
+    maybe_install_handler().unwrap();
+
+    let error = ConfigError {
+        source: io::Error::new(io::ErrorKind::NotFound, "no such file"),
+    };
+    let mut report = Report::new(error)
+        .wrap_err("failed to start")
+        .with_source(io::Error::new(io::ErrorKind::Other, "checked ~/.config"));
+
+    let expected = [
+        "failed to start",
+        "failed to read config",
+        "no such file",
+        "checked ~/.config",
+    ];
+    assert_eq!(chain(&report), expected);
+    assert_eq!(chain(&report), expected);
+    assert_eq!(report.root_cause().to_string(), "checked ~/.config");
+    assert_eq!(
+        format!("{:?}", report.chain().nth(2).unwrap()),
+        format!(
+            "{:?}",
+            io::Error::new(io::ErrorKind::NotFound, "no such file")
+        )
+    );
+
+    // The chain is still complete after the original error was modified.
+    report.downcast_mut::<ConfigError>().unwrap().source =
+        io::Error::new(io::ErrorKind::PermissionDenied, "permission denied");
+    let report = report.with_source(io::Error::new(io::ErrorKind::Other, "running as nobody"));
+    assert_eq!(
+        chain(&report),
+        [
+            "failed to start",
+            "failed to read config",
+            "permission denied",
+            "checked ~/.config",
+            "running as nobody",
+        ]
+    );
+
+    let report = std::thread::spawn(move || {
+        assert_eq!(report.chain().count(), 5);
+        report
+    })
+    .join()
+    .unwrap();
+    assert_eq!(report.root_cause().to_string(), "running as nobody");
+}
+
+#[test]
+fn test_with_source_downcast() {
+    maybe_install_handler().unwrap();
+
+    let mut report = Report::new(io::Error::new(io::ErrorKind::NotFound, "missing"))
+        .wrap_err("failed to read")
+        .with_source(DetectDrop::new("source", &Flag::new()));
+
+    assert_eq!(
+        report.downcast_ref::<io::Error>().unwrap().kind(),
+        io::ErrorKind::NotFound
+    );
+    assert!(report.downcast_mut::<DetectDrop>().is_some());
+    assert!(report.is::<DetectDrop>());
+
+    // Sources attached earlier are found first
+    let report = eyre!("exit status: 2")
+        .with_source(io::Error::new(io::ErrorKind::Other, "no targets"))
+        .with_source(io::Error::new(io::ErrorKind::Other, "stderr closed"));
+    let error = report.downcast::<io::Error>().unwrap();
+    assert_eq!(error.to_string(), "no targets");
+}
+
+#[test]
+fn test_with_source_drop() {
+    maybe_install_handler().unwrap();
+
+    let error_dropped = Flag::new();
+    let source_dropped = Flag::new();
+    let report = Report::new(DetectDrop::new("error", &error_dropped))
+        .wrap_err("context")
+        .with_source(DetectDrop::new("source", &source_dropped));
+    drop(report);
+    assert!(error_dropped.get());
+    assert!(source_dropped.get());
+
+    let error_dropped = Flag::new();
+    let source_dropped = Flag::new();
+    let report = Report::new(io::Error::new(io::ErrorKind::Other, "oh no!"))
+        .with_source(DetectDrop::new("error", &error_dropped))
+        .with_source(DetectDrop::new("source", &source_dropped));
+    let error = report.downcast::<DetectDrop>().unwrap();
+    assert!(!error_dropped.get());
+    assert!(source_dropped.get());
+    drop(error);
+    assert!(error_dropped.get());
+}